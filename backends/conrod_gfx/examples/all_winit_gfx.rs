@@ -145,7 +145,9 @@ fn main() {
             //Clear the window
             renderer.clear(&mut encoder, CLEAR_COLOR);
 
-            renderer.fill(&mut encoder, dims, dpi_factor, primitives, &image_map);
+            renderer
+                .fill(&mut encoder, dims, dpi_factor, primitives, &image_map)
+                .unwrap();
 
             renderer.draw(&mut factory, &mut encoder, &image_map);
 
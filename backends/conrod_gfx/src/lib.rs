@@ -10,9 +10,9 @@ use gfx::{
 };
 
 use conrod_core::{
-    color, image, render,
-    text::{self, rt, GlyphCache},
-    Rect, Scalar,
+    image,
+    mesh::{self, Mesh},
+    render, text, Rect, Scalar,
 };
 
 /// A `Command` describing a step in the drawing process.
@@ -22,6 +22,13 @@ pub enum Command<'a> {
     Draw(Draw<'a>),
     /// Update the scizzor within the pipeline.
     Scizzor(gfx::Rect),
+    /// Invoke the user's rendering code for a `CustomDraw` widget.
+    Custom {
+        /// The widget to be drawn along with its user data.
+        custom: &'a mesh::Custom,
+        /// The scizzor to which the custom drawing should be cropped.
+        scizzor: gfx::Rect,
+    },
 }
 
 /// An iterator yielding `Command`s, produced by the `Renderer::commands` method.
@@ -46,6 +53,7 @@ enum PreparedCommand {
     Image(image::Id, std::ops::Range<usize>),
     Plain(std::ops::Range<usize>),
     Scizzor(gfx::Rect),
+    Custom(mesh::Custom, gfx::Rect),
 }
 
 /// Draw text from the text cache texture `tex` in the fragment shader.
@@ -143,18 +151,28 @@ use self::defines::*;
 
 /// This type is used for translating `render::Primitives` into `Commands`s that indicate how to
 /// draw the GUI using `gfx`.
-pub struct Renderer<'a, R: Resources> {
+pub struct Renderer<R: Resources> {
     pipeline: PipelineState<R, pipe::Meta>,
-    glyph_cache: GlyphCache<'a>,
+    mesh: Mesh,
     cache_tex: gfx::handle::Texture<R, SurfaceFormat>,
     cache_tex_view: gfx::handle::ShaderResourceView<R, [f32; 4]>,
     data: pipe::Data<R>,
     commands: Vec<PreparedCommand>,
     vertices: Vec<Vertex>,
-    positioned_glyphs: Vec<text::PositionedGlyph>,
+    // The updated regions of the glyph cache pixel data converted to RGBA, reused between uploads.
+    cache_data: Vec<[u8; 4]>,
 }
 
-impl<'a, R: Resources> Renderer<'a, R> {
+// Provides the dimensions of the images within the renderer's `image::Map` to the mesh.
+struct ImageMap<'a, T: 'a>(&'a image::Map<(T, (u32, u32))>);
+
+impl<'a, T> mesh::ImageMap for ImageMap<'a, T> {
+    fn image_dimensions(&self, id: image::Id) -> Option<[u32; 2]> {
+        self.0.get(&id).map(|&(_, (w, h))| [w, h])
+    }
+}
+
+impl<R: Resources> Renderer<R> {
     /// Create a new renderer from a `gfx::Factory`, `gfx::handle::RenderTargetView` and
     /// a given `dpi_factor`
     pub fn new<F>(
@@ -200,35 +218,31 @@ impl<'a, R: Resources> Renderer<'a, R> {
             pipe::new(),
         )?;
 
-        let (glyph_cache, cache_tex, cache_tex_view) = {
+        let (mesh, cache_tex, cache_tex_view) = {
             let width = (width as f64 * dpi_factor) as u32;
             let height = (height as f64 * dpi_factor) as u32;
 
-            const SCALE_TOLERANCE: f32 = 0.1;
-            const POSITION_TOLERANCE: f32 = 0.1;
-
-            let cache = GlyphCache::builder()
-                .dimensions(width, height)
-                .scale_tolerance(SCALE_TOLERANCE)
-                .position_tolerance(POSITION_TOLERANCE)
-                .build();
+            // The glyph cache texture can only be re-created with a `Factory`, which `fill` does
+            // not have access to, so the glyph cache is kept at a fixed size.
+            let mesh = Mesh::with_glyph_cache_dimensions([width, height])
+                .max_glyph_cache_dimensions([width, height]);
 
             let data = vec![0; (width * height * 4) as usize];
 
             let (texture, texture_view) = create_texture(factory, width, height, &data);
 
-            (cache, texture, texture_view)
+            (mesh, texture, texture_view)
         };
 
         Ok(Renderer {
             pipeline,
-            glyph_cache,
+            mesh,
             cache_tex,
             cache_tex_view,
             data,
             commands: vec![],
             vertices: vec![],
-            positioned_glyphs: vec![],
+            cache_data: vec![],
         })
     }
 
@@ -246,366 +260,58 @@ impl<'a, R: Resources> Renderer<'a, R> {
     }
 
     /// Fill the inner vertex and command buffers by translating the given `primitives`.
+    ///
+    /// The primitives are first translated by a `conrod_core::mesh::Mesh`, whose vertices and
+    /// commands are then converted for use with gfx.
     pub fn fill<P, C>(
         &mut self,
         encoder: &mut gfx::Encoder<R, C>,
         dims: (f32, f32),
         dpi_factor: f64,
-        mut primitives: P,
+        primitives: P,
         image_map: &image::Map<(gfx::handle::ShaderResourceView<R, [f32; 4]>, (u32, u32))>,
-    ) where
+    ) -> Result<(), text::rt::gpu_cache::CacheWriteErr>
+    where
         P: render::PrimitiveWalker,
         C: gfx::CommandBuffer<R>,
     {
         let Renderer {
+            ref mut mesh,
             ref mut commands,
             ref mut vertices,
-            ref mut positioned_glyphs,
-            ref mut glyph_cache,
-            ref mut cache_tex,
+            ref cache_tex,
+            ref mut cache_data,
             ..
         } = *self;
 
-        commands.clear();
-        vertices.clear();
-
-        enum State {
-            Image { image_id: image::Id, start: usize },
-            Plain { start: usize },
-        }
-
-        let mut current_state = State::Plain { start: 0 };
-
-        // Switches to the `Plain` state and completes the previous `Command` if not already in the
-        // `Plain` state.
-        macro_rules! switch_to_plain_state {
-            () => {
-                match current_state {
-                    State::Plain { .. } => (),
-                    State::Image { image_id, start } => {
-                        commands.push(PreparedCommand::Image(image_id, start..vertices.len()));
-                        current_state = State::Plain {
-                            start: vertices.len(),
-                        };
-                    }
-                }
-            };
-        }
-
-        // Framebuffer dimensions and the "dots per inch" factor.
-        let (screen_w, screen_h) = dims;
-        let (win_w, win_h) = (screen_w as Scalar, screen_h as Scalar);
-        let half_win_w = win_w / 2.0;
-        let half_win_h = win_h / 2.0;
-
-        // Functions for converting for conrod scalar coords to GL vertex coords (-1.0 to 1.0).
-        let vx = |x: Scalar| (x * dpi_factor / half_win_w) as f32;
-        let vy = |y: Scalar| (y * dpi_factor / half_win_h) as f32;
-
-        let mut current_scizzor = gfx::Rect {
-            x: 0,
-            w: screen_w as u16,
-            y: 0,
-            h: screen_h as u16,
-        };
-
-        let rect_to_gfx_rect = |rect: Rect| {
-            let (w, h) = rect.w_h();
-            let left = (rect.left() * dpi_factor + half_win_w).round() as u16;
-            let bottom = (rect.bottom() * dpi_factor + half_win_h).round() as u16;
-            let width = (w * dpi_factor).round() as u16;
-            let height = (h * dpi_factor).round() as u16;
-            gfx::Rect {
-                x: std::cmp::max(left, 0),
-                w: std::cmp::min(width, screen_w as u16),
-                y: std::cmp::max(bottom, 0),
-                h: std::cmp::min(height, screen_h as u16),
-            }
-        };
-
-        // Draw each primitive in order of depth.
-        while let Some(primitive) = primitives.next_primitive() {
-            let render::Primitive {
-                kind,
-                scizzor,
-                rect,
-                ..
-            } = primitive;
-
-            // Check for a `Scizzor` command.
-            let new_scizzor = rect_to_gfx_rect(scizzor);
-            if new_scizzor != current_scizzor {
-                // Finish the current command.
-                match current_state {
-                    State::Plain { start } => {
-                        commands.push(PreparedCommand::Plain(start..vertices.len()))
-                    }
-                    State::Image { image_id, start } => {
-                        commands.push(PreparedCommand::Image(image_id, start..vertices.len()))
-                    }
-                }
-
-                // Update the scizzor and produce a command.
-                current_scizzor = new_scizzor;
-                commands.push(PreparedCommand::Scizzor(new_scizzor));
-
-                // Set the state back to plain drawing.
-                current_state = State::Plain {
-                    start: vertices.len(),
-                };
-            }
-
-            match kind {
-                render::PrimitiveKind::Rectangle { color } => {
-                    switch_to_plain_state!();
-
-                    let color = gamma_srgb_to_linear(color.to_fsa());
-                    let (l, r, b, t) = rect.l_r_b_t();
-
-                    let v = |x, y| {
-                        // Convert from conrod Scalar range to GL range -1.0 to 1.0.
-                        Vertex {
-                            pos: [vx(x), vy(y)],
-                            uv: [0.0, 0.0],
-                            color: color,
-                            mode: MODE_GEOMETRY,
-                        }
-                    };
-
-                    let mut push_v = |x, y| vertices.push(v(x, y));
-
-                    // Bottom left triangle.
-                    push_v(l, t);
-                    push_v(r, b);
-                    push_v(l, b);
-
-                    // Top right triangle.
-                    push_v(l, t);
-                    push_v(r, b);
-                    push_v(r, t);
-                }
-
-                render::PrimitiveKind::TrianglesSingleColor { color, triangles } => {
-                    if triangles.is_empty() {
-                        continue;
-                    }
-
-                    switch_to_plain_state!();
-
-                    let color = gamma_srgb_to_linear(color.into());
-
-                    let v = |p: [Scalar; 2]| Vertex {
-                        pos: [vx(p[0]), vy(p[1])],
-                        uv: [0.0, 0.0],
-                        color: color,
-                        mode: MODE_GEOMETRY,
-                    };
-
-                    for triangle in triangles {
-                        vertices.push(v(triangle[0]));
-                        vertices.push(v(triangle[1]));
-                        vertices.push(v(triangle[2]));
-                    }
-                }
-
-                render::PrimitiveKind::TrianglesMultiColor { triangles } => {
-                    if triangles.is_empty() {
-                        continue;
-                    }
-
-                    switch_to_plain_state!();
-
-                    let v = |(p, c): ([Scalar; 2], color::Rgba)| Vertex {
-                        pos: [vx(p[0]), vy(p[1])],
-                        uv: [0.0, 0.0],
-                        color: gamma_srgb_to_linear(c.into()),
-                        mode: MODE_GEOMETRY,
-                    };
-
-                    for triangle in triangles {
-                        vertices.push(v(triangle[0]));
-                        vertices.push(v(triangle[1]));
-                        vertices.push(v(triangle[2]));
-                    }
-                }
-
-                render::PrimitiveKind::Text {
-                    color,
-                    text,
-                    font_id,
-                } => {
-                    switch_to_plain_state!();
-
-                    positioned_glyphs.clear();
-                    positioned_glyphs.extend(text.positioned_glyphs(dpi_factor as f32));
-
-                    // Queue the glyphs to be cached
-                    for glyph in positioned_glyphs.iter() {
-                        glyph_cache.queue_glyph(font_id.index(), glyph.clone());
-                    }
-
-                    glyph_cache
-                        .cache_queued(|rect, data| {
-                            let offset = [rect.min.x as u16, rect.min.y as u16];
-                            let size = [rect.width() as u16, rect.height() as u16];
-
-                            let new_data =
-                                data.iter().map(|x| [255, 255, 255, *x]).collect::<Vec<_>>();
-
-                            update_texture(encoder, &cache_tex, offset, size, &new_data);
-                        })
-                        .unwrap();
-
-                    let color = gamma_srgb_to_linear(color.to_fsa());
-                    let cache_id = font_id.index();
-                    let origin = rt::point(0.0, 0.0);
-
-                    // A closure to convert RustType rects to GL rects
-                    let to_gl_rect = |screen_rect: rt::Rect<i32>| rt::Rect {
-                        min: origin
-                            + (rt::vector(
-                                screen_rect.min.x as f32 / screen_w - 0.5,
-                                1.0 - screen_rect.min.y as f32 / screen_h - 0.5,
-                            )) * 2.0,
-                        max: origin
-                            + (rt::vector(
-                                screen_rect.max.x as f32 / screen_w - 0.5,
-                                1.0 - screen_rect.max.y as f32 / screen_h - 0.5,
-                            )) * 2.0,
-                    };
-
-                    for g in positioned_glyphs.drain(..) {
-                        if let Ok(Some((uv_rect, screen_rect))) = glyph_cache.rect_for(cache_id, &g)
-                        {
-                            let gl_rect = to_gl_rect(screen_rect);
-                            let v = |p, t| Vertex {
-                                pos: p,
-                                uv: t,
-                                color: color,
-                                mode: MODE_TEXT,
-                            };
-                            let mut push_v = |p, t| vertices.push(v(p, t));
-                            push_v(
-                                [gl_rect.min.x, gl_rect.max.y],
-                                [uv_rect.min.x, uv_rect.max.y],
-                            );
-                            push_v(
-                                [gl_rect.min.x, gl_rect.min.y],
-                                [uv_rect.min.x, uv_rect.min.y],
-                            );
-                            push_v(
-                                [gl_rect.max.x, gl_rect.min.y],
-                                [uv_rect.max.x, uv_rect.min.y],
-                            );
-                            push_v(
-                                [gl_rect.max.x, gl_rect.min.y],
-                                [uv_rect.max.x, uv_rect.min.y],
-                            );
-                            push_v(
-                                [gl_rect.max.x, gl_rect.max.y],
-                                [uv_rect.max.x, uv_rect.max.y],
-                            );
-                            push_v(
-                                [gl_rect.min.x, gl_rect.max.y],
-                                [uv_rect.min.x, uv_rect.max.y],
-                            );
-                        }
-                    }
-                }
-
-                render::PrimitiveKind::Image {
-                    image_id,
-                    color,
-                    source_rect,
-                } => {
-                    // Switch to the `Image` state for this image if we're not in it already.
-                    let new_image_id = image_id;
-                    match current_state {
-                        // If we're already in the drawing mode for this image, we're done.
-                        State::Image { image_id, .. } if image_id == new_image_id => (),
-
-                        // If we were in the `Plain` drawing state, switch to Image drawing state.
-                        State::Plain { start } => {
-                            commands.push(PreparedCommand::Plain(start..vertices.len()));
-                            current_state = State::Image {
-                                image_id: new_image_id,
-                                start: vertices.len(),
-                            };
-                        }
-
-                        // If we were drawing a different image, switch state to draw *this* image.
-                        State::Image { image_id, start } => {
-                            commands.push(PreparedCommand::Image(image_id, start..vertices.len()));
-                            current_state = State::Image {
-                                image_id: new_image_id,
-                                start: vertices.len(),
-                            };
-                        }
-                    }
+        let fill = fill_streams(
+            mesh,
+            commands,
+            vertices,
+            dims,
+            dpi_factor,
+            &ImageMap(image_map),
+            primitives,
+        )?;
 
-                    let color = color.unwrap_or(color::WHITE).to_fsa();
-
-                    let (image_w, image_h) = image_map.get(&image_id).unwrap().1;
-                    let (image_w, image_h) = (image_w as Scalar, image_h as Scalar);
-
-                    // Get the sides of the source rectangle as uv coordinates.
-                    //
-                    // Texture coordinates range:
-                    // - left to right: 0.0 to 1.0
-                    // - bottom to top: 1.0 to 0.0
-                    // Note bottom and top are flipped in comparison to glium so that we don't need to flip images when loading
-                    let (uv_l, uv_r, uv_t, uv_b) = match source_rect {
-                        Some(src_rect) => {
-                            let (l, r, b, t) = src_rect.l_r_b_t();
-                            (
-                                (l / image_w) as f32,
-                                (r / image_w) as f32,
-                                (b / image_h) as f32,
-                                (t / image_h) as f32,
-                            )
-                        }
-                        None => (0.0, 1.0, 0.0, 1.0),
-                    };
-
-                    let v = |x, y, t| {
-                        // Convert from conrod Scalar range to GL range -1.0 to 1.0.
-                        let x = (x * dpi_factor as Scalar / half_win_w) as f32;
-                        let y = (y * dpi_factor as Scalar / half_win_h) as f32;
-                        Vertex {
-                            pos: [x, y],
-                            uv: t,
-                            color: color,
-                            mode: MODE_IMAGE,
-                        }
-                    };
-
-                    let mut push_v = |x, y, t| vertices.push(v(x, y, t));
-
-                    let (l, r, b, t) = rect.l_r_b_t();
-
-                    // Bottom left triangle.
-                    push_v(l, t, [uv_l, uv_t]);
-                    push_v(r, b, [uv_r, uv_b]);
-                    push_v(l, b, [uv_l, uv_b]);
-
-                    // Top right triangle.
-                    push_v(l, t, [uv_l, uv_t]);
-                    push_v(r, b, [uv_r, uv_b]);
-                    push_v(r, t, [uv_r, uv_t]);
+        // Write the updated regions of the glyph cache pixel data to the texture, storing coverage
+        // in the alpha channel.
+        if fill.glyph_cache_requires_upload {
+            let glyph_cache_w = mesh.glyph_cache().dimensions().0 as usize;
+            let pixels = mesh.glyph_cache_pixel_buffer();
+            for rect in mesh.glyph_cache_updated_rects() {
+                let offset = [rect.min.x as u16, rect.min.y as u16];
+                let size = [rect.width() as u16, rect.height() as u16];
+                cache_data.clear();
+                for y in rect.min.y..rect.max.y {
+                    let start = y as usize * glyph_cache_w + rect.min.x as usize;
+                    let row = &pixels[start..start + rect.width() as usize];
+                    cache_data.extend(row.iter().map(|&a| [255, 255, 255, a]));
                 }
-
-                // We have no special case widgets to handle.
-                render::PrimitiveKind::Other(_) => (),
-            }
-        }
-
-        // Enter the final command.
-        match current_state {
-            State::Plain { start } => commands.push(PreparedCommand::Plain(start..vertices.len())),
-            State::Image { image_id, start } => {
-                commands.push(PreparedCommand::Image(image_id, start..vertices.len()))
+                update_texture(encoder, cache_tex, offset, size, cache_data);
             }
         }
+        Ok(())
     }
 
     /// Draws using the inner list of `Command`s to the given `display`.
@@ -614,6 +320,8 @@ impl<'a, R: Resources> Renderer<'a, R> {
     /// and `commands` methods separately. This method is simply a convenience wrapper around those
     /// methods for the case that the user does not require accessing or modifying conrod's draw
     /// parameters, uniforms or generated draw commands.
+    ///
+    /// `CustomDraw` widgets are not drawn. See `draw_with_custom`.
     pub fn draw<F, C>(
         &self,
         factory: &mut F,
@@ -622,6 +330,22 @@ impl<'a, R: Resources> Renderer<'a, R> {
     ) where
         F: Factory<R>,
         C: gfx::CommandBuffer<R>,
+    {
+        self.draw_with_custom(factory, encoder, image_map, |_, _, _, _| ())
+    }
+
+    /// The same as `draw`, but calls `draw_custom` with the factory, the encoder, the widget and
+    /// its scizzor for each `CustomDraw` widget, in order of depth among the other widgets.
+    pub fn draw_with_custom<F, C, D>(
+        &self,
+        factory: &mut F,
+        encoder: &mut gfx::Encoder<R, C>,
+        image_map: &image::Map<(gfx::handle::ShaderResourceView<R, [f32; 4]>, (u32, u32))>,
+        mut draw_custom: D,
+    ) where
+        F: Factory<R>,
+        C: gfx::CommandBuffer<R>,
+        D: FnMut(&mut F, &mut gfx::Encoder<R, C>, &mesh::Custom, gfx::Rect),
     {
         let Renderer {
             ref pipeline,
@@ -637,6 +361,11 @@ impl<'a, R: Resources> Renderer<'a, R> {
                 // Update the `scizzor` before continuing to draw.
                 Command::Scizzor(scizzor) => data.scissor = scizzor,
 
+                // Hand the encoder to the user's rendering code.
+                Command::Custom { custom, scizzor } => {
+                    draw_custom(factory, encoder, custom, scizzor)
+                }
+
                 // Draw to the target with the given `draw` command.
                 Command::Draw(draw) => match draw {
                     // Draw text and plain 2D geometry.
@@ -683,18 +412,6 @@ impl<'a, R: Resources> Renderer<'a, R> {
     }
 }
 
-fn gamma_srgb_to_linear(c: [f32; 4]) -> [f32; 4] {
-    fn component(f: f32) -> f32 {
-        // Taken from https://github.com/PistonDevelopers/graphics/src/color.rs#L42
-        if f <= 0.04045 {
-            f / 12.92
-        } else {
-            ((f + 0.055) / 1.055).powf(2.4)
-        }
-    }
-    [component(c[0]), component(c[1]), component(c[2]), c[3]]
-}
-
 // Creates a gfx texture with the given data
 fn create_texture<F, R>(
     factory: &mut F,
@@ -790,6 +507,88 @@ fn update_texture<R, C>(
         .unwrap();
 }
 
+// Fill the mesh from the given primitives and convert its vertices and commands for use with
+// gfx, returning the result of the fill so that the glyph cache texture may be updated.
+fn fill_streams<P, M>(
+    mesh: &mut Mesh,
+    commands: &mut Vec<PreparedCommand>,
+    vertices: &mut Vec<Vertex>,
+    dims: (f32, f32),
+    dpi_factor: f64,
+    image_map: &M,
+    primitives: P,
+) -> Result<mesh::Fill, text::rt::gpu_cache::CacheWriteErr>
+where
+    P: render::PrimitiveWalker,
+    M: mesh::ImageMap,
+{
+    // Framebuffer dimensions and the "dots per inch" factor.
+    let (screen_w, screen_h) = dims;
+    let viewport = Rect::from_xy_dim([0.0, 0.0], [screen_w as Scalar, screen_h as Scalar]);
+    let fill = mesh.fill(viewport, dpi_factor, image_map, primitives)?;
+    fill_from_mesh(mesh, screen_h as u32, commands, vertices);
+    Ok(fill)
+}
+
+// Convert the vertices and commands produced by the mesh to those used by gfx.
+//
+// The vertex positions and image texture coordinates used by gfx have their *y* axis pointing
+// upwards, while its scizzor is positioned from the bottom of the framebuffer.
+fn fill_from_mesh(
+    mesh: &Mesh,
+    screen_h: u32,
+    commands: &mut Vec<PreparedCommand>,
+    vertices: &mut Vec<Vertex>,
+) {
+    commands.clear();
+    vertices.clear();
+
+    vertices.extend(mesh.vertices().iter().map(|v| {
+        let [x, y] = v.position;
+        let [u, tex_v] = v.tex_coords;
+        let uv = match v.mode {
+            mesh::MODE_IMAGE => [u, 1.0 - tex_v],
+            _ => [u, tex_v],
+        };
+        Vertex {
+            pos: [x, -y],
+            uv,
+            color: v.rgba,
+            mode: v.mode,
+        }
+    }));
+
+    commands.extend(mesh.commands().filter_map(|command| {
+        let cmd = match command {
+            mesh::Command::Scizzor(scizzor) => {
+                PreparedCommand::Scizzor(gfx_scizzor(scizzor, screen_h))
+            }
+            mesh::Command::Draw(mesh::Draw::Plain(range)) => PreparedCommand::Plain(range),
+            mesh::Command::Draw(mesh::Draw::Image(id, range)) => PreparedCommand::Image(id, range),
+            mesh::Command::Custom(custom) => {
+                let scizzor = gfx_scizzor(custom.scizzor, screen_h);
+                PreparedCommand::Custom(custom, scizzor)
+            }
+            // Clip masks are not supported, so draws are cropped to the bounding scizzor alone.
+            mesh::Command::ClipMask(_) => return None,
+        };
+        Some(cmd)
+    }));
+}
+
+// Convert the mesh's scizzor to a `gfx::Rect` positioned from the bottom of the framebuffer.
+fn gfx_scizzor(scizzor: mesh::Scizzor, screen_h: u32) -> gfx::Rect {
+    let [left, top] = scizzor.top_left;
+    let [width, height] = scizzor.dimensions;
+    let bottom = (screen_h as i32 - top - height as i32).max(0);
+    gfx::Rect {
+        x: left as u16,
+        y: bottom as u16,
+        w: width as u16,
+        h: height as u16,
+    }
+}
+
 impl<'a> Iterator for Commands<'a> {
     type Item = Command<'a>;
     fn next(&mut self) -> Option<Self::Item> {
@@ -805,6 +604,7 @@ impl<'a> Iterator for Commands<'a> {
             PreparedCommand::Image(id, ref range) => {
                 Command::Draw(Draw::Image(id, &vertices[range.clone()]))
             }
+            PreparedCommand::Custom(ref custom, scizzor) => Command::Custom { custom, scizzor },
         })
     }
 }
@@ -824,3 +624,52 @@ impl std::fmt::Display for RendererCreationError {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    include!("../../mesh_streams.rs");
+
+    #[test]
+    fn fill_streams_should_match_the_other_backends() {
+        let (ui, image_map) = ui();
+        let mut mesh = Mesh::new();
+        let (mut commands, mut vertices) = (vec![], vec![]);
+        let dims = (SCREEN_W as f32, SCREEN_H as f32);
+        let primitives = ui.draw();
+        super::fill_streams(
+            &mut mesh,
+            &mut commands,
+            &mut vertices,
+            dims,
+            1.0,
+            &image_map,
+            primitives,
+        )
+        .unwrap();
+
+        let commands: Vec<_> = commands
+            .iter()
+            .map(|command| match *command {
+                super::PreparedCommand::Scizzor(r) => {
+                    Command::Scizzor([r.x as u32, r.y as u32, r.w as u32, r.h as u32])
+                }
+                super::PreparedCommand::Plain(ref range) => Command::Plain(range.clone()),
+                super::PreparedCommand::Image(id, ref range) => Command::Image(id, range.clone()),
+                super::PreparedCommand::Custom(ref custom, r) => {
+                    let scizzor = [r.x as u32, r.y as u32, r.w as u32, r.h as u32];
+                    Command::Custom(custom.clone(), scizzor)
+                }
+            })
+            .collect();
+        let vertices: Vec<_> = vertices
+            .iter()
+            .map(|v| Vertex {
+                position: v.pos,
+                tex_coords: v.uv,
+                color: v.color,
+                mode: v.mode,
+            })
+            .collect();
+        assert_streams_match(&ui, &image_map, &commands, &vertices);
+    }
+}
@@ -138,7 +138,9 @@ fn main() {
         image_map: &conrod_core::image::Map<glium::Texture2d>,
         primitives: &conrod_core::render::OwnedPrimitives,
     ) {
-        renderer.fill(display, primitives.walk(), &image_map);
+        renderer
+            .fill(display, primitives.walk(), &image_map)
+            .unwrap();
         let mut target = display.draw();
         target.clear_color(0.0, 0.0, 0.0, 1.0);
         renderer.draw(display, &mut target, &image_map).unwrap();
@@ -103,7 +103,7 @@ fn main() {
                 // Render the `Ui` and then display it on the screen.
                 let primitives = ui.draw();
 
-                renderer.fill(display, primitives, &image_map);
+                renderer.fill(display, primitives, &image_map).unwrap();
                 let mut target = display.draw();
                 target.clear_color(0.0, 0.0, 0.0, 1.0);
                 renderer.draw(display, &mut target, &image_map).unwrap();
@@ -126,7 +126,7 @@ fn main() {
                 // Draw the `Ui` if it has changed.
                 let primitives = ui.draw();
 
-                renderer.fill(display, primitives, &image_map);
+                renderer.fill(display, primitives, &image_map).unwrap();
                 let mut target = display.draw();
                 target.clear_color(0.0, 0.0, 0.0, 1.0);
                 renderer.draw(display, &mut target, &image_map).unwrap();
@@ -243,9 +243,18 @@ fn set_widgets(ui: &mut conrod_core::UiCell, ids: &Ids, graph: &mut MyGraph, lay
     let mut session = session.next();
     for edge in session.edges() {
         let (a, b) = node::edge_socket_rects(&edge, ui);
-        let line = widget::Line::abs(a.xy(), b.xy())
-            .color(conrod_core::color::DARK_CHARCOAL)
-            .thickness(3.0);
+        let (start, end) = (a.xy(), b.xy());
+
+        // Curve out of the output socket on the right and into the input socket on the left.
+        use conrod_core::widget::path::{Command, Style};
+        let bend = ((end[0] - start[0]).abs() / 2.0).max(50.0);
+        let commands = [
+            Command::MoveTo(start),
+            Command::CubicTo([start[0] + bend, start[1]], [end[0] - bend, end[1]], end),
+        ];
+        let style = Style::outline_styled(widget::line::Style::new().thickness(3.0));
+        let line =
+            widget::Path::abs_styled(commands, style).color(conrod_core::color::DARK_CHARCOAL);
 
         // Each edge contains:
         //
@@ -98,7 +98,7 @@ fn main() {
                 // Draw the `Ui` if it has changed.
                 let primitives = ui.draw();
 
-                renderer.fill(&display, primitives, &image_map);
+                renderer.fill(&display, primitives, &image_map).unwrap();
                 let mut target = display.draw();
                 target.clear_color(0.0, 0.0, 0.0, 1.0);
                 renderer.draw(&display, &mut target, &image_map).unwrap();
@@ -0,0 +1,168 @@
+//!
+//! A demonstration of rendering a `Ui` to a texture and displaying it within another `Ui` via the
+//! `SubUi` widget, which also remaps the input received by the widget into the embedded `Ui`.
+//!
+
+#[macro_use]
+extern crate conrod_core;
+extern crate conrod_glium;
+extern crate glium;
+#[macro_use]
+extern crate conrod_winit;
+extern crate find_folder;
+
+mod support;
+
+use conrod_core::{color, widget, Colorable, Labelable, Positionable, Sizeable, Widget};
+use glium::Surface;
+
+const WIDTH: u32 = 800;
+const HEIGHT: u32 = 600;
+
+// The window dimensions of the embedded `Ui` in points, and the DPI factor of its texture.
+const SUB_WIDTH: u32 = 300;
+const SUB_HEIGHT: u32 = 200;
+const SUB_HIDPI_FACTOR: f64 = 2.0;
+
+fn main() {
+    // Build the window.
+    let event_loop = glium::glutin::event_loop::EventLoop::new();
+    let window = glium::glutin::window::WindowBuilder::new()
+        .with_title("SubUi Demonstration")
+        .with_inner_size(glium::glutin::dpi::LogicalSize::new(WIDTH, HEIGHT));
+    let context = glium::glutin::ContextBuilder::new()
+        .with_vsync(true)
+        .with_multisampling(4);
+    let display = glium::Display::new(window, context, &event_loop).unwrap();
+
+    // Construct the window's `Ui` along with the `Ui` that is rendered to a texture.
+    let mut ui = conrod_core::UiBuilder::new([WIDTH as f64, HEIGHT as f64]).build();
+    let mut sub_ui = conrod_core::UiBuilder::new([SUB_WIDTH as f64, SUB_HEIGHT as f64]).build();
+
+    // Add a `Font` to the embedded `Ui`'s `font::Map` from file.
+    let assets = find_folder::Search::KidsThenParents(3, 5)
+        .for_folder("assets")
+        .unwrap();
+    let font_path = assets.join("fonts/NotoSans/NotoSans-Regular.ttf");
+    sub_ui.fonts.insert_from_file(font_path).unwrap();
+
+    // Each `Ui` is rendered with a `Renderer` of its own.
+    let mut renderer = conrod_glium::Renderer::new(&display).unwrap();
+    let mut sub_renderer = conrod_glium::Renderer::new(&display).unwrap();
+
+    widget_ids!(struct Ids { background, sub_ui });
+    widget_ids!(struct SubIds { background, button });
+    let ids = Ids::new(ui.widget_id_generator());
+    let sub_ids = SubIds::new(sub_ui.widget_id_generator());
+
+    // The texture to which the embedded `Ui` is rendered, sized in pixels.
+    let (tex_w, tex_h) = (
+        (SUB_WIDTH as f64 * SUB_HIDPI_FACTOR) as u32,
+        (SUB_HEIGHT as f64 * SUB_HIDPI_FACTOR) as u32,
+    );
+    let texture = glium::texture::Texture2d::empty(&display, tex_w, tex_h).unwrap();
+    let mut image_map = conrod_core::image::Map::new();
+    let sub_ui_texture = image_map.insert(texture);
+
+    // The embedded `Ui` displays no images of its own.
+    let sub_image_map = conrod_core::image::Map::<glium::texture::Texture2d>::new();
+
+    let mut count = 0;
+
+    // Poll events from the window.
+    support::run_loop(display, event_loop, move |request, display| {
+        match request {
+            support::Request::Event {
+                event,
+                should_update_ui,
+                should_exit,
+            } => {
+                // Use the `winit` backend feature to convert the winit event to a conrod one.
+                if let Some(event) = support::convert_event(&event, &display.gl_window().window()) {
+                    ui.handle_event(event);
+                    *should_update_ui = true;
+                }
+
+                match event {
+                    glium::glutin::event::Event::WindowEvent { event, .. } => match event {
+                        // Break from the loop upon `Escape`.
+                        glium::glutin::event::WindowEvent::CloseRequested
+                        | glium::glutin::event::WindowEvent::KeyboardInput {
+                            input:
+                                glium::glutin::event::KeyboardInput {
+                                    virtual_keycode:
+                                        Some(glium::glutin::event::VirtualKeyCode::Escape),
+                                    ..
+                                },
+                            ..
+                        } => *should_exit = true,
+                        _ => {}
+                    },
+                    _ => {}
+                }
+            }
+            support::Request::SetUi { needs_redraw } => {
+                // Instantiate the window's widgets, collecting the input for the embedded `Ui`.
+                let inputs = {
+                    let ui = &mut ui.set_widgets();
+                    widget::Canvas::new()
+                        .color(color::DARK_CHARCOAL)
+                        .set(ids.background, ui);
+                    widget::SubUi::new(sub_ui_texture, [SUB_WIDTH as f64, SUB_HEIGHT as f64])
+                        .w_h(SUB_WIDTH as f64 * 1.5, SUB_HEIGHT as f64 * 1.5)
+                        .middle()
+                        .set(ids.sub_ui, ui)
+                };
+
+                // Feed the remapped input to the embedded `Ui` and instantiate its widgets.
+                for input in inputs {
+                    sub_ui.handle_event(input);
+                }
+                {
+                    let ui = &mut sub_ui.set_widgets();
+                    widget::Canvas::new()
+                        .color(color::LIGHT_BLUE)
+                        .set(sub_ids.background, ui);
+                    let label = count.to_string();
+                    for _click in widget::Button::new()
+                        .w_h(120.0, 60.0)
+                        .middle()
+                        .label(&label)
+                        .set(sub_ids.button, ui)
+                    {
+                        count += 1;
+                    }
+                }
+
+                // The window must be redrawn whenever the texture of the embedded `Ui` changes.
+                *needs_redraw = ui.has_changed() || sub_ui.has_changed();
+            }
+            support::Request::Redraw => {
+                // Render the embedded `Ui` to its texture using the texture's own dimensions and
+                // DPI factor rather than those of the window.
+                if let Some(primitives) = sub_ui.draw_if_changed() {
+                    let offscreen =
+                        conrod_glium::Offscreen::new(display, (tex_w, tex_h), SUB_HIDPI_FACTOR);
+                    sub_renderer
+                        .fill(&offscreen, primitives, &sub_image_map)
+                        .unwrap();
+                    let texture = &image_map[&sub_ui_texture];
+                    let mut framebuffer =
+                        glium::framebuffer::SimpleFrameBuffer::new(display, texture).unwrap();
+                    framebuffer.clear_color(0.0, 0.0, 0.0, 1.0);
+                    sub_renderer
+                        .draw(&offscreen, &mut framebuffer, &sub_image_map)
+                        .unwrap();
+                }
+
+                // Render the window's `Ui`, which displays the texture, to the screen.
+                let primitives = ui.draw();
+                renderer.fill(display, primitives, &image_map).unwrap();
+                let mut target = display.draw();
+                target.clear_color(0.0, 0.0, 0.0, 1.0);
+                renderer.draw(display, &mut target, &image_map).unwrap();
+                target.finish().unwrap();
+            }
+        }
+    })
+}
@@ -91,7 +91,7 @@ fn main() {
 
         // Render the `Ui` and then display it on the screen.
         if let Some(primitives) = ui.draw_if_changed() {
-            renderer.fill(&display.0, primitives, &image_map);
+            renderer.fill(&display.0, primitives, &image_map).unwrap();
             let mut target = display.0.draw();
             target.clear_color(0.0, 1.0, 0.0, 1.0);
             renderer.draw(&display.0, &mut target, &image_map).unwrap();
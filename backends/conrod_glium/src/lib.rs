@@ -4,7 +4,8 @@ extern crate conrod_core;
 #[macro_use]
 extern crate glium;
 
-use conrod_core::{color, image, render, text, Rect, Scalar};
+use conrod_core::mesh::{self, Mesh};
+use conrod_core::{image, render, text, Rect, Scalar};
 
 /// A `Command` describing a step in the drawing process.
 #[derive(Clone, Debug)]
@@ -13,6 +14,13 @@ pub enum Command<'a> {
     Draw(Draw<'a>),
     /// Update the scizzor within the `glium::DrawParameters`.
     Scizzor(glium::Rect),
+    /// Invoke the user's rendering code for a `CustomDraw` widget.
+    Custom {
+        /// The widget to be drawn along with its user data.
+        custom: &'a mesh::Custom,
+        /// The scizzor to which the custom drawing should be cropped.
+        scizzor: glium::Rect,
+    },
 }
 
 /// A `Command` for drawing to the target.
@@ -31,11 +39,11 @@ enum PreparedCommand {
     Image(image::Id, std::ops::Range<usize>),
     Plain(std::ops::Range<usize>),
     Scizzor(glium::Rect),
+    Custom(mesh::Custom, glium::Rect),
 }
 
-/// A rusttype `GlyphCache` along with a `glium::texture::Texture2d` for caching text on the `GPU`.
+/// A `glium::texture::Texture2d` for caching text on the `GPU`.
 pub struct GlyphCache {
-    cache: text::GlyphCache<'static>,
     texture: glium::texture::Texture2d,
 }
 
@@ -44,9 +52,13 @@ pub struct GlyphCache {
 pub struct Renderer {
     program: glium::Program,
     glyph_cache: GlyphCache,
+    mesh: Mesh,
+    sdf_text: bool,
     commands: Vec<PreparedCommand>,
     vertices: Vec<Vertex>,
-    positioned_glyphs: Vec<text::PositionedGlyph>,
+    // The updated regions of the glyph cache pixel data in the texture's client format, reused
+    // between uploads.
+    text_data: Vec<u8>,
 }
 
 /// An iterator yielding `Command`s, produced by the `Renderer::commands` method.
@@ -64,6 +76,15 @@ pub enum RendererCreationError {
     Program(glium::program::ProgramChooserCreationError),
 }
 
+/// Possible errors that may occur during a call to `Renderer::fill`.
+#[derive(Debug)]
+pub enum FillError {
+    /// Errors that might occur when caching the glyphs of the given text.
+    GlyphCache(text::rt::gpu_cache::CacheWriteErr),
+    /// Errors that might occur when re-creating the glyph cache texture after it grows.
+    Texture(glium::texture::TextureCreationError),
+}
+
 /// Possible errors that may occur during a call to `Renderer::draw`.
 #[derive(Debug)]
 pub enum DrawError {
@@ -81,6 +102,7 @@ pub struct Vertex {
     /// `0` for rendering text.
     /// `1` for rendering an image.
     /// `2` for rendering non-textured 2D geometry.
+    /// `3` for rendering text from a signed distance field.
     ///
     /// If any other value is given, the fragment shader will not output any color.
     pub mode: u32,
@@ -112,6 +134,9 @@ pub const MODE_TEXT: u32 = 0;
 pub const MODE_IMAGE: u32 = 1;
 /// Ignore `tex` and draw simple, colored 2D geometry.
 pub const MODE_GEOMETRY: u32 = 2;
+/// Draw text from the signed distance fields within the text cache texture `tex` in the fragment
+/// shader.
+pub const MODE_TEXT_SDF: u32 = 3;
 
 /// The vertex shader used within the `glium::Program` for OpenGL.
 pub const VERTEX_SHADER_120: &'static str = "
@@ -144,8 +169,18 @@ pub const FRAGMENT_SHADER_120: &'static str = "
     varying float v_mode;
 
     void main() {
+        // SDF Text
+        //
+        // Checked first so that the field's derivative is only computed for SDF text. The field
+        // changes by `sdf_width` across a single pixel.
+        if (v_mode == 3.0) {
+            float sdf = texture2D(tex, v_tex_coords).r;
+            float sdf_width = max(fwidth(sdf), 0.0001);
+            float sdf_alpha = clamp((sdf - 0.5) / sdf_width + 0.5, 0.0, 1.0);
+            gl_FragColor = v_color * vec4(1.0, 1.0, 1.0, sdf_alpha);
+
         // Text
-        if (v_mode == 0.0) {
+        } else if (v_mode == 0.0) {
             gl_FragColor = v_color * vec4(1.0, 1.0, 1.0, texture2D(tex, v_tex_coords).r);
 
         // Image
@@ -192,8 +227,18 @@ pub const FRAGMENT_SHADER_140: &'static str = "
     out vec4 f_color;
 
     void main() {
+        // SDF Text
+        //
+        // Checked first so that the field's derivative is only computed for SDF text. The field
+        // changes by `sdf_width` across a single pixel.
+        if (v_mode == uint(3)) {
+            float sdf = texture(tex, v_tex_coords).r;
+            float sdf_width = max(fwidth(sdf), 0.0001);
+            float sdf_alpha = clamp((sdf - 0.5) / sdf_width + 0.5, 0.0, 1.0);
+            f_color = v_color * vec4(1.0, 1.0, 1.0, sdf_alpha);
+
         // Text
-        if (v_mode == uint(0)) {
+        } else if (v_mode == uint(0)) {
             f_color = v_color * vec4(1.0, 1.0, 1.0, texture(tex, v_tex_coords).r);
 
         // Image
@@ -242,8 +287,18 @@ pub const FRAGMENT_SHADER_300_ES: &'static str = "
     out vec4 f_color;
 
     void main() {
+        // SDF Text
+        //
+        // Checked first so that the field's derivative is only computed for SDF text. The field
+        // changes by `sdf_width` across a single pixel.
+        if (v_mode == uint(3)) {
+            float sdf = texture(tex, v_tex_coords).r;
+            float sdf_width = max(fwidth(sdf), 0.0001);
+            float sdf_alpha = clamp((sdf - 0.5) / sdf_width + 0.5, 0.0, 1.0);
+            f_color = v_color * vec4(1.0, 1.0, 1.0, sdf_alpha);
+
         // Text
-        if (v_mode == uint(0)) {
+        } else if (v_mode == uint(0)) {
             f_color = v_color * vec4(1.0, 1.0, 1.0, texture(tex, v_tex_coords).r);
 
         // Image
@@ -272,6 +327,21 @@ where
     }
 }
 
+// Provides the dimensions of the images within an `image::Map` of glium textures to the mesh.
+struct ImageMap<'a, T: 'a>(&'a image::Map<T>);
+
+impl<'a, T> mesh::ImageMap for ImageMap<'a, T>
+where
+    T: TextureDimensions,
+{
+    fn image_dimensions(&self, id: image::Id) -> Option<[u32; 2]> {
+        self.0.get(&id).map(|img| {
+            let (w, h) = img.dimensions();
+            [w, h]
+        })
+    }
+}
+
 /// Construct the glium shader program that can be used to render `Vertex`es.
 pub fn program<F>(facade: &F) -> Result<glium::Program, glium::program::ProgramChooserCreationError>
 where
@@ -342,17 +412,6 @@ pub fn text_texture_uncompressed_float_format(
     }
 }
 
-// Creating the rusttype glyph cache used within a `GlyphCache`.
-fn rusttype_glyph_cache(w: u32, h: u32) -> text::GlyphCache<'static> {
-    const SCALE_TOLERANCE: f32 = 0.1;
-    const POSITION_TOLERANCE: f32 = 0.1;
-    text::GlyphCache::builder()
-        .dimensions(w, h)
-        .scale_tolerance(SCALE_TOLERANCE)
-        .position_tolerance(POSITION_TOLERANCE)
-        .build()
-}
-
 // Create the texture used within a `GlyphCache` of the given size.
 fn glyph_cache_texture<F>(
     facade: &F,
@@ -396,17 +455,9 @@ impl GlyphCache {
     where
         F: glium::backend::Facade,
     {
-        // First, the rusttype `Cache` which performs the logic for rendering and laying out glyphs
-        // in the cache.
-        let cache = rusttype_glyph_cache(width, height);
-
-        // Now the texture to which glyphs will be rendered.
+        // The texture to which glyphs will be rendered.
         let texture = glyph_cache_texture(facade, width, height)?;
-
-        Ok(GlyphCache {
-            cache: cache,
-            texture: texture,
-        })
+        Ok(GlyphCache { texture: texture })
     }
 
     /// Construct a `GlyphCache` with a size equal to the given `Display`'s current framebuffer
@@ -425,7 +476,7 @@ impl GlyphCache {
     }
 }
 
-pub trait Display {
+pub trait Display: glium::backend::Facade {
     fn opengl_version(&self) -> &glium::Version;
     fn framebuffer_dimensions(&self) -> (u32, u32);
     fn hidpi_factor(&self) -> f64;
@@ -445,6 +496,58 @@ impl Display for glium::Display {
     }
 }
 
+/// Describes an offscreen target, such as a texture, for a `Ui` rendered apart from the window.
+///
+/// Passing an `Offscreen` to `Renderer::fill` lays out the primitives for the given framebuffer
+/// dimensions and DPI factor rather than those of the window, after which the `Renderer` may
+/// `draw` to the target's surface (e.g. a `glium::framebuffer::SimpleFrameBuffer`). Each `Ui`
+/// rendered in this manner should have a `Renderer` of its own.
+pub struct Offscreen<'a, D: 'a> {
+    display: &'a D,
+    framebuffer_dimensions: (u32, u32),
+    hidpi_factor: f64,
+}
+
+impl<'a, D> Offscreen<'a, D>
+where
+    D: Display,
+{
+    /// Describe an offscreen target of the given dimensions in pixels and DPI factor.
+    pub fn new(display: &'a D, framebuffer_dimensions: (u32, u32), hidpi_factor: f64) -> Self {
+        Offscreen {
+            display,
+            framebuffer_dimensions,
+            hidpi_factor,
+        }
+    }
+}
+
+impl<'a, D> glium::backend::Facade for Offscreen<'a, D>
+where
+    D: Display,
+{
+    fn get_context(&self) -> &std::rc::Rc<glium::backend::Context> {
+        self.display.get_context()
+    }
+}
+
+impl<'a, D> Display for Offscreen<'a, D>
+where
+    D: Display,
+{
+    fn opengl_version(&self) -> &glium::Version {
+        self.display.opengl_version()
+    }
+
+    fn framebuffer_dimensions(&self) -> (u32, u32) {
+        self.framebuffer_dimensions
+    }
+
+    fn hidpi_factor(&self) -> f64 {
+        self.hidpi_factor
+    }
+}
+
 impl Renderer {
     /// Construct a new empty `Renderer`.
     ///
@@ -477,15 +580,29 @@ impl Renderer {
         F: glium::backend::Facade,
     {
         let program = program(facade)?;
+        let (width, height) = gc.texture.dimensions();
+        let mesh = Mesh::with_glyph_cache_dimensions([width, height]);
         Ok(Renderer {
             program: program,
             glyph_cache: gc,
+            mesh: mesh,
+            sdf_text: false,
             commands: Vec::new(),
             vertices: Vec::new(),
-            positioned_glyphs: Vec::new(),
+            text_data: Vec::new(),
         })
     }
 
+    /// Specify whether text should be drawn from signed distance fields.
+    ///
+    /// Each glyph is then rasterised only once, so that text may be scaled smoothly. See
+    /// `Mesh::sdf_text`.
+    pub fn sdf_text(mut self, sdf_text: bool) -> Self {
+        self.mesh = self.mesh.sdf_text(sdf_text);
+        self.sdf_text = sdf_text;
+        self
+    }
+
     /// Produce an `Iterator` yielding `Command`s.
     pub fn commands(&self) -> Commands {
         let Renderer {
@@ -500,407 +617,93 @@ impl Renderer {
     }
 
     /// Fill the inner vertex and command buffers by translating the given `primitives`.
-    pub fn fill<D, P, T>(&mut self, display: &D, mut primitives: P, image_map: &image::Map<T>)
+    ///
+    /// The primitives are first translated by a `conrod_core::mesh::Mesh`, whose vertices and
+    /// commands are then converted for use with glium.
+    pub fn fill<D, P, T>(
+        &mut self,
+        display: &D,
+        primitives: P,
+        image_map: &image::Map<T>,
+    ) -> Result<(), FillError>
     where
         P: render::PrimitiveWalker,
         D: Display,
         T: TextureDimensions,
     {
         let Renderer {
+            ref mut mesh,
+            ref mut glyph_cache,
             ref mut commands,
             ref mut vertices,
-            ref mut glyph_cache,
-            ref mut positioned_glyphs,
+            ref mut text_data,
             ..
         } = *self;
 
-        commands.clear();
-        vertices.clear();
-
-        // This is necessary for supporting rusttype's GPU cache with OpenGL versions older than GL
-        // 3.0 and GL ES 3.0. It is used to convert from the `U8` data format given by `rusttype`
-        // to the `U8U8U8` format that is necessary for older versions of OpenGL.
-        //
-        // The buffer is only used if an older version was detected, otherwise the text GPU cache
-        // uses the rusttype `data` buffer directly.
-        let mut text_data_u8u8u8 = Vec::new();
-
-        // Determine the texture format that we're using.
-        let opengl_version = display.opengl_version();
-        let client_format = text_texture_client_format(opengl_version);
-
-        enum State {
-            Image { image_id: image::Id, start: usize },
-            Plain { start: usize },
+        let fill = fill_streams(
+            mesh,
+            commands,
+            vertices,
+            display.framebuffer_dimensions(),
+            display.hidpi_factor(),
+            &ImageMap(image_map),
+            primitives,
+        )?;
+
+        // If the glyph cache grew, re-create the texture with the new dimensions.
+        if let Some([w, h]) = fill.glyph_cache_resized {
+            glyph_cache.texture = glyph_cache_texture(display, w, h)?;
         }
 
-        let mut current_state = State::Plain { start: 0 };
-
-        // Switches to the `Plain` state and completes the previous `Command` if not already in the
-        // `Plain` state.
-        macro_rules! switch_to_plain_state {
-            () => {
-                match current_state {
-                    State::Plain { .. } => (),
-                    State::Image { image_id, start } => {
-                        commands.push(PreparedCommand::Image(image_id, start..vertices.len()));
-                        current_state = State::Plain {
-                            start: vertices.len(),
-                        };
-                    }
-                }
-            };
-        }
-
-        // Framebuffer dimensions and the "dots per inch" factor.
-        let (screen_w, screen_h) = display.framebuffer_dimensions();
-        let (win_w, win_h) = (screen_w as Scalar, screen_h as Scalar);
-        let half_win_w = win_w / 2.0;
-        let half_win_h = win_h / 2.0;
-        let dpi_factor = display.hidpi_factor() as Scalar;
-
-        // Functions for converting for conrod scalar coords to GL vertex coords (-1.0 to 1.0).
-        let vx = |x: Scalar| (x * dpi_factor / half_win_w) as f32;
-        let vy = |y: Scalar| (y * dpi_factor / half_win_h) as f32;
-
-        let mut current_scizzor = glium::Rect {
-            left: 0,
-            width: screen_w,
-            bottom: 0,
-            height: screen_h,
-        };
-
-        let rect_to_glium_rect = |rect: Rect| {
-            let (w, h) = rect.w_h();
-            let left = (rect.left() * dpi_factor + half_win_w).round() as u32;
-            let bottom = (rect.bottom() * dpi_factor + half_win_h).round() as u32;
-            let width = (w * dpi_factor).round() as u32;
-            let height = (h * dpi_factor).round() as u32;
-            glium::Rect {
-                left: std::cmp::max(left, 0),
-                bottom: std::cmp::max(bottom, 0),
-                width: std::cmp::min(width, screen_w),
-                height: std::cmp::min(height, screen_h),
-            }
-        };
-
-        // Draw each primitive in order of depth.
-        while let Some(primitive) = primitives.next_primitive() {
-            let render::Primitive {
-                kind,
-                scizzor,
-                rect,
-                ..
-            } = primitive;
-
-            // Check for a `Scizzor` command.
-            let new_scizzor = rect_to_glium_rect(scizzor);
-            if new_scizzor != current_scizzor {
-                // Finish the current command.
-                match current_state {
-                    State::Plain { start } => {
-                        commands.push(PreparedCommand::Plain(start..vertices.len()))
-                    }
-                    State::Image { image_id, start } => {
-                        commands.push(PreparedCommand::Image(image_id, start..vertices.len()))
-                    }
-                }
-
-                // Update the scizzor and produce a command.
-                current_scizzor = new_scizzor;
-                commands.push(PreparedCommand::Scizzor(new_scizzor));
-
-                // Set the state back to plain drawing.
-                current_state = State::Plain {
-                    start: vertices.len(),
-                };
-            }
-
-            match kind {
-                render::PrimitiveKind::Rectangle { color } => {
-                    switch_to_plain_state!();
-
-                    let color = gamma_srgb_to_linear(color.to_fsa());
-                    let (l, r, b, t) = rect.l_r_b_t();
-
-                    let v = |x, y| {
-                        // Convert from conrod Scalar range to GL range -1.0 to 1.0.
-                        Vertex {
-                            position: [vx(x), vy(y)],
-                            tex_coords: [0.0, 0.0],
-                            color: color,
-                            mode: MODE_GEOMETRY,
+        // Write the updated regions of the glyph cache pixel data to the texture.
+        if fill.glyph_cache_requires_upload {
+            let glyph_cache_w = mesh.glyph_cache().dimensions().0 as usize;
+            let pixels = mesh.glyph_cache_pixel_buffer();
+            let client_format = text_texture_client_format(display.opengl_version());
+            for rect in mesh.glyph_cache_updated_rects() {
+                let (w, h) = (rect.width(), rect.height());
+                let rows = (rect.min.y..rect.max.y).map(|y| {
+                    let start = y as usize * glyph_cache_w + rect.min.x as usize;
+                    &pixels[start..start + w as usize]
+                });
+                let data = match client_format {
+                    // The mesh gives data in the `U8` format so we can use it directly.
+                    glium::texture::ClientFormat::U8 => {
+                        text_data.clear();
+                        for row in rows {
+                            text_data.extend_from_slice(row);
                         }
-                    };
-
-                    let mut push_v = |x, y| vertices.push(v(x, y));
-
-                    // Bottom left triangle.
-                    push_v(l, t);
-                    push_v(r, b);
-                    push_v(l, b);
-
-                    // Top right triangle.
-                    push_v(l, t);
-                    push_v(r, b);
-                    push_v(r, t);
-                }
-
-                render::PrimitiveKind::TrianglesSingleColor { color, triangles } => {
-                    if triangles.is_empty() {
-                        continue;
-                    }
-
-                    switch_to_plain_state!();
-
-                    let color = gamma_srgb_to_linear(color.into());
-
-                    let v = |p: [Scalar; 2]| Vertex {
-                        position: [vx(p[0]), vy(p[1])],
-                        tex_coords: [0.0, 0.0],
-                        color: color,
-                        mode: MODE_GEOMETRY,
-                    };
-
-                    for triangle in triangles {
-                        vertices.push(v(triangle[0]));
-                        vertices.push(v(triangle[1]));
-                        vertices.push(v(triangle[2]));
+                        std::borrow::Cow::Borrowed(&text_data[..])
                     }
-                }
-
-                render::PrimitiveKind::TrianglesMultiColor { triangles } => {
-                    if triangles.is_empty() {
-                        continue;
-                    }
-
-                    switch_to_plain_state!();
-
-                    let v = |(p, c): ([Scalar; 2], color::Rgba)| Vertex {
-                        position: [vx(p[0]), vy(p[1])],
-                        tex_coords: [0.0, 0.0],
-                        color: gamma_srgb_to_linear(c.into()),
-                        mode: MODE_GEOMETRY,
-                    };
-
-                    for triangle in triangles {
-                        vertices.push(v(triangle[0]));
-                        vertices.push(v(triangle[1]));
-                        vertices.push(v(triangle[2]));
-                    }
-                }
-
-                render::PrimitiveKind::Text {
-                    color,
-                    text,
-                    font_id,
-                } => {
-                    switch_to_plain_state!();
-
-                    positioned_glyphs.clear();
-                    positioned_glyphs.extend(text.positioned_glyphs(dpi_factor as f32));
-
-                    let GlyphCache {
-                        ref mut cache,
-                        ref mut texture,
-                    } = *glyph_cache;
-
-                    // Queue the glyphs to be cached.
-                    for glyph in positioned_glyphs.iter() {
-                        cache.queue_glyph(font_id.index(), glyph.clone());
-                    }
-
-                    // Cache the glyphs on the GPU.
-                    cache
-                        .cache_queued(|rect, data| {
-                            let w = rect.width();
-                            let h = rect.height();
-                            let glium_rect = glium::Rect {
-                                left: rect.min.x,
-                                bottom: rect.min.y,
-                                width: w,
-                                height: h,
-                            };
-
-                            let data = match client_format {
-                                // `rusttype` gives data in the `U8` format so we can use it directly.
-                                glium::texture::ClientFormat::U8 => {
-                                    std::borrow::Cow::Borrowed(data)
-                                }
-                                // Otherwise we have to convert to the supported format.
-                                glium::texture::ClientFormat::U8U8U8 => {
-                                    text_data_u8u8u8.clear();
-                                    for &b in data.iter() {
-                                        text_data_u8u8u8.push(b);
-                                        text_data_u8u8u8.push(b);
-                                        text_data_u8u8u8.push(b);
-                                    }
-                                    std::borrow::Cow::Borrowed(&text_data_u8u8u8[..])
-                                }
-                                // The text cache is only ever created with U8 or U8U8U8 formats.
-                                _ => unreachable!(),
-                            };
-
-                            let image = glium::texture::RawImage2d {
-                                data: data,
-                                width: w,
-                                height: h,
-                                format: client_format,
-                            };
-                            texture.main_level().write(glium_rect, image);
-                        })
-                        .unwrap();
-
-                    let color = gamma_srgb_to_linear(color.to_fsa());
-
-                    let cache_id = font_id.index();
-
-                    let origin = text::rt::point(0.0, 0.0);
-                    let to_gl_rect = |screen_rect: text::rt::Rect<i32>| text::rt::Rect {
-                        min: origin
-                            + (text::rt::vector(
-                                screen_rect.min.x as f32 / screen_w as f32 - 0.5,
-                                1.0 - screen_rect.min.y as f32 / screen_h as f32 - 0.5,
-                            )) * 2.0,
-                        max: origin
-                            + (text::rt::vector(
-                                screen_rect.max.x as f32 / screen_w as f32 - 0.5,
-                                1.0 - screen_rect.max.y as f32 / screen_h as f32 - 0.5,
-                            )) * 2.0,
-                    };
-
-                    for g in positioned_glyphs.drain(..) {
-                        if let Ok(Some((uv_rect, screen_rect))) = cache.rect_for(cache_id, &g) {
-                            let gl_rect = to_gl_rect(screen_rect);
-                            let v = |p, t| Vertex {
-                                position: p,
-                                tex_coords: t,
-                                color: color,
-                                mode: MODE_TEXT,
-                            };
-                            let mut push_v = |p, t| vertices.push(v(p, t));
-                            push_v(
-                                [gl_rect.min.x, gl_rect.max.y],
-                                [uv_rect.min.x, uv_rect.max.y],
-                            );
-                            push_v(
-                                [gl_rect.min.x, gl_rect.min.y],
-                                [uv_rect.min.x, uv_rect.min.y],
-                            );
-                            push_v(
-                                [gl_rect.max.x, gl_rect.min.y],
-                                [uv_rect.max.x, uv_rect.min.y],
-                            );
-                            push_v(
-                                [gl_rect.max.x, gl_rect.min.y],
-                                [uv_rect.max.x, uv_rect.min.y],
-                            );
-                            push_v(
-                                [gl_rect.max.x, gl_rect.max.y],
-                                [uv_rect.max.x, uv_rect.max.y],
-                            );
-                            push_v(
-                                [gl_rect.min.x, gl_rect.max.y],
-                                [uv_rect.min.x, uv_rect.max.y],
-                            );
+                    // Otherwise we have to convert to the supported format.
+                    glium::texture::ClientFormat::U8U8U8 => {
+                        text_data.clear();
+                        for &b in rows.flat_map(|row| row.iter()) {
+                            text_data.push(b);
+                            text_data.push(b);
+                            text_data.push(b);
                         }
+                        std::borrow::Cow::Borrowed(&text_data[..])
                     }
-                }
-
-                render::PrimitiveKind::Image {
-                    image_id,
-                    color,
-                    source_rect,
-                } => {
-                    // Switch to the `Image` state for this image if we're not in it already.
-                    let new_image_id = image_id;
-                    match current_state {
-                        // If we're already in the drawing mode for this image, we're done.
-                        State::Image { image_id, .. } if image_id == new_image_id => (),
-
-                        // If we were in the `Plain` drawing state, switch to Image drawing state.
-                        State::Plain { start } => {
-                            commands.push(PreparedCommand::Plain(start..vertices.len()));
-                            current_state = State::Image {
-                                image_id: new_image_id,
-                                start: vertices.len(),
-                            };
-                        }
-
-                        // If we were drawing a different image, switch state to draw *this* image.
-                        State::Image { image_id, start } => {
-                            commands.push(PreparedCommand::Image(image_id, start..vertices.len()));
-                            current_state = State::Image {
-                                image_id: new_image_id,
-                                start: vertices.len(),
-                            };
-                        }
-                    }
-
-                    let color = color.unwrap_or(color::WHITE).to_fsa();
-
-                    if let Some(image) = image_map.get(&image_id) {
-                        let (image_w, image_h) = image.dimensions();
-                        let (image_w, image_h) = (image_w as Scalar, image_h as Scalar);
-
-                        // Get the sides of the source rectangle as uv coordinates.
-                        //
-                        // Texture coordinates range:
-                        // - left to right: 0.0 to 1.0
-                        // - bottom to top: 0.0 to 1.0
-                        let (uv_l, uv_r, uv_b, uv_t) = match source_rect {
-                            Some(src_rect) => {
-                                let (l, r, b, t) = src_rect.l_r_b_t();
-                                (
-                                    (l / image_w) as f32,
-                                    (r / image_w) as f32,
-                                    (b / image_h) as f32,
-                                    (t / image_h) as f32,
-                                )
-                            }
-                            None => (0.0, 1.0, 0.0, 1.0),
-                        };
-
-                        let v = |x, y, t| {
-                            // Convert from conrod Scalar range to GL range -1.0 to 1.0.
-                            let x = (x * dpi_factor as Scalar / half_win_w) as f32;
-                            let y = (y * dpi_factor as Scalar / half_win_h) as f32;
-                            Vertex {
-                                position: [x, y],
-                                tex_coords: t,
-                                color: color,
-                                mode: MODE_IMAGE,
-                            }
-                        };
-
-                        let mut push_v = |x, y, t| vertices.push(v(x, y, t));
-
-                        let (l, r, b, t) = rect.l_r_b_t();
-
-                        // Bottom left triangle.
-                        push_v(l, t, [uv_l, uv_t]);
-                        push_v(r, b, [uv_r, uv_b]);
-                        push_v(l, b, [uv_l, uv_b]);
-
-                        // Top right triangle.
-                        push_v(l, t, [uv_l, uv_t]);
-                        push_v(r, b, [uv_r, uv_b]);
-                        push_v(r, t, [uv_r, uv_t]);
-                    }
-                }
-
-                // We have no special case widgets to handle.
-                render::PrimitiveKind::Other(_) => (),
-            }
-        }
-
-        // Enter the final command.
-        match current_state {
-            State::Plain { start } => commands.push(PreparedCommand::Plain(start..vertices.len())),
-            State::Image { image_id, start } => {
-                commands.push(PreparedCommand::Image(image_id, start..vertices.len()))
+                    // The text cache is only ever created with U8 or U8U8U8 formats.
+                    _ => unreachable!(),
+                };
+                let image = glium::texture::RawImage2d {
+                    data: data,
+                    width: w,
+                    height: h,
+                    format: client_format,
+                };
+                let glium_rect = glium::Rect {
+                    left: rect.min.x,
+                    bottom: rect.min.y,
+                    width: w,
+                    height: h,
+                };
+                glyph_cache.texture.main_level().write(glium_rect, image);
             }
         }
+        Ok(())
     }
 
     /// Draws using the inner list of `Command`s to the given `display`.
@@ -909,6 +712,8 @@ impl Renderer {
     /// and `commands` methods separately. This method is simply a convenience wrapper around those
     /// methods for the case that the user does not require accessing or modifying conrod's draw
     /// parameters, uniforms or generated draw commands.
+    ///
+    /// `CustomDraw` widgets are not drawn. See `draw_with_custom`.
     pub fn draw<F, S, T>(
         &self,
         facade: &F,
@@ -919,13 +724,36 @@ impl Renderer {
         F: glium::backend::Facade,
         S: glium::Surface,
         for<'a> glium::uniforms::Sampler<'a, T>: glium::uniforms::AsUniformValue,
+    {
+        self.draw_with_custom(facade, surface, image_map, |_, _, _| ())
+    }
+
+    /// The same as `draw`, but calls `draw_custom` with the surface, the widget and its scizzor for
+    /// each `CustomDraw` widget, in order of depth among the other widgets.
+    pub fn draw_with_custom<F, S, T, C>(
+        &self,
+        facade: &F,
+        surface: &mut S,
+        image_map: &image::Map<T>,
+        mut draw_custom: C,
+    ) -> Result<(), DrawError>
+    where
+        F: glium::backend::Facade,
+        S: glium::Surface,
+        for<'a> glium::uniforms::Sampler<'a, T>: glium::uniforms::AsUniformValue,
+        C: FnMut(&mut S, &mesh::Custom, glium::Rect),
     {
         let mut draw_params = draw_parameters();
         let no_indices = glium::index::NoIndices(glium::index::PrimitiveType::TrianglesList);
+        // Signed distance fields are magnified, so must be interpolated.
+        let text_magnify_filter = match self.sdf_text {
+            true => glium::uniforms::MagnifySamplerFilter::Linear,
+            false => glium::uniforms::MagnifySamplerFilter::Nearest,
+        };
         let uniforms = uniform! {
             tex: self.glyph_cache.texture()
                 .sampled()
-                .magnify_filter(glium::uniforms::MagnifySamplerFilter::Nearest)
+                .magnify_filter(text_magnify_filter)
                 .minify_filter(glium::uniforms::MinifySamplerFilter::Linear)
         };
 
@@ -936,6 +764,9 @@ impl Renderer {
                 // Update the `scizzor` before continuing to draw.
                 Command::Scizzor(scizzor) => draw_params.scissor = Some(scizzor),
 
+                // Hand the surface to the user's rendering code.
+                Command::Custom { custom, scizzor } => draw_custom(surface, custom, scizzor),
+
                 // Draw to the target with the given `draw` command.
                 Command::Draw(draw) => match draw {
                     // Draw text and plain 2D geometry.
@@ -944,15 +775,13 @@ impl Renderer {
                     Draw::Plain(slice) => {
                         if slice.len() >= NUM_VERTICES_IN_TRIANGLE {
                             let vertex_buffer = glium::VertexBuffer::new(facade, slice)?;
-                            surface
-                                .draw(
-                                    &vertex_buffer,
-                                    no_indices,
-                                    &self.program,
-                                    &uniforms,
-                                    &draw_params,
-                                )
-                                .unwrap();
+                            surface.draw(
+                                &vertex_buffer,
+                                no_indices,
+                                &self.program,
+                                &uniforms,
+                                &draw_params,
+                            )?;
                         }
                     }
 
@@ -962,22 +791,20 @@ impl Renderer {
                     // Only submit the vertices if there is enough for at least one triangle.
                     Draw::Image(image_id, slice) => {
                         if slice.len() >= NUM_VERTICES_IN_TRIANGLE {
-                            let vertex_buffer = glium::VertexBuffer::new(facade, slice).unwrap();
+                            let vertex_buffer = glium::VertexBuffer::new(facade, slice)?;
                             if let Some(image) = image_map.get(&image_id) {
                                 let image_uniforms = uniform! {
                                     tex: glium::uniforms::Sampler::new(image)
                                         .wrap_function(glium::uniforms::SamplerWrapFunction::Clamp)
                                         .magnify_filter(glium::uniforms::MagnifySamplerFilter::Nearest),
                                 };
-                                surface
-                                    .draw(
-                                        &vertex_buffer,
-                                        no_indices,
-                                        &self.program,
-                                        &image_uniforms,
-                                        &draw_params,
-                                    )
-                                    .unwrap();
+                                surface.draw(
+                                    &vertex_buffer,
+                                    no_indices,
+                                    &self.program,
+                                    &image_uniforms,
+                                    &draw_params,
+                                )?;
                             }
                         }
                     }
@@ -989,6 +816,87 @@ impl Renderer {
     }
 }
 
+// Fill the mesh from the given primitives and convert its vertices and commands for use with
+// glium, returning the result of the fill so that the glyph cache texture may be updated.
+fn fill_streams<P, M>(
+    mesh: &mut Mesh,
+    commands: &mut Vec<PreparedCommand>,
+    vertices: &mut Vec<Vertex>,
+    framebuffer_dimensions: (u32, u32),
+    dpi_factor: f64,
+    image_map: &M,
+    primitives: P,
+) -> Result<mesh::Fill, text::rt::gpu_cache::CacheWriteErr>
+where
+    P: render::PrimitiveWalker,
+    M: mesh::ImageMap,
+{
+    let (screen_w, screen_h) = framebuffer_dimensions;
+    let viewport = Rect::from_xy_dim([0.0, 0.0], [screen_w as Scalar, screen_h as Scalar]);
+    let fill = mesh.fill(viewport, dpi_factor, image_map, primitives)?;
+    fill_from_mesh(mesh, screen_h, commands, vertices);
+    Ok(fill)
+}
+
+// Convert the vertices and commands produced by the mesh to those used by glium.
+//
+// Glium's vertex positions and image texture coordinates have their *y* axis pointing upwards,
+// while its scizzor is positioned from the bottom of the framebuffer.
+fn fill_from_mesh(
+    mesh: &Mesh,
+    screen_h: u32,
+    commands: &mut Vec<PreparedCommand>,
+    vertices: &mut Vec<Vertex>,
+) {
+    commands.clear();
+    vertices.clear();
+
+    vertices.extend(mesh.vertices().iter().map(|v| {
+        let [x, y] = v.position;
+        let [u, tex_v] = v.tex_coords;
+        let tex_coords = match v.mode {
+            mesh::MODE_IMAGE => [u, 1.0 - tex_v],
+            _ => [u, tex_v],
+        };
+        Vertex {
+            mode: v.mode,
+            position: [x, -y],
+            tex_coords: tex_coords,
+            color: v.rgba,
+        }
+    }));
+
+    commands.extend(mesh.commands().filter_map(|command| {
+        let cmd = match command {
+            mesh::Command::Scizzor(scizzor) => {
+                PreparedCommand::Scizzor(glium_scizzor(scizzor, screen_h))
+            }
+            mesh::Command::Draw(mesh::Draw::Plain(range)) => PreparedCommand::Plain(range),
+            mesh::Command::Draw(mesh::Draw::Image(id, range)) => PreparedCommand::Image(id, range),
+            mesh::Command::Custom(custom) => {
+                let scizzor = glium_scizzor(custom.scizzor, screen_h);
+                PreparedCommand::Custom(custom, scizzor)
+            }
+            // Clip masks are not supported, so draws are cropped to the bounding scizzor alone.
+            mesh::Command::ClipMask(_) => return None,
+        };
+        Some(cmd)
+    }));
+}
+
+// Convert the mesh's scizzor to a `glium::Rect` positioned from the bottom of the framebuffer.
+fn glium_scizzor(scizzor: mesh::Scizzor, screen_h: u32) -> glium::Rect {
+    let [left, top] = scizzor.top_left;
+    let [width, height] = scizzor.dimensions;
+    let bottom = (screen_h as i32 - top - height as i32).max(0);
+    glium::Rect {
+        left: left as u32,
+        bottom: bottom as u32,
+        width: width,
+        height: height,
+    }
+}
+
 impl<'a> Iterator for Commands<'a> {
     type Item = Command<'a>;
     fn next(&mut self) -> Option<Self::Item> {
@@ -1004,6 +912,7 @@ impl<'a> Iterator for Commands<'a> {
             PreparedCommand::Image(id, ref range) => {
                 Command::Draw(Draw::Image(id, &vertices[range.clone()]))
             }
+            PreparedCommand::Custom(ref custom, scizzor) => Command::Custom { custom, scizzor },
         })
     }
 }
@@ -1031,6 +940,29 @@ impl std::fmt::Display for RendererCreationError {
     }
 }
 
+impl From<text::rt::gpu_cache::CacheWriteErr> for FillError {
+    fn from(err: text::rt::gpu_cache::CacheWriteErr) -> Self {
+        FillError::GlyphCache(err)
+    }
+}
+
+impl From<glium::texture::TextureCreationError> for FillError {
+    fn from(err: glium::texture::TextureCreationError) -> Self {
+        FillError::Texture(err)
+    }
+}
+
+impl std::error::Error for FillError {}
+
+impl std::fmt::Display for FillError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> Result<(), std::fmt::Error> {
+        match *self {
+            FillError::GlyphCache(ref e) => std::fmt::Display::fmt(e, f),
+            FillError::Texture(ref e) => std::fmt::Display::fmt(e, f),
+        }
+    }
+}
+
 impl From<glium::vertex::BufferCreationError> for DrawError {
     fn from(err: glium::vertex::BufferCreationError) -> Self {
         DrawError::Buffer(err)
@@ -1053,3 +985,51 @@ impl std::fmt::Display for DrawError {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    include!("../../mesh_streams.rs");
+
+    #[test]
+    fn fill_streams_should_match_the_other_backends() {
+        let (ui, image_map) = ui();
+        let mut mesh = Mesh::new();
+        let (mut commands, mut vertices) = (vec![], vec![]);
+        let dims = (SCREEN_W, SCREEN_H);
+        let primitives = ui.draw();
+        super::fill_streams(
+            &mut mesh,
+            &mut commands,
+            &mut vertices,
+            dims,
+            1.0,
+            &image_map,
+            primitives,
+        )
+        .unwrap();
+
+        let commands: Vec<_> = commands
+            .iter()
+            .map(|command| match *command {
+                super::PreparedCommand::Scizzor(r) => {
+                    Command::Scizzor([r.left, r.bottom, r.width, r.height])
+                }
+                super::PreparedCommand::Plain(ref range) => Command::Plain(range.clone()),
+                super::PreparedCommand::Image(id, ref range) => Command::Image(id, range.clone()),
+                super::PreparedCommand::Custom(ref custom, r) => {
+                    Command::Custom(custom.clone(), [r.left, r.bottom, r.width, r.height])
+                }
+            })
+            .collect();
+        let vertices: Vec<_> = vertices
+            .iter()
+            .map(|v| Vertex {
+                position: v.position,
+                tex_coords: v.tex_coords,
+                color: v.color,
+                mode: v.mode,
+            })
+            .collect();
+        assert_streams_match(&ui, &image_map, &commands, &vertices);
+    }
+}
@@ -1,6 +1,7 @@
 //! A piston backend for rendering conrod primitives.
 
-use conrod_core::{image, render, text, utils, Rect};
+use conrod_core::position::Transform;
+use conrod_core::{image, render, text, utils, widget, Rect, Scalar};
 use piston_graphics;
 
 #[doc(inline)]
@@ -19,7 +20,44 @@ pub use piston_graphics::{Context, DrawState, Graphics, ImageSize, Transformed};
 /// - `cache_queue_glyphs` - A function for caching glyphs within the given texture cache.
 /// - `texture_from_image` - A function that borrows a drawable texture `T` from an `Img`. In many
 ///   cases, `Img` may be the same type as `T`, however we provide this to allow for flexibility.
+///
+/// `CustomDraw` widgets are not drawn. See `primitives_with_custom`.
 pub fn primitives<'a, P, G, T, Img, C, F>(
+    primitives: P,
+    context: Context,
+    graphics: &'a mut G,
+    text_texture_cache: &'a mut T,
+    glyph_cache: &'a mut text::GlyphCache,
+    image_map: &'a image::Map<Img>,
+    cache_queued_glyphs: C,
+    texture_from_image: F,
+) where
+    P: render::PrimitiveWalker,
+    G: Graphics<Texture = T>,
+    T: ImageSize,
+    C: FnMut(&mut G, &mut T, text::rt::Rect<u32>, &[u8]),
+    F: FnMut(&Img) -> &T,
+{
+    primitives_with_custom(
+        primitives,
+        context,
+        graphics,
+        text_texture_cache,
+        glyph_cache,
+        image_map,
+        cache_queued_glyphs,
+        texture_from_image,
+        |_, _, _| (),
+    )
+}
+
+/// The same as `primitives`, but calls `draw_custom` for each `CustomDraw` widget, in order of
+/// depth among the other widgets.
+///
+/// `draw_custom` is given the `Graphics` backend, the widget's `Primitive` and a `Context` that is
+/// cropped to the primitive's `scizzor` and transformed such that the primitive's `rect` may be
+/// drawn to directly.
+pub fn primitives_with_custom<'a, P, G, T, Img, C, F, D>(
     mut primitives: P,
     context: Context,
     graphics: &'a mut G,
@@ -28,17 +66,24 @@ pub fn primitives<'a, P, G, T, Img, C, F>(
     image_map: &'a image::Map<Img>,
     mut cache_queued_glyphs: C,
     mut texture_from_image: F,
+    mut draw_custom: D,
 ) where
     P: render::PrimitiveWalker,
     G: Graphics<Texture = T>,
     T: ImageSize,
     C: FnMut(&mut G, &mut T, text::rt::Rect<u32>, &[u8]),
     F: FnMut(&Img) -> &T,
+    D: FnMut(&mut G, Context, render::Primitive),
 {
     // A re-usable buffer of rectangles describing the glyph's screen and texture positions.
     let mut glyph_rectangles = Vec::new();
 
     while let Some(prim) = render::PrimitiveWalker::next_primitive(&mut primitives) {
+        if let render::PrimitiveKind::Custom { .. } = prim.kind {
+            let context = primitive_context(context, prim.scizzor, prim.transform);
+            draw_custom(graphics, context, prim);
+            continue;
+        }
         primitive(
             prim,
             context,
@@ -87,14 +132,11 @@ pub fn primitive<'a, Img, G, T, C, F>(
         kind,
         scizzor,
         rect,
+        transform,
         ..
     } = primitive;
     let view_size = context.get_view_size();
-    // Translate the `context` to suit conrod's orientation (middle (0, 0), y pointing upwards).
-    let context = context
-        .trans(view_size[0] / 2.0, view_size[1] / 2.0)
-        .scale(1.0, -1.0);
-    let context = crop_context(context, scizzor);
+    let context = primitive_context(context, scizzor, transform);
 
     match kind {
         render::PrimitiveKind::Rectangle { color } => {
@@ -142,16 +184,17 @@ pub fn primitive<'a, Img, G, T, C, F>(
                 .viewport
                 .map(|v| v.draw_size[0] as f32 / v.window_size[0] as f32)
                 .unwrap_or(1.0);
-            let positioned_glyphs: Vec<_> = text.positioned_glyphs(dpi_factor).collect();
+            let highlighted_glyphs: Vec<_> = text.highlighted_glyphs(dpi_factor).collect();
             // Re-orient the context to top-left origin with *y* facing downwards, as the
-            // `positioned_glyphs` yield pixel positioning.
+            // `highlighted_glyphs` yield pixel positioning.
             let context = context
                 .scale(1.0, -1.0)
                 .trans(-view_size[0] / 2.0, -view_size[1] / 2.0);
 
-            // Queue the glyphs to be cached.
-            for glyph in positioned_glyphs.iter() {
-                glyph_cache.queue_glyph(font_id.index(), glyph.clone());
+            // Queue the glyphs to be cached, using the font of any highlight covering them.
+            for g in highlighted_glyphs.iter() {
+                let cache_id = g.font_id.unwrap_or(font_id).index();
+                glyph_cache.queue_glyph(cache_id, g.glyph.clone());
             }
 
             // Cache the glyphs within the GPU cache.
@@ -161,67 +204,99 @@ pub fn primitive<'a, Img, G, T, C, F>(
                 })
                 .unwrap();
 
-            let cache_id = font_id.index();
             let (tex_w, tex_h) = text_texture_cache.get_size();
-            let color = color.to_fsa();
 
-            let rectangles = positioned_glyphs
-                .into_iter()
-                .filter_map(|g| glyph_cache.rect_for(cache_id, &g).ok().unwrap_or(None))
-                .map(|(uv_rect, screen_rect)| {
-                    let rectangle = {
-                        let div_dpi_factor = |s| (s as f32 / dpi_factor as f32) as f64;
-                        let left = div_dpi_factor(screen_rect.min.x);
-                        let top = div_dpi_factor(screen_rect.min.y);
-                        let right = div_dpi_factor(screen_rect.max.x);
-                        let bottom = div_dpi_factor(screen_rect.max.y);
-                        let w = right - left;
-                        let h = bottom - top;
-                        [left, top, w, h]
-                    };
-                    let source_rectangle = {
-                        let x = (uv_rect.min.x * tex_w as f32) as f64;
-                        let y = (uv_rect.min.y * tex_h as f32) as f64;
-                        let w = ((uv_rect.max.x - uv_rect.min.x) * tex_w as f32) as f64;
-                        let h = ((uv_rect.max.y - uv_rect.min.y) * tex_h as f32) as f64;
-                        [x, y, w, h]
-                    };
-                    (rectangle, source_rectangle)
-                });
-            glyph_rectangles.clear();
-            glyph_rectangles.extend(rectangles);
-            piston_graphics::image::draw_many(
-                &glyph_rectangles,
-                color,
-                text_texture_cache,
-                &context.draw_state,
-                context.transform,
-                graphics,
-            );
+            // Glyphs are drawn in runs sharing the same highlight colour, as `draw_many` expects a
+            // single colour.
+            let mut glyphs = highlighted_glyphs.iter().peekable();
+            while let Some(first) = glyphs.next() {
+                let run_color = first.color.unwrap_or(color);
+                glyph_rectangles.clear();
+                let mut g = first;
+                loop {
+                    let cache_id = g.font_id.unwrap_or(font_id).index();
+                    if let Ok(Some((uv_rect, screen_rect))) =
+                        glyph_cache.rect_for(cache_id, &g.glyph)
+                    {
+                        let rectangle = {
+                            let div_dpi_factor = |s| (s as f32 / dpi_factor as f32) as f64;
+                            let left = div_dpi_factor(screen_rect.min.x);
+                            let top = div_dpi_factor(screen_rect.min.y);
+                            let right = div_dpi_factor(screen_rect.max.x);
+                            let bottom = div_dpi_factor(screen_rect.max.y);
+                            let w = right - left;
+                            let h = bottom - top;
+                            [left, top, w, h]
+                        };
+                        let source_rectangle = {
+                            let x = (uv_rect.min.x * tex_w as f32) as f64;
+                            let y = (uv_rect.min.y * tex_h as f32) as f64;
+                            let w = ((uv_rect.max.x - uv_rect.min.x) * tex_w as f32) as f64;
+                            let h = ((uv_rect.max.y - uv_rect.min.y) * tex_h as f32) as f64;
+                            [x, y, w, h]
+                        };
+                        glyph_rectangles.push((rectangle, source_rectangle));
+                    }
+                    match glyphs.peek() {
+                        Some(next) if next.color.unwrap_or(color) == run_color => {
+                            g = glyphs.next().unwrap();
+                        }
+                        _ => break,
+                    }
+                }
+                piston_graphics::image::draw_many(
+                    &glyph_rectangles,
+                    run_color.to_fsa(),
+                    text_texture_cache,
+                    &context.draw_state,
+                    context.transform,
+                    graphics,
+                );
+            }
         }
 
         render::PrimitiveKind::Image {
             image_id,
             color,
             source_rect,
+            nine_slice,
+            fit,
         } => {
             if let Some(img) = image_map.get(&image_id) {
-                let mut image = piston_graphics::image::Image::new();
-                image.color = color.map(|c| c.to_fsa());
-                if let Some(source_rect) = source_rect {
-                    let (x, y, w, h) = source_rect.x_y_w_h();
-                    image.source_rectangle = Some([x, y, w, h]);
-                }
-                let (left, top, w, h) = rect.l_t_w_h();
-                image.rectangle = Some([0.0, 0.0, w, h]);
-                let context = context.trans(left, top).scale(1.0, -1.0);
-                let transform = context.transform;
-                let draw_state = &context.draw_state;
                 let tex = texture_from_image(img);
-                image.draw(tex, draw_state, transform, graphics);
+                let mut draw_image = |rect: Rect, source_rect: Option<Rect>| {
+                    let mut image = piston_graphics::image::Image::new();
+                    image.color = color.map(|c| c.to_fsa());
+                    if let Some(source_rect) = source_rect {
+                        let (x, y, w, h) = source_rect.x_y_w_h();
+                        image.source_rectangle = Some([x, y, w, h]);
+                    }
+                    let (left, top, w, h) = rect.l_t_w_h();
+                    image.rectangle = Some([0.0, 0.0, w, h]);
+                    let context = context.trans(left, top).scale(1.0, -1.0);
+                    let transform = context.transform;
+                    let draw_state = &context.draw_state;
+                    image.draw(tex, draw_state, transform, graphics);
+                };
+                match (nine_slice, fit) {
+                    (None, widget::image::Fit::Stretch) => draw_image(rect, source_rect),
+                    _ => {
+                        let (image_w, image_h) = tex.get_size();
+                        let image_dim = [image_w as Scalar, image_h as Scalar];
+                        let image_rect = Rect::from_corners([0.0, 0.0], image_dim);
+                        let source_rect = source_rect.unwrap_or(image_rect);
+                        let quads = widget::image::quads(rect, source_rect, fit, nine_slice);
+                        for (rect, src_rect) in quads {
+                            draw_image(rect, Some(src_rect));
+                        }
+                    }
+                }
             }
         }
 
+        // Custom widgets are left to the user. See `primitives_with_custom`.
+        render::PrimitiveKind::Custom { .. } => (),
+
         render::PrimitiveKind::Other(_widget) => {
             // TODO: Perhaps add a function to the `primitives` params to allow a user to
             // handle these.
@@ -229,6 +304,24 @@ pub fn primitive<'a, Img, G, T, C, F>(
     }
 }
 
+// Translate the given `context` to suit conrod's orientation (middle (0, 0), y pointing upwards),
+// crop it to the `scizzor` and apply the primitive's transform from the space in which it was laid
+// out, if it has one.
+fn primitive_context(context: Context, scizzor: Rect, transform: Option<Transform>) -> Context {
+    let view_size = context.get_view_size();
+    let context = context
+        .trans(view_size[0] / 2.0, view_size[1] / 2.0)
+        .scale(1.0, -1.0);
+    let context = crop_context(context, scizzor);
+    match transform {
+        None => context,
+        Some(t) => context.append_transform([
+            [t.x_axis[0], t.y_axis[0], t.translation[0]],
+            [t.x_axis[1], t.y_axis[1], t.translation[1]],
+        ]),
+    }
+}
+
 /// Crop the given **Context** to the given **Rect**.
 ///
 /// This is non-trivial as we must consider the view_size, viewport, the difference in
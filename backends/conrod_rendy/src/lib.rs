@@ -80,6 +80,10 @@ pub struct UiPipelineDesc {
     pub glyph_cache_dimensions: [u32; 2],
 }
 
+/// The render group drawing the `Ui`.
+///
+/// `CustomDraw` widgets are not supported, as there is no way for the user to draw within the
+/// render group. Debug builds panic upon encountering one.
 #[derive(Debug)]
 pub struct UiPipeline<B: Backend> {
     mesh: Mesh,
@@ -261,10 +265,10 @@ where
         _images: Vec<NodeImage>,
         set_layouts: &[Handle<DescriptorSetLayout<B>>],
     ) -> Result<Self::Pipeline, CreationError> {
-        // TODO: Consider using `Mesh::with_glyph_cache_dimensions` and allowing user to specify
-        // glyph cache dimensions. Currently we just use the default size, but this is not always
-        // enough for large GUIs with lots of text.
-        let mesh = Mesh::with_glyph_cache_dimensions(self.glyph_cache_dimensions);
+        // The glyph cache texture can only be created here where the factory is mutable, so the
+        // glyph cache is kept at the given dimensions rather than grown to fit the text.
+        let mesh = Mesh::with_glyph_cache_dimensions(self.glyph_cache_dimensions)
+            .max_glyph_cache_dimensions(self.glyph_cache_dimensions);
 
         // Create the texture used for caching glyphs on the GPU.
         let sampler_img_state = sampler_img_state(queue);
@@ -513,6 +517,17 @@ where
                         encoder.set_scissors(first_scissor, Some(&rect));
                     }
                 }
+
+                // There is no way for the user to draw within the render node.
+                mesh::Command::Custom(_) => {
+                    debug_assert!(
+                        false,
+                        "`CustomDraw` widgets are not supported by conrod_rendy"
+                    );
+                }
+
+                // Clip masks are not supported, so draws are cropped to the bounding scissor alone.
+                mesh::Command::ClipMask(_) => (),
             }
         }
     }
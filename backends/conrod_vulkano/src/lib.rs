@@ -16,7 +16,7 @@ use vulkano::descriptor_set::layout::{
 };
 use vulkano::descriptor_set::{DescriptorSet, DescriptorSetError, SingleLayoutDescSetPool};
 use vulkano::device::physical::QueueFamily;
-use vulkano::device::{Device, Queue};
+use vulkano::device::{Device, DeviceOwned, Queue};
 use vulkano::format::Format;
 use vulkano::image::view::{ImageView, ImageViewType};
 use vulkano::image::{
@@ -138,6 +138,7 @@ pub struct Renderer {
     pipeline: Arc<GraphicsPipeline>,
     glyph_uploads: Arc<CpuBufferPool<u8>>,
     glyph_cache_tex: Arc<StorageImage>,
+    graphics_queue_family_id: u32,
     sampler: Arc<Sampler>,
     tex_descs: SingleLayoutDescSetPool,
     mesh: Mesh,
@@ -181,6 +182,13 @@ pub enum DrawError {
     VertexBufferAlloc(DeviceMemoryAllocError),
 }
 
+/// Errors that might occur while filling the renderer with primitives.
+#[derive(Debug)]
+pub enum FillError {
+    GlyphCacheWrite(rt::gpu_cache::CacheWriteErr),
+    GlyphCacheTextureCreation(ImageCreationError),
+}
+
 impl mesh::ImageDimensions for Image {
     fn dimensions(&self) -> [u32; 2] {
         [self.width, self.height]
@@ -302,33 +310,9 @@ impl Renderer {
         );
         let mesh = Mesh::with_glyph_cache_dimensions(glyph_cache_dims);
 
-        let glyph_cache_tex = {
-            let [width, height] = glyph_cache_dims;
-            StorageImage::with_usage(
-                device.clone(),
-                ImageDimensions::Dim2d {
-                    width,
-                    height,
-                    array_layers: 1,
-                },
-                Format::R8_UNORM,
-                ImageUsage {
-                    transfer_destination: true,
-                    sampled: true,
-                    ..ImageUsage::none()
-                },
-                ImageCreateFlags {
-                    sparse_binding: false,
-                    sparse_residency: false,
-                    sparse_aliased: false,
-                    mutable_format: false,
-                    cube_compatible: false,
-                    array_2d_compatible: false,
-                    block_texel_view_compatible: false,
-                },
-                vec![graphics_queue_family],
-            )?
-        };
+        let glyph_cache_tex =
+            create_glyph_cache_tex(device.clone(), glyph_cache_dims, graphics_queue_family)?;
+        let graphics_queue_family_id = graphics_queue_family.id();
 
         let tex_descs =
             SingleLayoutDescSetPool::new(pipeline.layout().descriptor_set_layouts()[0].clone());
@@ -338,6 +322,7 @@ impl Renderer {
             pipeline,
             glyph_uploads,
             glyph_cache_tex,
+            graphics_queue_family_id,
             sampler,
             tex_descs,
             mesh,
@@ -354,16 +339,21 @@ impl Renderer {
     /// This method may return an `Option<GlyphCacheCommand>`, in which case the user should use
     /// the contained `glyph_cpu_buffer_pool` to write the pixel data to the GPU, and then use a
     /// `copy_buffer_to_image` command to write the data to the given `glyph_cache_texture` image.
+    ///
+    /// If the glyph cache had to grow in order to fit the text, the `glyph_cache_texture` is
+    /// re-created with the new dimensions.
     pub fn fill<P: render::PrimitiveWalker>(
         &mut self,
         image_map: &image::Map<Image>,
         viewport: [f32; 4],
         dpi_factor: f64,
         primitives: P,
-    ) -> Result<Option<GlyphCacheCommand>, rt::gpu_cache::CacheWriteErr> {
+    ) -> Result<Option<GlyphCacheCommand>, FillError> {
         let Renderer {
+            ref pipeline,
             ref glyph_uploads,
-            ref glyph_cache_tex,
+            ref mut glyph_cache_tex,
+            graphics_queue_family_id,
             ref mut mesh,
             ..
         } = *self;
@@ -372,6 +362,16 @@ impl Renderer {
         let rb = [vp_r as Scalar, vp_b as Scalar];
         let viewport = Rect::from_corners(lt, rb);
         let fill = mesh.fill(viewport, dpi_factor, image_map, primitives)?;
+        if let Some(glyph_cache_dims) = fill.glyph_cache_resized {
+            let device = pipeline.device().clone();
+            let queue_family = device
+                .physical_device()
+                .queue_family_by_id(graphics_queue_family_id)
+                .expect("no queue family for the glyph cache texture");
+            *glyph_cache_tex =
+                create_glyph_cache_tex(device.clone(), glyph_cache_dims, queue_family)
+                    .map_err(FillError::GlyphCacheTextureCreation)?;
+        }
         let glyph_cache_cmd = match fill.glyph_cache_requires_upload {
             false => None,
             true => Some(GlyphCacheCommand {
@@ -392,6 +392,9 @@ impl Renderer {
     /// and `commands` methods separately. This method is simply a convenience wrapper around those
     /// methods for the case that the user does not require accessing or modifying conrod's draw
     /// parameters, uniforms or generated draw commands.
+    ///
+    /// `CustomDraw` widgets are not drawn. Users drawing their own widgets should interleave their
+    /// drawing with the `mesh::Command::Custom`s yielded by the `commands` method instead.
     pub fn draw(
         &mut self,
         queue: Arc<Queue>,
@@ -432,6 +435,12 @@ impl Renderer {
                 // Update the `scizzor` before continuing to draw.
                 mesh::Command::Scizzor(scizzor) => current_scizzor = conv_scizzor(scizzor),
 
+                // Custom widgets are left to users of the `commands` method.
+                mesh::Command::Custom(_) => (),
+
+                // Clip masks are not supported, so draws are cropped to the bounding scizzor alone.
+                mesh::Command::ClipMask(_) => (),
+
                 // Draw to the target with the given `draw` command.
                 mesh::Command::Draw(draw) => match draw {
                     // Draw text and plain 2D geometry.
@@ -502,6 +511,37 @@ impl Renderer {
     }
 }
 
+fn create_glyph_cache_tex(
+    device: Arc<Device>,
+    [width, height]: [u32; 2],
+    queue_family: QueueFamily,
+) -> Result<Arc<StorageImage>, ImageCreationError> {
+    StorageImage::with_usage(
+        device,
+        ImageDimensions::Dim2d {
+            width,
+            height,
+            array_layers: 1,
+        },
+        Format::R8_UNORM,
+        ImageUsage {
+            transfer_destination: true,
+            sampled: true,
+            ..ImageUsage::none()
+        },
+        ImageCreateFlags {
+            sparse_binding: false,
+            sparse_residency: false,
+            sparse_aliased: false,
+            mutable_format: false,
+            cube_compatible: false,
+            array_2d_compatible: false,
+            block_texel_view_compatible: false,
+        },
+        vec![queue_family],
+    )
+}
+
 fn conv_vertex_buffer(buffer: &[mesh::Vertex]) -> &[Vertex] {
     unsafe { &*(buffer as *const [conrod_core::mesh::Vertex] as *const [Vertex]) }
 }
@@ -542,6 +582,12 @@ impl From<DeviceMemoryAllocError> for DrawError {
     }
 }
 
+impl From<rt::gpu_cache::CacheWriteErr> for FillError {
+    fn from(err: rt::gpu_cache::CacheWriteErr) -> Self {
+        FillError::GlyphCacheWrite(err)
+    }
+}
+
 impl StdError for RendererCreationError {
     fn cause(&self) -> Option<&dyn StdError> {
         match *self {
@@ -562,6 +608,15 @@ impl StdError for DrawError {
     }
 }
 
+impl StdError for FillError {
+    fn cause(&self) -> Option<&dyn StdError> {
+        match *self {
+            FillError::GlyphCacheWrite(ref err) => Some(err),
+            FillError::GlyphCacheTextureCreation(ref err) => Some(err),
+        }
+    }
+}
+
 impl fmt::Display for RendererCreationError {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match *self {
@@ -581,3 +636,12 @@ impl fmt::Display for DrawError {
         }
     }
 }
+
+impl fmt::Display for FillError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            FillError::GlyphCacheWrite(ref err) => err.fmt(f),
+            FillError::GlyphCacheTextureCreation(ref err) => err.fmt(f),
+        }
+    }
+}
@@ -191,7 +191,7 @@ fn main() {
                 let [win_w, win_h]: [f32; 2] = [size.width as f32, size.height as f32];
                 let viewport = [0.0, 0.0, win_w, win_h];
                 if let Some(cmd) = renderer
-                    .fill(&image_map, viewport, scale_factor, primitives)
+                    .fill(&device, &image_map, viewport, scale_factor, primitives)
                     .unwrap()
                 {
                     cmd.load_buffer_and_encode(&device, &mut encoder);
@@ -249,6 +249,22 @@ fn main() {
                                 conrod_wgpu::RenderPassCommand::Draw { vertex_range } => {
                                     render_pass.draw(vertex_range, instance_range.clone());
                                 }
+                                // This example has no `CustomDraw` widgets.
+                                conrod_wgpu::RenderPassCommand::Custom { .. } => {}
+                                conrod_wgpu::RenderPassCommand::SetStencilReference {
+                                    reference,
+                                } => {
+                                    render_pass.set_stencil_reference(reference);
+                                }
+                                conrod_wgpu::RenderPassCommand::ClearStencil {
+                                    vertex_buffer,
+                                    vertex_range,
+                                } => {
+                                    render_pass.set_vertex_buffer(slot, vertex_buffer.slice(..));
+                                    render_pass.draw(vertex_range, instance_range.clone());
+                                    let vertex_buffer = render.vertex_buffer.slice(..);
+                                    render_pass.set_vertex_buffer(slot, vertex_buffer);
+                                }
                             }
                         }
                     }
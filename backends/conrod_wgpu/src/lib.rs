@@ -43,6 +43,7 @@ pub struct Vertex {
     /// `0` for rendering text.
     /// `1` for rendering an image.
     /// `2` for rendering non-textured 2D geometry.
+    /// `3` for rendering text from a signed distance field.
     ///
     /// If any other value is given, the fragment shader will not output any color.
     pub mode: u32,
@@ -65,6 +66,10 @@ pub struct Renderer {
     bind_groups: HashMap<image::Id, wgpu::BindGroup>,
     // We also need a unique
     render_pipelines: HashMap<wgpu::TextureSampleType, Pipeline>,
+    // The format of the depth-stencil attachment, if clip masks are written to its stencil.
+    stencil_format: Option<wgpu::TextureFormat>,
+    // The pipelines for writing clip masks to the stencil, if enabled.
+    clip_pipelines: Option<ClipPipelines>,
 }
 
 /// Data that must be unique per `wgpu::TextureSampleType`, i.e. bind group layout and render
@@ -74,6 +79,27 @@ struct Pipeline {
     render_pipeline: wgpu::RenderPipeline,
 }
 
+/// The pipelines used to write a `mesh::ClipMask` to the stencil attachment.
+struct ClipPipelines {
+    // Resets the stencil value of every pixel within the scissor to zero.
+    clear: wgpu::RenderPipeline,
+    // Increments the stencil value of every pixel covered by a shape.
+    mask: wgpu::RenderPipeline,
+    // A quad covering the whole viewport, drawn with the `clear` pipeline.
+    clear_quad: wgpu::Buffer,
+}
+
+/// The way in which a render pipeline uses the stencil attachment.
+#[derive(Copy, Clone, Debug, PartialEq)]
+enum StencilMode {
+    // Only draw where the stencil value equals the reference.
+    Test,
+    // Set the stencil value to zero without drawing any color.
+    Clear,
+    // Increment the stencil value without drawing any color.
+    Mask,
+}
+
 /// An command for uploading an individual glyph.
 pub struct GlyphCacheCommand<'a> {
     /// The CPU buffer containing the pixel data.
@@ -108,6 +134,25 @@ pub enum RenderPassCommand<'a> {
     /// An image requiring a different bind group layout requires drawing and in turn, we must set
     /// the necessary render pipeline.
     SetPipeline { pipeline: &'a wgpu::RenderPipeline },
+    /// Invoke the user's rendering code for a `CustomDraw` widget.
+    ///
+    /// The pipeline, bind group and scissor are set again by the commands that follow, however
+    /// the user must restore conrod's vertex buffer if they set their own.
+    Custom { custom: mesh::Custom },
+    /// Specify the stencil value that pixels must have in order to be drawn to.
+    ///
+    /// Only produced when clipping via the stencil is enabled with `Renderer::stencil_clipping`.
+    SetStencilReference { reference: u32 },
+    /// Draw all vertices of the given buffer, a quad covering the whole viewport, in order to
+    /// clear the stencil before writing a clip mask.
+    ///
+    /// The `Render::vertex_buffer` must be set again before the commands that follow.
+    ///
+    /// Only produced when clipping via the stencil is enabled with `Renderer::stencil_clipping`.
+    ClearStencil {
+        vertex_buffer: &'a wgpu::Buffer,
+        vertex_range: std::ops::Range<u32>,
+    },
 }
 
 const GLYPH_TEX_FORMAT: wgpu::TextureFormat = wgpu::TextureFormat::R8Unorm;
@@ -157,8 +202,11 @@ impl Renderer {
             "wgpu glyph cache width must be multiple of 256"
         );
 
-        // The mesh for converting primitives into vertices.
-        let mesh = Mesh::with_glyph_cache_dimensions(glyph_cache_dims);
+        // The mesh for converting primitives into vertices. The glyph cache may grow as large as
+        // the device allows.
+        let max_tex_dim = device.limits().max_texture_dimension_2d;
+        let mesh = Mesh::with_glyph_cache_dimensions(glyph_cache_dims)
+            .max_glyph_cache_dimensions([max_tex_dim; 2]);
 
         // Load shader modules.
         let vs_mod = device.create_shader_module(&wgpu::include_wgsl!("shaders/vert.wgsl"));
@@ -190,6 +238,7 @@ impl Renderer {
             &fs_mod,
             dst_format,
             dst_sample_count,
+            None,
         );
         let default_bind_group = bind_group(
             device,
@@ -218,10 +267,75 @@ impl Renderer {
             dst_sample_count,
             bind_groups,
             render_pipelines,
+            stencil_format: None,
+            clip_pipelines: None,
             mesh,
         }
     }
 
+    /// Crop the children of widgets with a non-rectangular `widget::CropShape` by writing each
+    /// `mesh::ClipMask` to the stencil of a depth-stencil attachment with the given format.
+    ///
+    /// The render pass must then have a depth-stencil attachment of this format whose stencil is
+    /// cleared to `0`, and the user must handle the `RenderPassCommand::ClearStencil` and
+    /// `RenderPassCommand::SetStencilReference` commands. Otherwise, the children of such widgets
+    /// are cropped to their bounding scissor.
+    pub fn stencil_clipping(
+        mut self,
+        device: &wgpu::Device,
+        stencil_format: wgpu::TextureFormat,
+    ) -> Self {
+        let stencil = Some(stencil_format);
+        for pipeline in self.render_pipelines.values_mut() {
+            let pipeline_layout = pipeline_layout(device, &pipeline.bind_group_layout);
+            pipeline.render_pipeline = render_pipeline(
+                device,
+                &pipeline_layout,
+                &self.vs_mod,
+                &self.fs_mod,
+                self.dst_format,
+                self.dst_sample_count,
+                stencil.map(|format| (format, StencilMode::Test)),
+            );
+        }
+        let default_tct = DEFAULT_IMAGE_TEX_FORMAT.describe().sample_type;
+        let bind_group_layout = &self.render_pipelines[&default_tct].bind_group_layout;
+        let pipeline_layout = pipeline_layout(device, bind_group_layout);
+        let clip_pipeline = |mode| {
+            render_pipeline(
+                device,
+                &pipeline_layout,
+                &self.vs_mod,
+                &self.fs_mod,
+                self.dst_format,
+                self.dst_sample_count,
+                Some((stencil_format, mode)),
+            )
+        };
+        let clear_quad = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: Some("conrod_clear_quad_buffer_init_descriptor"),
+            contents: vertices_as_bytes(&clear_quad_vertices()),
+            usage: wgpu::BufferUsages::VERTEX,
+        });
+        let clip_pipelines = ClipPipelines {
+            clear: clip_pipeline(StencilMode::Clear),
+            mask: clip_pipeline(StencilMode::Mask),
+            clear_quad,
+        };
+        self.clip_pipelines = Some(clip_pipelines);
+        self.stencil_format = stencil;
+        self
+    }
+
+    /// Specify whether text should be drawn from signed distance fields.
+    ///
+    /// Each glyph is then rasterised only once, so that text may be scaled smoothly. See
+    /// `Mesh::sdf_text`.
+    pub fn sdf_text(mut self, sdf_text: bool) -> Self {
+        self.mesh = self.mesh.sdf_text(sdf_text);
+        self
+    }
+
     /// Produce an `Iterator` yielding `Command`s.
     pub fn commands(&self) -> mesh::Commands {
         self.mesh.commands()
@@ -232,8 +346,12 @@ impl Renderer {
     /// This method may return an `Option<GlyphCacheCommand>`, in which case the user should use
     /// the contained `glyph_cpu_buffer_pool` to write the pixel data to the GPU, and then use a
     /// `copy_buffer_to_image` command to write the data to the given `glyph_cache_texture` image.
+    ///
+    /// If the glyph cache had to grow in order to fit the text, the glyph cache texture is
+    /// re-created on the given `device`.
     pub fn fill<'a, P>(
         &'a mut self,
+        device: &wgpu::Device,
         image_map: &image::Map<Image>,
         viewport: [f32; 4],
         scale_factor: f64,
@@ -251,6 +369,21 @@ impl Renderer {
             .mesh
             .fill(viewport, scale_factor, image_map, primitives)?;
 
+        // If the glyph cache grew, re-create its texture along with the bind groups that use it.
+        if let Some(glyph_cache_dims) = fill.glyph_cache_resized {
+            let glyph_cache_tex_desc = glyph_cache_tex_desc(glyph_cache_dims);
+            self.glyph_cache_tex = device.create_texture(&glyph_cache_tex_desc);
+            let default_tct = DEFAULT_IMAGE_TEX_FORMAT.describe().sample_type;
+            self.default_bind_group = bind_group(
+                device,
+                &self.render_pipelines[&default_tct].bind_group_layout,
+                &self.glyph_cache_tex,
+                &self.sampler,
+                &self._default_image_tex,
+            );
+            self.bind_groups.clear();
+        }
+
         // Check whether or not we need a glyph cache update.
         let glyph_cache_cmd = match fill.glyph_cache_requires_upload {
             false => None,
@@ -280,8 +413,10 @@ impl Renderer {
             ref default_bind_group,
             ref glyph_cache_tex,
             ref sampler,
+            ref clip_pipelines,
             dst_format,
             dst_sample_count,
+            stencil_format,
             ..
         } = *self;
 
@@ -316,6 +451,7 @@ impl Renderer {
                     fs_mod,
                     dst_format,
                     dst_sample_count,
+                    stencil_format.map(|format| (format, StencilMode::Test)),
                 );
                 Pipeline {
                     bind_group_layout,
@@ -335,8 +471,7 @@ impl Renderer {
         }
 
         // Prepare a single vertex buffer containing all vertices for all geometry.
-        let vertices = mesh.vertices();
-        let vertices_bytes = vertices_as_bytes(vertices);
+        let vertices_bytes = vertices_as_bytes(mesh.vertices());
         let usage = wgpu::BufferUsages::VERTEX;
         let vertex_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
             label: Some("conrod_buffer_init_descriptor"),
@@ -354,6 +489,55 @@ impl Renderer {
 
         for command in mesh.commands() {
             match command {
+                // Hand the render pass to the user's rendering code, after which conrod's state
+                // must be set again.
+                mesh::Command::Custom(custom) => {
+                    let scizzor = custom.scizzor;
+                    commands.push(RenderPassCommand::Custom { custom });
+                    bind_group = None;
+                    let top_left = [scizzor.top_left[0] as u32, scizzor.top_left[1] as u32];
+                    let dimensions = scizzor.dimensions;
+                    let cmd = RenderPassCommand::SetScissor {
+                        top_left,
+                        dimensions,
+                    };
+                    commands.push(cmd);
+                }
+
+                // Write the clip mask to the stencil, after which only the pixels covered by all
+                // of its shapes pass the stencil test. Without a stencil, the bounding scissor
+                // alone crops the draws.
+                mesh::Command::ClipMask(clip_mask) => {
+                    let clip_pipelines = match *clip_pipelines {
+                        Some(ref clip_pipelines) => clip_pipelines,
+                        None => continue,
+                    };
+                    let pipeline = &clip_pipelines.clear;
+                    commands.push(RenderPassCommand::SetPipeline { pipeline });
+                    let cmd = RenderPassCommand::SetBindGroup {
+                        bind_group: default_bind_group,
+                    };
+                    commands.push(cmd);
+                    let cmd = RenderPassCommand::ClearStencil {
+                        vertex_buffer: &clip_pipelines.clear_quad,
+                        vertex_range: 0..clear_quad_vertices().len() as u32,
+                    };
+                    commands.push(cmd);
+                    if !clip_mask.shapes.is_empty() {
+                        let pipeline = &clip_pipelines.mask;
+                        commands.push(RenderPassCommand::SetPipeline { pipeline });
+                        for shape in &clip_mask.shapes {
+                            let cmd = RenderPassCommand::Draw {
+                                vertex_range: shape.start as u32..shape.end as u32,
+                            };
+                            commands.push(cmd);
+                        }
+                    }
+                    let reference = clip_mask.shapes.len() as u32;
+                    commands.push(RenderPassCommand::SetStencilReference { reference });
+                    bind_group = None;
+                }
+
                 // Update the `scizzor` before continuing to draw.
                 mesh::Command::Scizzor(s) => {
                     let top_left = [s.top_left[0] as u32, s.top_left[1] as u32];
@@ -693,7 +877,13 @@ fn render_pipeline(
     fs_mod: &wgpu::ShaderModule,
     dst_format: wgpu::TextureFormat,
     dst_sample_count: u32,
+    stencil: Option<(wgpu::TextureFormat, StencilMode)>,
 ) -> wgpu::RenderPipeline {
+    // Pipelines that only write to the stencil draw no color.
+    let write_mask = match stencil {
+        Some((_, StencilMode::Clear)) | Some((_, StencilMode::Mask)) => wgpu::ColorWrites::empty(),
+        _ => wgpu::ColorWrites::ALL,
+    };
     let color_state = wgpu::ColorTargetState {
         format: dst_format,
         blend: Some(wgpu::BlendState {
@@ -708,7 +898,7 @@ fn render_pipeline(
                 operation: wgpu::BlendOperation::Add,
             },
         }),
-        write_mask: wgpu::ColorWrites::ALL,
+        write_mask,
     };
     let vertex_attrs = vertex_attrs();
     let vertex_buffer_desc = wgpu::VertexBufferLayout {
@@ -743,13 +933,64 @@ fn render_pipeline(
         layout: Some(layout),
         vertex: vertex_state,
         primitive: primitive_state,
-        depth_stencil: None,
+        depth_stencil: stencil.map(|(format, mode)| depth_stencil_state(format, mode)),
         multisample: multisample_state,
         fragment: Some(fragment_state),
     };
     device.create_render_pipeline(&desc)
 }
 
+fn depth_stencil_state(format: wgpu::TextureFormat, mode: StencilMode) -> wgpu::DepthStencilState {
+    let (compare, pass_op) = match mode {
+        StencilMode::Test => (wgpu::CompareFunction::Equal, wgpu::StencilOperation::Keep),
+        StencilMode::Clear => (wgpu::CompareFunction::Always, wgpu::StencilOperation::Zero),
+        StencilMode::Mask => (
+            wgpu::CompareFunction::Always,
+            wgpu::StencilOperation::IncrementClamp,
+        ),
+    };
+    let face = wgpu::StencilFaceState {
+        compare,
+        fail_op: wgpu::StencilOperation::Keep,
+        depth_fail_op: wgpu::StencilOperation::Keep,
+        pass_op,
+    };
+    let write_mask = match mode {
+        StencilMode::Test => 0,
+        StencilMode::Clear | StencilMode::Mask => !0,
+    };
+    wgpu::DepthStencilState {
+        format,
+        depth_write_enabled: false,
+        depth_compare: wgpu::CompareFunction::Always,
+        stencil: wgpu::StencilState {
+            front: face,
+            back: face,
+            read_mask: !0,
+            write_mask,
+        },
+        bias: wgpu::DepthBiasState::default(),
+    }
+}
+
+// Two triangles covering the whole viewport, used to clear the stencil.
+fn clear_quad_vertices() -> [mesh::Vertex; 6] {
+    let vertex = |x, y| mesh::Vertex {
+        position: [x, y],
+        tex_coords: [0.0, 0.0],
+        rgba: [1.0; 4],
+        mode: mesh::MODE_GEOMETRY,
+    };
+    [
+        vertex(-1.0, 1.0),
+        vertex(1.0, 1.0),
+        vertex(-1.0, -1.0),
+        vertex(1.0, 1.0),
+        vertex(1.0, -1.0),
+        vertex(-1.0, -1.0),
+    ]
+}
+
 fn vertices_as_bytes(s: &[mesh::Vertex]) -> &[u8] {
     let len = s.len() * std::mem::size_of::<mesh::Vertex>();
     let ptr = s.as_ptr() as *const u8;
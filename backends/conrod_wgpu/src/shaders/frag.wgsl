@@ -18,6 +18,9 @@ fn main(
     var text_color: vec4<f32> = textureSample(text_texture, image_sampler, uv);
     var image_color: vec4<f32> = textureSample(image_texture, image_sampler, uv);
     var text_alpha: f32 = text_color.x;
+    // The field of SDF text changes by `sdf_width` across a single pixel.
+    var sdf_width: f32 = max(fwidth(text_alpha), 0.0001);
+    var sdf_alpha: f32 = clamp((text_alpha - 0.5) / sdf_width + 0.5, 0.0, 1.0);
     var out_color: vec4<f32> = vec4<f32>(0.5, 0.0, 0.0, 1.0);
     if (mode == u32(0)) {
         out_color = color * vec4<f32>(1.0, 1.0, 1.0, text_alpha);
@@ -27,6 +30,10 @@ fn main(
         } else {
             if (mode == u32(2)) {
                 out_color = color;
+            } else {
+                if (mode == u32(3)) {
+                    out_color = color * vec4<f32>(1.0, 1.0, 1.0, sdf_alpha);
+                }
             }
         }
     }
@@ -0,0 +1,140 @@
+// The fixture with which the glium and gfx backends test that they produce the same vertex and
+// command streams for the same `Ui`.
+//
+// This file is included within the `tests` module of each backend, which fills its own streams
+// from the fixture `Ui` before converting them to the backend-neutral `Command`s and `Vertex`s
+// below.
+
+use conrod_core::mesh::{self, Mesh};
+use conrod_core::{color, image, widget, Colorable, Positionable, Rect, Sizeable, Widget};
+use std::ops::Range;
+
+// The dimensions of the framebuffer in pixels.
+const SCREEN_W: u32 = 400;
+const SCREEN_H: u32 = 300;
+
+// A vertex whose position and image texture coordinates have their *y* axis pointing upwards.
+#[derive(Debug, PartialEq)]
+struct Vertex {
+    position: [f32; 2],
+    tex_coords: [f32; 2],
+    color: [f32; 4],
+    mode: u32,
+}
+
+// A command whose scizzors are given as `[left, bottom, width, height]` in pixels.
+#[derive(Debug, PartialEq)]
+enum Command {
+    Scizzor([u32; 4]),
+    Plain(Range<usize>),
+    Image(image::Id, Range<usize>),
+    Custom(mesh::Custom, [u32; 4]),
+}
+
+// A `Ui` drawing a rectangle, some text, an image and a custom widget, all cropped by a canvas,
+// along with the dimensions of the image.
+fn ui() -> (conrod_core::Ui, image::Map<[u32; 2]>) {
+    let mut image_map = image::Map::new();
+    let image_id = image_map.insert([64, 32]);
+    let mut ui = conrod_core::UiBuilder::new([SCREEN_W as f64, SCREEN_H as f64]).build();
+    let path = concat!(
+        env!("CARGO_MANIFEST_DIR"),
+        "/../../assets/fonts/NotoSans/NotoSans-Regular.ttf"
+    );
+    let font_id = ui.fonts.insert_from_file(path).unwrap();
+    let mut id_generator = ui.widget_id_generator();
+    let ids: Vec<_> = (0..5).map(|_| id_generator.next()).collect();
+    {
+        let ui = &mut ui.set_widgets();
+        widget::Canvas::new()
+            .w_h(200.0, 100.0)
+            .top_left_with_margin(20.0)
+            .crop_kids()
+            .set(ids[0], ui);
+        widget::Rectangle::fill([300.0, 50.0])
+            .color(color::RED)
+            .top_left_of(ids[0])
+            .set(ids[1], ui);
+        widget::Text::new("Conrod")
+            .font_id(font_id)
+            .middle_of(ids[0])
+            .set(ids[2], ui);
+        widget::Image::new(image_id)
+            .w_h(40.0, 30.0)
+            .bottom_right_of(ids[0])
+            .set(ids[3], ui);
+        widget::CustomDraw::new(0u32)
+            .w_h(40.0, 30.0)
+            .bottom_left_of(ids[0])
+            .set(ids[4], ui);
+    }
+    (ui, image_map)
+}
+
+// Assert that the streams filled by a backend for the fixture `Ui` are those of a `Mesh` filled
+// from the same `Ui`, with positions and image texture coordinates flipped vertically and each
+// scizzor positioned from the bottom of the framebuffer.
+fn assert_streams_match(
+    ui: &conrod_core::Ui,
+    image_map: &image::Map<[u32; 2]>,
+    commands: &[Command],
+    vertices: &[Vertex],
+) {
+    let viewport = Rect::from_xy_dim([0.0, 0.0], [SCREEN_W as f64, SCREEN_H as f64]);
+    let mut mesh = Mesh::new();
+    mesh.fill(viewport, 1.0, image_map, ui.draw()).unwrap();
+
+    let expected_vertices: Vec<_> = mesh
+        .vertices()
+        .iter()
+        .map(|v| {
+            let [u, tex_v] = v.tex_coords;
+            let tex_coords = match v.mode {
+                mesh::MODE_IMAGE => [u, 1.0 - tex_v],
+                _ => [u, tex_v],
+            };
+            Vertex {
+                position: [v.position[0], -v.position[1]],
+                tex_coords: tex_coords,
+                color: v.rgba,
+                mode: v.mode,
+            }
+        })
+        .collect();
+    assert_eq!(vertices, &expected_vertices[..]);
+
+    let scizzor = |scizzor: mesh::Scizzor| {
+        let [left, top] = scizzor.top_left;
+        let [width, height] = scizzor.dimensions;
+        let bottom = SCREEN_H - top as u32 - height;
+        [left as u32, bottom, width, height]
+    };
+    let expected_commands: Vec<_> = mesh
+        .commands()
+        .filter_map(|command| {
+            let command = match command {
+                mesh::Command::Scizzor(s) => Command::Scizzor(scizzor(s)),
+                mesh::Command::Draw(mesh::Draw::Plain(range)) => Command::Plain(range),
+                mesh::Command::Draw(mesh::Draw::Image(id, range)) => Command::Image(id, range),
+                mesh::Command::Custom(custom) => {
+                    let s = scizzor(custom.scizzor);
+                    Command::Custom(custom, s)
+                }
+                // Neither backend supports clip masks.
+                mesh::Command::ClipMask(_) => return None,
+            };
+            Some(command)
+        })
+        .collect();
+    assert_eq!(commands, &expected_commands[..]);
+
+    // Text, images and custom widgets are drawn, cropped by the canvas.
+    let canvas_scizzor = [20, 180, 200, 100];
+    assert!(vertices.iter().any(|v| v.mode == mesh::MODE_TEXT));
+    assert!(vertices.iter().any(|v| v.mode == mesh::MODE_IMAGE));
+    assert!(commands.contains(&Command::Scizzor(canvas_scizzor)));
+    assert!(commands.iter().any(|command| match *command {
+        Command::Custom(_, scizzor) => scizzor == canvas_scizzor,
+        _ => false,
+    }));
+}
@@ -11,6 +11,7 @@
 //! and [HSL](http://en.wikipedia.org/wiki/HSL_and_HSV) creation, gradients and built-in names.
 //!
 
+use std;
 use std::f32::consts::PI;
 use utils::{degrees, fmod, turns};
 
@@ -80,6 +81,42 @@ pub fn hsl(hue: f32, saturation: f32, lightness: f32) -> Color {
     hsla(hue, saturation, lightness, 1.0)
 }
 
+/// Create [HSV colors](http://en.wikipedia.org/wiki/HSL_and_HSV) with an alpha component for
+/// transparency. The hue is specified in radians.
+#[inline]
+pub fn hsva(hue: f32, saturation: f32, value: f32, alpha: f32) -> Color {
+    let (r, g, b) = hsv_to_rgb(hue, saturation, value);
+    Color::Rgba(r, g, b, alpha)
+}
+
+/// Create [HSV colors](http://en.wikipedia.org/wiki/HSL_and_HSV). The hue is specified in radians.
+#[inline]
+pub fn hsv(hue: f32, saturation: f32, value: f32) -> Color {
+    hsva(hue, saturation, value, 1.0)
+}
+
+/// Create colors from the [Oklab](https://bottosson.github.io/posts/oklab/) perceptual color
+/// space with an alpha component for transparency.
+///
+/// `l` is the perceived lightness between 0.0 and 1.0, while `a` and `b` describe how green/red
+/// and blue/yellow the color is, typically within the range -0.4 to 0.4. Colors outside of the
+/// sRGB gamut are clamped.
+#[inline]
+pub fn oklaba(l: f32, a: f32, b: f32, alpha: f32) -> Color {
+    let (r, g, b) = oklab_to_rgb(l, a, b);
+    Color::Rgba(clampf32(r), clampf32(g), clampf32(b), alpha)
+}
+
+/// Create colors from the cylindrical form of Oklab, with the lightness, chroma and hue (in
+/// radians) along with an alpha component for transparency.
+///
+/// Stepping the hue while keeping the lightness and chroma constant produces colors of consistent
+/// perceived brightness, which is useful for generating palettes.
+#[inline]
+pub fn oklcha(l: f32, chroma: f32, hue: f32, alpha: f32) -> Color {
+    oklaba(l, chroma * hue.cos(), chroma * hue.sin(), alpha)
+}
+
 /// Produce a gray based on the input. 0.0 is white, 1.0 is black.
 pub fn grayscale(p: f32) -> Color {
     Color::Hsla(0.0, 0.0, 1.0 - p, 1.0)
@@ -121,22 +158,53 @@ impl Color {
         }
     }
 
+    /// Calculate the [relative luminance](https://www.w3.org/TR/WCAG21/#dfn-relative-luminance)
+    /// of the Color as defined by WCAG, ignoring alpha.
+    pub fn relative_luminance(self) -> f32 {
+        let Rgba(r, g, b, _) = self.to_rgb();
+        let (r, g, b) = (srgb_to_linear(r), srgb_to_linear(g), srgb_to_linear(b));
+        0.2126 * r + 0.7152 * g + 0.0722 * b
+    }
+
+    /// The [WCAG contrast ratio](https://www.w3.org/TR/WCAG21/#dfn-contrast-ratio) between this
+    /// Color and the `other` within the range 1.0 (no contrast) to 21.0 (black on white).
+    ///
+    /// WCAG recommends a ratio of at least 4.5 for normal text and 3.0 for large text.
+    pub fn contrast_ratio(self, other: Color) -> f32 {
+        let (a, b) = (self.relative_luminance(), other.relative_luminance());
+        (a.max(b) + 0.05) / (a.min(b) + 0.05)
+    }
+
+    /// Return whichever of the given candidates has the highest contrast ratio against this
+    /// Color, e.g. to pick the most readable label color for a background.
+    ///
+    /// Returns `None` if there are no candidates.
+    pub fn best_contrast<I>(self, candidates: I) -> Option<Color>
+    where
+        I: IntoIterator<Item = Color>,
+    {
+        candidates
+            .into_iter()
+            .map(|c| (self.contrast_ratio(c), c))
+            .fold(None, |best, (ratio, c)| match best {
+                Some((best_ratio, _)) if best_ratio >= ratio => best,
+                _ => Some((ratio, c)),
+            })
+            .map(|(_, c)| c)
+    }
+
     /// Return either black or white, depending which contrasts the Color the most. This will be
     /// useful for determining a readable color for text on any given background Color.
+    ///
+    /// The choice is made by comparing WCAG contrast ratios, so that the returned color always has
+    /// a contrast ratio of at least 4.5 against an opaque Color. Earlier versions thresholded the
+    /// weighted sum of the gamma-encoded channels at 0.5 instead, so some mid-tones now receive
+    /// the other color.
     pub fn plain_contrast(self) -> Color {
-        match self {
-            Color::Hsla(h, s, l, _) => {
-                let (r, g, b) = hsl_to_rgb(h, s, l);
-                rgb(r, g, b).plain_contrast()
-            }
-            Color::Rgba(r, g, b, _) => {
-                let l = 0.2126 * r + 0.7152 * g + 0.0722 * b;
-                if l > 0.5 {
-                    BLACK
-                } else {
-                    WHITE
-                }
-            }
+        if self.contrast_ratio(BLACK) > self.contrast_ratio(WHITE) {
+            BLACK
+        } else {
+            WHITE
         }
     }
 
@@ -162,6 +230,31 @@ impl Color {
         }
     }
 
+    /// Extract the components of a color in the HSV format.
+    pub fn to_hsv(self) -> Hsva {
+        let Rgba(r, g, b, a) = self.to_rgb();
+        let (h, s, v) = rgb_to_hsv(r, g, b);
+        Hsva(h, s, v, a)
+    }
+
+    /// Extract the components of a color in the Oklab format.
+    pub fn to_oklab(self) -> Oklaba {
+        let Rgba(r, g, b, a) = self.to_rgb();
+        let (l, ok_a, ok_b) = rgb_to_oklab(r, g, b);
+        Oklaba(l, ok_a, ok_b, a)
+    }
+
+    /// Extract the components of a color in the cylindrical Oklab (OkLCH) format.
+    ///
+    /// The hue is in radians within the range `[0.0, 2*PI)`.
+    pub fn to_oklch(self) -> Oklcha {
+        let Oklaba(l, a, b, alpha) = self.to_oklab();
+        let chroma = (a * a + b * b).sqrt();
+        let hue = b.atan2(a);
+        let hue = if hue < 0.0 { hue + 2.0 * PI } else { hue };
+        Oklcha(l, chroma, hue, alpha)
+    }
+
     /// Extract the components of a color in the RGB format within a fixed-size array.
     pub fn to_fsa(self) -> [f32; 4] {
         let Rgba(r, g, b, a) = self.to_rgb();
@@ -179,13 +272,47 @@ impl Color {
         ]
     }
 
-    // /// Return the hex representation of this color in the format #RRGGBBAA
-    // /// e.g. `Color(1.0, 0.0, 5.0, 1.0) == "#FF0080FF"`
-    // pub fn to_hex(self) -> String {
-    //     let vals = self.to_byte_fsa();
-    //     let hex = vals.to_hex().to_ascii_uppercase();
-    //     format!("#{}", &hex)
-    // }
+    /// Return the hex representation of this color in the format #RRGGBBAA
+    /// e.g. `rgba(1.0, 0.0, 0.5, 1.0).to_hex() == "#FF0080FF"`
+    ///
+    /// Each channel is rounded to the nearest byte so that the result round-trips via `from_hex`.
+    pub fn to_hex(self) -> String {
+        let Rgba(r, g, b, a) = self.to_rgb();
+        let byte = |c: f32| (clampf32(c) * 255.0).round() as u8;
+        format!(
+            "#{:02X}{:02X}{:02X}{:02X}",
+            byte(r),
+            byte(g),
+            byte(b),
+            byte(a)
+        )
+    }
+
+    /// Parse a color from its hex representation.
+    ///
+    /// The leading `#` is optional and the digits may be given in either of the forms `RGB`,
+    /// `RGBA`, `RRGGBB` or `RRGGBBAA`.
+    pub fn from_hex(hex: &str) -> Result<Color, ParseColorError> {
+        let hex = hex.trim();
+        let hex = if hex.starts_with('#') { &hex[1..] } else { hex };
+        if !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+            return Err(ParseColorError::InvalidHex);
+        }
+        let digit = |i: usize| u8::from_str_radix(&hex[i..i + 1], 16).unwrap();
+        let pair = |i: usize| u8::from_str_radix(&hex[i..i + 2], 16).unwrap();
+        let [r, g, b, a] = match hex.len() {
+            3 | 4 => {
+                let a = if hex.len() == 4 { digit(3) * 17 } else { 255 };
+                [digit(0) * 17, digit(1) * 17, digit(2) * 17, a]
+            }
+            6 | 8 => {
+                let a = if hex.len() == 8 { pair(6) } else { 255 };
+                [pair(0), pair(2), pair(4), a]
+            }
+            _ => return Err(ParseColorError::InvalidHex),
+        };
+        Ok(rgba_bytes(r, g, b, a as f32 / 255.0))
+    }
 
     /// Return the same color but with the given luminance.
     pub fn with_luminance(self, l: f32) -> Color {
@@ -251,6 +378,86 @@ impl Color {
         rgba(r, g, b, a)
     }
 
+    /// Linearly interpolate between this Color and `other` within the sRGB color space, where `t`
+    /// is `0.0` for `self` and `1.0` for `other`.
+    pub fn lerp(self, other: Color, t: f32) -> Color {
+        let Rgba(r1, g1, b1, a1) = self.to_rgb();
+        let Rgba(r2, g2, b2, a2) = other.to_rgb();
+        rgba(
+            lerpf32(r1, r2, t),
+            lerpf32(g1, g2, t),
+            lerpf32(b1, b2, t),
+            lerpf32(a1, a2, t),
+        )
+    }
+
+    /// Interpolate between this Color and `other` within the Oklab color space.
+    ///
+    /// Unlike `lerp`, the intermediate colors change in perceived lightness at an even rate and
+    /// avoid the muddy greys produced when mixing complementary colors in sRGB.
+    pub fn lerp_oklab(self, other: Color, t: f32) -> Color {
+        let Oklaba(l1, a1, b1, alpha1) = self.to_oklab();
+        let Oklaba(l2, a2, b2, alpha2) = other.to_oklab();
+        oklaba(
+            lerpf32(l1, l2, t),
+            lerpf32(a1, a2, t),
+            lerpf32(b1, b2, t),
+            lerpf32(alpha1, alpha2, t),
+        )
+    }
+
+    /// Interpolate between this Color and `other` within the OkLCH color space, taking the
+    /// shortest path around the hue circle.
+    ///
+    /// This preserves the vividness of the colors throughout the interpolation, making it well
+    /// suited to gradients between saturated colors.
+    pub fn lerp_oklch(self, other: Color, t: f32) -> Color {
+        let Oklcha(l1, c1, h1, alpha1) = self.to_oklch();
+        let Oklcha(l2, c2, h2, alpha2) = other.to_oklch();
+        // The hue of an achromatic color is meaningless, so use the other color's hue.
+        let (h1, h2) = match (c1 < 1e-4, c2 < 1e-4) {
+            (true, false) => (h2, h2),
+            (false, true) => (h1, h1),
+            _ => (h1, h2),
+        };
+        let mut dh = h2 - h1;
+        if dh > PI {
+            dh -= 2.0 * PI;
+        } else if dh < -PI {
+            dh += 2.0 * PI;
+        }
+        oklcha(
+            lerpf32(l1, l2, t),
+            lerpf32(c1, c2, t),
+            h1 + dh * t,
+            lerpf32(alpha1, alpha2, t),
+        )
+    }
+
+    /// Composite this Color over the given `backdrop` using the "source-over" operator, i.e. the
+    /// result of drawing this Color on top of the backdrop.
+    pub fn over(self, backdrop: Color) -> Color {
+        self.blend(backdrop, BlendMode::Normal)
+    }
+
+    /// Blend this Color onto the given `backdrop` using the given `BlendMode`, compositing the
+    /// result via the "source-over" operator as described by the
+    /// [W3C compositing specification](https://www.w3.org/TR/compositing-1/).
+    pub fn blend(self, backdrop: Color, mode: BlendMode) -> Color {
+        let Rgba(sr, sg, sb, sa) = self.to_rgb();
+        let Rgba(br, bg, bb, ba) = backdrop.to_rgb();
+        let (sa, ba) = (clampf32(sa), clampf32(ba));
+        let a = sa + ba * (1.0 - sa);
+        if a == 0.0 {
+            return TRANSPARENT;
+        }
+        let channel = |s: f32, b: f32| {
+            let s = (1.0 - ba) * s + ba * clampf32(mode.blend_channel(b, s));
+            (sa * s + ba * b * (1.0 - sa)) / a
+        };
+        rgba(channel(sr, br), channel(sg, bg), channel(sb, bb), a)
+    }
+
     /// Return the Color's invert.
     pub fn invert(self) -> Color {
         let Rgba(r, g, b, a) = self.to_rgb();
@@ -294,6 +501,91 @@ impl Color {
     }
 }
 
+/// The ways in which a source color may be blended with a backdrop color.
+///
+/// See the [W3C compositing specification](https://www.w3.org/TR/compositing-1/#blending) for a
+/// description of each mode.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum BlendMode {
+    /// The source color replaces the backdrop.
+    Normal,
+    /// Multiplies the source and backdrop, always producing a darker color.
+    Multiply,
+    /// Multiplies the complements of the source and backdrop, always producing a lighter color.
+    Screen,
+    /// Multiplies or screens the colors depending on the backdrop.
+    Overlay,
+    /// Selects the darker of the source and backdrop.
+    Darken,
+    /// Selects the lighter of the source and backdrop.
+    Lighten,
+    /// Brightens the backdrop to reflect the source.
+    ColorDodge,
+    /// Darkens the backdrop to reflect the source.
+    ColorBurn,
+    /// Multiplies or screens the colors depending on the source.
+    HardLight,
+    /// Darkens or lightens the colors depending on the source.
+    SoftLight,
+    /// Subtracts the darker of the two colors from the lighter.
+    Difference,
+    /// Similar to `Difference` but lower in contrast.
+    Exclusion,
+}
+
+impl BlendMode {
+    /// Blend a single backdrop color channel `b` with a source channel `s`.
+    pub fn blend_channel(self, b: f32, s: f32) -> f32 {
+        match self {
+            BlendMode::Normal => s,
+            BlendMode::Multiply => b * s,
+            BlendMode::Screen => b + s - b * s,
+            BlendMode::Overlay => BlendMode::HardLight.blend_channel(s, b),
+            BlendMode::Darken => b.min(s),
+            BlendMode::Lighten => b.max(s),
+            BlendMode::ColorDodge => {
+                if b == 0.0 {
+                    0.0
+                } else if s >= 1.0 {
+                    1.0
+                } else {
+                    (b / (1.0 - s)).min(1.0)
+                }
+            }
+            BlendMode::ColorBurn => {
+                if b >= 1.0 {
+                    1.0
+                } else if s <= 0.0 {
+                    0.0
+                } else {
+                    1.0 - ((1.0 - b) / s).min(1.0)
+                }
+            }
+            BlendMode::HardLight => {
+                if s <= 0.5 {
+                    BlendMode::Multiply.blend_channel(b, 2.0 * s)
+                } else {
+                    BlendMode::Screen.blend_channel(b, 2.0 * s - 1.0)
+                }
+            }
+            BlendMode::SoftLight => {
+                if s <= 0.5 {
+                    b - (1.0 - 2.0 * s) * b * (1.0 - b)
+                } else {
+                    let d = if b <= 0.25 {
+                        ((16.0 * b - 12.0) * b + 4.0) * b
+                    } else {
+                        b.sqrt()
+                    };
+                    b + (2.0 * s - 1.0) * (d - b)
+                }
+            }
+            BlendMode::Difference => (b - s).abs(),
+            BlendMode::Exclusion => b + s - 2.0 * b * s,
+        }
+    }
+}
+
 /// The parts of HSL along with an alpha for transparency.
 #[derive(Copy, Clone, Debug, PartialEq)]
 pub struct Hsla(pub f32, pub f32, pub f32, pub f32);
@@ -326,6 +618,54 @@ impl From<Rgba> for Color {
     }
 }
 
+/// The parts of HSV along with an alpha for transparency.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Hsva(pub f32, pub f32, pub f32, pub f32);
+
+impl From<Color> for Hsva {
+    fn from(color: Color) -> Self {
+        color.to_hsv()
+    }
+}
+
+impl From<Hsva> for Color {
+    fn from(Hsva(h, s, v, a): Hsva) -> Self {
+        hsva(h, s, v, a)
+    }
+}
+
+/// The parts of Oklab (lightness, green/red and blue/yellow) along with an alpha for transparency.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Oklaba(pub f32, pub f32, pub f32, pub f32);
+
+impl From<Color> for Oklaba {
+    fn from(color: Color) -> Self {
+        color.to_oklab()
+    }
+}
+
+impl From<Oklaba> for Color {
+    fn from(Oklaba(l, a, b, alpha): Oklaba) -> Self {
+        oklaba(l, a, b, alpha)
+    }
+}
+
+/// The parts of OkLCH (lightness, chroma and hue) along with an alpha for transparency.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Oklcha(pub f32, pub f32, pub f32, pub f32);
+
+impl From<Color> for Oklcha {
+    fn from(color: Color) -> Self {
+        color.to_oklch()
+    }
+}
+
+impl From<Oklcha> for Color {
+    fn from(Oklcha(l, c, h, alpha): Oklcha) -> Self {
+        oklcha(l, c, h, alpha)
+    }
+}
+
 impl Into<[f32; 4]> for Rgba {
     fn into(self) -> [f32; 4] {
         let Rgba(r, g, b, a) = self;
@@ -333,6 +673,203 @@ impl Into<[f32; 4]> for Rgba {
     }
 }
 
+/// Parses a `Color` from any of the following CSS-style forms (case-insensitive):
+///
+/// - Hex colors, e.g. `#F80`, `#F80C`, `#FF8800` or `#FF8800CC`.
+/// - Named colors, e.g. `rebeccapurple` or `transparent` (see `named`).
+/// - RGB functions, e.g. `rgb(255, 136, 0)`, `rgba(100%, 50%, 0%, 0.8)` or `rgb(255 136 0 / 80%)`.
+/// - HSL functions, e.g. `hsl(32, 100%, 50%)`, `hsla(0.5turn, 100%, 50%, 0.8)`.
+impl std::str::FromStr for Color {
+    type Err = ParseColorError;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        if s.is_empty() {
+            return Err(ParseColorError::Empty);
+        }
+        if s.starts_with('#') {
+            return Color::from_hex(s);
+        }
+        let lower = s.to_ascii_lowercase();
+        match lower.find('(') {
+            Some(open) => {
+                if !lower.ends_with(')') {
+                    return Err(ParseColorError::InvalidFunction);
+                }
+                let args = parse_color_args(&lower[open + 1..lower.len() - 1])?;
+                match lower[..open].trim() {
+                    "rgb" | "rgba" => parse_rgb_args(&args),
+                    "hsl" | "hsla" => parse_hsl_args(&args),
+                    _ => Err(ParseColorError::InvalidFunction),
+                }
+            }
+            None => named(&lower).ok_or(ParseColorError::UnknownName),
+        }
+    }
+}
+
+/// An error produced when parsing a `Color` from a string.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum ParseColorError {
+    /// The string was empty.
+    Empty,
+    /// A hex color did not consist of 3, 4, 6 or 8 hex digits.
+    InvalidHex,
+    /// The string was not a hex color or function and did not match any known color name.
+    UnknownName,
+    /// The string was not one of the supported `rgb()`, `rgba()`, `hsl()` or `hsla()` functions.
+    InvalidFunction,
+    /// A color function was given the wrong number of arguments.
+    InvalidArgumentCount,
+    /// A color function argument could not be parsed.
+    InvalidArgument,
+}
+
+impl std::error::Error for ParseColorError {}
+
+impl std::fmt::Display for ParseColorError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> Result<(), std::fmt::Error> {
+        let s = match *self {
+            ParseColorError::Empty => "The color string was empty.",
+            ParseColorError::InvalidHex => "A hex color must consist of 3, 4, 6 or 8 hex digits.",
+            ParseColorError::UnknownName => "The color name was not recognised.",
+            ParseColorError::InvalidFunction => {
+                "Expected one of the `rgb()`, `rgba()`, `hsl()` or `hsla()` color functions."
+            }
+            ParseColorError::InvalidArgumentCount => {
+                "A color function expects either 3 or 4 arguments."
+            }
+            ParseColorError::InvalidArgument => "A color function argument could not be parsed.",
+        };
+        write!(f, "{}", s)
+    }
+}
+
+/// A single argument of a CSS-style color function.
+#[derive(Copy, Clone, Debug, PartialEq)]
+enum ColorArg {
+    /// A plain number along with its unit, if any, e.g. `deg` or `turn`.
+    Number(f32, AngleUnit),
+    /// A percentage, normalised to the range `0.0..=1.0`.
+    Percentage(f32),
+}
+
+/// The units in which the hue of an `hsl()` color may be specified.
+#[derive(Copy, Clone, Debug, PartialEq)]
+enum AngleUnit {
+    None,
+    Degrees,
+    Radians,
+    Turns,
+}
+
+/// Split the arguments of a color function by either commas or whitespace, with the alpha
+/// optionally separated by a `/`.
+///
+/// The separators may not be mixed: either every argument is separated by a comma, or every
+/// argument is separated by whitespace, in which case a `/` may only separate the three channels
+/// from the alpha. Empty arguments are rejected.
+fn parse_color_args(args: &str) -> Result<Vec<ColorArg>, ParseColorError> {
+    let mut tokens = vec![];
+    if args.contains(',') {
+        for arg in args.split(',') {
+            let arg = arg.trim();
+            if arg.is_empty() || arg.contains('/') || arg.contains(char::is_whitespace) {
+                return Err(ParseColorError::InvalidArgument);
+            }
+            tokens.push(arg);
+        }
+    } else {
+        let mut parts = args.split('/');
+        let channels = parts.next().unwrap_or("");
+        tokens.extend(channels.split_whitespace());
+        if let Some(alpha) = parts.next() {
+            let mut alpha = alpha.split_whitespace();
+            match (alpha.next(), alpha.next(), parts.next()) {
+                (Some(alpha), None, None) if tokens.len() == 3 => tokens.push(alpha),
+                _ => return Err(ParseColorError::InvalidArgument),
+            }
+        }
+    }
+    tokens.into_iter().map(parse_color_arg).collect()
+}
+
+/// Parse a single color function argument.
+fn parse_color_arg(arg: &str) -> Result<ColorArg, ParseColorError> {
+    let (number, unit) = if arg.ends_with('%') {
+        let pc = arg[..arg.len() - 1].parse::<f32>();
+        return pc
+            .map(|pc| ColorArg::Percentage(pc / 100.0))
+            .map_err(|_| ParseColorError::InvalidArgument);
+    } else if arg.ends_with("deg") {
+        (&arg[..arg.len() - 3], AngleUnit::Degrees)
+    } else if arg.ends_with("rad") {
+        (&arg[..arg.len() - 3], AngleUnit::Radians)
+    } else if arg.ends_with("turn") {
+        (&arg[..arg.len() - 4], AngleUnit::Turns)
+    } else {
+        (arg, AngleUnit::None)
+    };
+    number
+        .parse::<f32>()
+        .map(|n| ColorArg::Number(n, unit))
+        .map_err(|_| ParseColorError::InvalidArgument)
+}
+
+/// The alpha channel given as the optional fourth color function argument.
+fn parse_alpha_arg(args: &[ColorArg]) -> Result<f32, ParseColorError> {
+    match args.get(3) {
+        None => Ok(1.0),
+        Some(&ColorArg::Number(a, AngleUnit::None)) | Some(&ColorArg::Percentage(a)) => {
+            Ok(clampf32(a))
+        }
+        Some(_) => Err(ParseColorError::InvalidArgument),
+    }
+}
+
+/// Produce a color from the arguments of an `rgb()` or `rgba()` function.
+fn parse_rgb_args(args: &[ColorArg]) -> Result<Color, ParseColorError> {
+    if args.len() != 3 && args.len() != 4 {
+        return Err(ParseColorError::InvalidArgumentCount);
+    }
+    let mut channels = [0.0; 3];
+    for (channel, arg) in channels.iter_mut().zip(args) {
+        *channel = match *arg {
+            ColorArg::Number(c, AngleUnit::None) => clampf32(c / 255.0),
+            ColorArg::Percentage(c) => clampf32(c),
+            _ => return Err(ParseColorError::InvalidArgument),
+        };
+    }
+    let a = parse_alpha_arg(args)?;
+    Ok(rgba(channels[0], channels[1], channels[2], a))
+}
+
+/// Produce a color from the arguments of an `hsl()` or `hsla()` function.
+///
+/// The hue is in degrees unless otherwise specified. Saturation and lightness may be given either
+/// as percentages or plain numbers within `0..=100`.
+fn parse_hsl_args(args: &[ColorArg]) -> Result<Color, ParseColorError> {
+    if args.len() != 3 && args.len() != 4 {
+        return Err(ParseColorError::InvalidArgumentCount);
+    }
+    let hue = match args[0] {
+        ColorArg::Number(h, AngleUnit::None) | ColorArg::Number(h, AngleUnit::Degrees) => {
+            degrees(h)
+        }
+        ColorArg::Number(h, AngleUnit::Radians) => h,
+        ColorArg::Number(h, AngleUnit::Turns) => turns(h),
+        ColorArg::Percentage(_) => return Err(ParseColorError::InvalidArgument),
+    };
+    let percentage = |arg: ColorArg| match arg {
+        ColorArg::Number(p, AngleUnit::None) => Ok(clampf32(p / 100.0)),
+        ColorArg::Percentage(p) => Ok(clampf32(p)),
+        _ => Err(ParseColorError::InvalidArgument),
+    };
+    let saturation = percentage(args[1])?;
+    let lightness = percentage(args[2])?;
+    let a = parse_alpha_arg(args)?;
+    Ok(hsla(hue, saturation, lightness, a))
+}
+
 /// Convert an f32 color to a byte.
 #[inline]
 pub fn f32_to_byte(c: f32) -> u8 {
@@ -389,6 +926,85 @@ pub fn hsl_to_rgb(hue: f32, saturation: f32, lightness: f32) -> (f32, f32, f32)
     (r + m, g + m, b + m)
 }
 
+/// Pure function for converting rgb to hsv.
+/// * Inputs expected to be between `0.0` and `1.0`.
+/// * Outputs `[0.0, 2*PI)` for `h`, `[0.0, 1.0]` for both `s` and `v`
+pub fn rgb_to_hsv(r: f32, g: f32, b: f32) -> (f32, f32, f32) {
+    let (hue, _, _) = rgb_to_hsl(r, g, b);
+    let c_max = r.max(g).max(b);
+    let c_min = r.min(g).min(b);
+    let saturation = if c_max == 0.0 {
+        0.0
+    } else {
+        (c_max - c_min) / c_max
+    };
+    (hue, saturation, c_max)
+}
+
+/// Pure function for converting hsv to rgb.
+pub fn hsv_to_rgb(hue: f32, saturation: f32, value: f32) -> (f32, f32, f32) {
+    // Convert via HSL, which shares the same hue.
+    let lightness = value * (1.0 - saturation / 2.0);
+    let saturation = if lightness == 0.0 || lightness == 1.0 {
+        0.0
+    } else {
+        (value - lightness) / lightness.min(1.0 - lightness)
+    };
+    let hue = hue - turns((hue / (2.0 * PI)).floor());
+    hsl_to_rgb(hue, saturation, lightness)
+}
+
+/// Convert a gamma-encoded sRGB channel to linear light.
+pub fn srgb_to_linear(c: f32) -> f32 {
+    if c <= 0.04045 {
+        c / 12.92
+    } else {
+        ((c + 0.055) / 1.055).powf(2.4)
+    }
+}
+
+/// Convert a linear light channel to gamma-encoded sRGB.
+pub fn linear_to_srgb(c: f32) -> f32 {
+    if c <= 0.0031308 {
+        c * 12.92
+    } else {
+        1.055 * c.powf(1.0 / 2.4) - 0.055
+    }
+}
+
+/// Pure function for converting sRGB to [Oklab](https://bottosson.github.io/posts/oklab/).
+pub fn rgb_to_oklab(r: f32, g: f32, b: f32) -> (f32, f32, f32) {
+    let (r, g, b) = (srgb_to_linear(r), srgb_to_linear(g), srgb_to_linear(b));
+    let l = (0.4122214708 * r + 0.5363325363 * g + 0.0514459929 * b).cbrt();
+    let m = (0.2119034982 * r + 0.6806995451 * g + 0.1073969566 * b).cbrt();
+    let s = (0.0883024619 * r + 0.2817188376 * g + 0.6299787005 * b).cbrt();
+    (
+        0.2104542553 * l + 0.7936177850 * m - 0.0040720468 * s,
+        1.9779984951 * l - 2.4285922050 * m + 0.4505937099 * s,
+        0.0259040371 * l + 0.7827717662 * m - 0.8086757660 * s,
+    )
+}
+
+/// Pure function for converting Oklab to sRGB.
+///
+/// Colors outside of the sRGB gamut produce channels outside of the range `0.0..=1.0`.
+pub fn oklab_to_rgb(l: f32, a: f32, b: f32) -> (f32, f32, f32) {
+    let cube = |x: f32| x * x * x;
+    let lc = cube(l + 0.3963377774 * a + 0.2158037573 * b);
+    let mc = cube(l - 0.1055613458 * a - 0.0638541728 * b);
+    let sc = cube(l - 0.0894841775 * a - 1.2914855480 * b);
+    (
+        linear_to_srgb(4.0767416621 * lc - 3.3077115913 * mc + 0.2309699292 * sc),
+        linear_to_srgb(-1.2684380046 * lc + 2.6097574011 * mc - 0.3413193965 * sc),
+        linear_to_srgb(-0.0041960863 * lc - 0.7034186147 * mc + 1.7076147010 * sc),
+    )
+}
+
+/// Linearly interpolate between `a` and `b` by `t`.
+fn lerpf32(a: f32, b: f32, t: f32) -> f32 {
+    a + (b - a) * t
+}
+
 /// Linear or Radial Gradient.
 #[derive(Clone, Debug)]
 pub enum Gradient {
@@ -516,6 +1132,177 @@ pub const DARK_CHARCOAL: Color = make_color!(46, 52, 54);
 /// Transparent
 pub const TRANSPARENT: Color = Color::Rgba(0.0, 0.0, 0.0, 0.0);
 
+/// Look up a [CSS named color](https://www.w3.org/TR/css-color-4/#named-colors) by name,
+/// ignoring case.
+///
+/// Note that these follow the CSS specification and so differ from the Tango-based constants in
+/// this module, e.g. `named("red")` is `#FF0000` whereas `RED` is `#CC0000`.
+pub fn named(name: &str) -> Option<Color> {
+    let name = name.trim().to_ascii_lowercase();
+    if name == "transparent" {
+        return Some(TRANSPARENT);
+    }
+    CSS_NAMED_COLORS
+        .binary_search_by(|&(n, _)| n.cmp(&name[..]))
+        .ok()
+        .map(|i| {
+            let [r, g, b] = CSS_NAMED_COLORS[i].1;
+            rgb_bytes(r, g, b)
+        })
+}
+
+/// The CSS named colors, sorted by name.
+const CSS_NAMED_COLORS: &[(&str, [u8; 3])] = &[
+    ("aliceblue", [0xF0, 0xF8, 0xFF]),
+    ("antiquewhite", [0xFA, 0xEB, 0xD7]),
+    ("aqua", [0x00, 0xFF, 0xFF]),
+    ("aquamarine", [0x7F, 0xFF, 0xD4]),
+    ("azure", [0xF0, 0xFF, 0xFF]),
+    ("beige", [0xF5, 0xF5, 0xDC]),
+    ("bisque", [0xFF, 0xE4, 0xC4]),
+    ("black", [0x00, 0x00, 0x00]),
+    ("blanchedalmond", [0xFF, 0xEB, 0xCD]),
+    ("blue", [0x00, 0x00, 0xFF]),
+    ("blueviolet", [0x8A, 0x2B, 0xE2]),
+    ("brown", [0xA5, 0x2A, 0x2A]),
+    ("burlywood", [0xDE, 0xB8, 0x87]),
+    ("cadetblue", [0x5F, 0x9E, 0xA0]),
+    ("chartreuse", [0x7F, 0xFF, 0x00]),
+    ("chocolate", [0xD2, 0x69, 0x1E]),
+    ("coral", [0xFF, 0x7F, 0x50]),
+    ("cornflowerblue", [0x64, 0x95, 0xED]),
+    ("cornsilk", [0xFF, 0xF8, 0xDC]),
+    ("crimson", [0xDC, 0x14, 0x3C]),
+    ("cyan", [0x00, 0xFF, 0xFF]),
+    ("darkblue", [0x00, 0x00, 0x8B]),
+    ("darkcyan", [0x00, 0x8B, 0x8B]),
+    ("darkgoldenrod", [0xB8, 0x86, 0x0B]),
+    ("darkgray", [0xA9, 0xA9, 0xA9]),
+    ("darkgreen", [0x00, 0x64, 0x00]),
+    ("darkgrey", [0xA9, 0xA9, 0xA9]),
+    ("darkkhaki", [0xBD, 0xB7, 0x6B]),
+    ("darkmagenta", [0x8B, 0x00, 0x8B]),
+    ("darkolivegreen", [0x55, 0x6B, 0x2F]),
+    ("darkorange", [0xFF, 0x8C, 0x00]),
+    ("darkorchid", [0x99, 0x32, 0xCC]),
+    ("darkred", [0x8B, 0x00, 0x00]),
+    ("darksalmon", [0xE9, 0x96, 0x7A]),
+    ("darkseagreen", [0x8F, 0xBC, 0x8F]),
+    ("darkslateblue", [0x48, 0x3D, 0x8B]),
+    ("darkslategray", [0x2F, 0x4F, 0x4F]),
+    ("darkslategrey", [0x2F, 0x4F, 0x4F]),
+    ("darkturquoise", [0x00, 0xCE, 0xD1]),
+    ("darkviolet", [0x94, 0x00, 0xD3]),
+    ("deeppink", [0xFF, 0x14, 0x93]),
+    ("deepskyblue", [0x00, 0xBF, 0xFF]),
+    ("dimgray", [0x69, 0x69, 0x69]),
+    ("dimgrey", [0x69, 0x69, 0x69]),
+    ("dodgerblue", [0x1E, 0x90, 0xFF]),
+    ("firebrick", [0xB2, 0x22, 0x22]),
+    ("floralwhite", [0xFF, 0xFA, 0xF0]),
+    ("forestgreen", [0x22, 0x8B, 0x22]),
+    ("fuchsia", [0xFF, 0x00, 0xFF]),
+    ("gainsboro", [0xDC, 0xDC, 0xDC]),
+    ("ghostwhite", [0xF8, 0xF8, 0xFF]),
+    ("gold", [0xFF, 0xD7, 0x00]),
+    ("goldenrod", [0xDA, 0xA5, 0x20]),
+    ("gray", [0x80, 0x80, 0x80]),
+    ("green", [0x00, 0x80, 0x00]),
+    ("greenyellow", [0xAD, 0xFF, 0x2F]),
+    ("grey", [0x80, 0x80, 0x80]),
+    ("honeydew", [0xF0, 0xFF, 0xF0]),
+    ("hotpink", [0xFF, 0x69, 0xB4]),
+    ("indianred", [0xCD, 0x5C, 0x5C]),
+    ("indigo", [0x4B, 0x00, 0x82]),
+    ("ivory", [0xFF, 0xFF, 0xF0]),
+    ("khaki", [0xF0, 0xE6, 0x8C]),
+    ("lavender", [0xE6, 0xE6, 0xFA]),
+    ("lavenderblush", [0xFF, 0xF0, 0xF5]),
+    ("lawngreen", [0x7C, 0xFC, 0x00]),
+    ("lemonchiffon", [0xFF, 0xFA, 0xCD]),
+    ("lightblue", [0xAD, 0xD8, 0xE6]),
+    ("lightcoral", [0xF0, 0x80, 0x80]),
+    ("lightcyan", [0xE0, 0xFF, 0xFF]),
+    ("lightgoldenrodyellow", [0xFA, 0xFA, 0xD2]),
+    ("lightgray", [0xD3, 0xD3, 0xD3]),
+    ("lightgreen", [0x90, 0xEE, 0x90]),
+    ("lightgrey", [0xD3, 0xD3, 0xD3]),
+    ("lightpink", [0xFF, 0xB6, 0xC1]),
+    ("lightsalmon", [0xFF, 0xA0, 0x7A]),
+    ("lightseagreen", [0x20, 0xB2, 0xAA]),
+    ("lightskyblue", [0x87, 0xCE, 0xFA]),
+    ("lightslategray", [0x77, 0x88, 0x99]),
+    ("lightslategrey", [0x77, 0x88, 0x99]),
+    ("lightsteelblue", [0xB0, 0xC4, 0xDE]),
+    ("lightyellow", [0xFF, 0xFF, 0xE0]),
+    ("lime", [0x00, 0xFF, 0x00]),
+    ("limegreen", [0x32, 0xCD, 0x32]),
+    ("linen", [0xFA, 0xF0, 0xE6]),
+    ("magenta", [0xFF, 0x00, 0xFF]),
+    ("maroon", [0x80, 0x00, 0x00]),
+    ("mediumaquamarine", [0x66, 0xCD, 0xAA]),
+    ("mediumblue", [0x00, 0x00, 0xCD]),
+    ("mediumorchid", [0xBA, 0x55, 0xD3]),
+    ("mediumpurple", [0x93, 0x70, 0xDB]),
+    ("mediumseagreen", [0x3C, 0xB3, 0x71]),
+    ("mediumslateblue", [0x7B, 0x68, 0xEE]),
+    ("mediumspringgreen", [0x00, 0xFA, 0x9A]),
+    ("mediumturquoise", [0x48, 0xD1, 0xCC]),
+    ("mediumvioletred", [0xC7, 0x15, 0x85]),
+    ("midnightblue", [0x19, 0x19, 0x70]),
+    ("mintcream", [0xF5, 0xFF, 0xFA]),
+    ("mistyrose", [0xFF, 0xE4, 0xE1]),
+    ("moccasin", [0xFF, 0xE4, 0xB5]),
+    ("navajowhite", [0xFF, 0xDE, 0xAD]),
+    ("navy", [0x00, 0x00, 0x80]),
+    ("oldlace", [0xFD, 0xF5, 0xE6]),
+    ("olive", [0x80, 0x80, 0x00]),
+    ("olivedrab", [0x6B, 0x8E, 0x23]),
+    ("orange", [0xFF, 0xA5, 0x00]),
+    ("orangered", [0xFF, 0x45, 0x00]),
+    ("orchid", [0xDA, 0x70, 0xD6]),
+    ("palegoldenrod", [0xEE, 0xE8, 0xAA]),
+    ("palegreen", [0x98, 0xFB, 0x98]),
+    ("paleturquoise", [0xAF, 0xEE, 0xEE]),
+    ("palevioletred", [0xDB, 0x70, 0x93]),
+    ("papayawhip", [0xFF, 0xEF, 0xD5]),
+    ("peachpuff", [0xFF, 0xDA, 0xB9]),
+    ("peru", [0xCD, 0x85, 0x3F]),
+    ("pink", [0xFF, 0xC0, 0xCB]),
+    ("plum", [0xDD, 0xA0, 0xDD]),
+    ("powderblue", [0xB0, 0xE0, 0xE6]),
+    ("purple", [0x80, 0x00, 0x80]),
+    ("rebeccapurple", [0x66, 0x33, 0x99]),
+    ("red", [0xFF, 0x00, 0x00]),
+    ("rosybrown", [0xBC, 0x8F, 0x8F]),
+    ("royalblue", [0x41, 0x69, 0xE1]),
+    ("saddlebrown", [0x8B, 0x45, 0x13]),
+    ("salmon", [0xFA, 0x80, 0x72]),
+    ("sandybrown", [0xF4, 0xA4, 0x60]),
+    ("seagreen", [0x2E, 0x8B, 0x57]),
+    ("seashell", [0xFF, 0xF5, 0xEE]),
+    ("sienna", [0xA0, 0x52, 0x2D]),
+    ("silver", [0xC0, 0xC0, 0xC0]),
+    ("skyblue", [0x87, 0xCE, 0xEB]),
+    ("slateblue", [0x6A, 0x5A, 0xCD]),
+    ("slategray", [0x70, 0x80, 0x90]),
+    ("slategrey", [0x70, 0x80, 0x90]),
+    ("snow", [0xFF, 0xFA, 0xFA]),
+    ("springgreen", [0x00, 0xFF, 0x7F]),
+    ("steelblue", [0x46, 0x82, 0xB4]),
+    ("tan", [0xD2, 0xB4, 0x8C]),
+    ("teal", [0x00, 0x80, 0x80]),
+    ("thistle", [0xD8, 0xBF, 0xD8]),
+    ("tomato", [0xFF, 0x63, 0x47]),
+    ("turquoise", [0x40, 0xE0, 0xD0]),
+    ("violet", [0xEE, 0x82, 0xEE]),
+    ("wheat", [0xF5, 0xDE, 0xB3]),
+    ("white", [0xFF, 0xFF, 0xFF]),
+    ("whitesmoke", [0xF5, 0xF5, 0xF5]),
+    ("yellow", [0xFF, 0xFF, 0x00]),
+    ("yellowgreen", [0x9A, 0xCD, 0x32]),
+];
+
 /// Types that can be colored.
 pub trait Colorable: Sized {
     /// Set the color of the widget.
@@ -7,7 +7,7 @@
 use super::{EdgeIndex, Graph};
 use daggy::Walker;
 use fnv;
-use position::{Point, Rect};
+use position::{Point, Rect, Transform};
 use theme::Theme;
 use widget;
 
@@ -51,8 +51,9 @@ impl PickWidgets {
                 None => continue,
                 Some(rect) => rect,
             };
-            if !visible_rect.is_over(self.xy) {
-                continue;
+            match untransformed_point(graph, idx, self.xy) {
+                Some(xy) if visible_rect.is_over(xy) => (),
+                _ => continue,
             }
             // Now that we know we're over the bounding box, we can check the more
             // detailed widget-specific `is_over` function.
@@ -62,7 +63,11 @@ impl PickWidgets {
                     None => break,
                     Some(container) => container,
                 };
-                match (container.is_over.0)(&container, self.xy, theme) {
+                let xy = match untransformed_point(graph, id, self.xy) {
+                    None => break,
+                    Some(xy) => xy,
+                };
+                match (container.is_over.0)(&container, xy, theme) {
                     widget::IsOver::Bool(false) => break,
                     widget::IsOver::Bool(true) => return Some(id),
                     widget::IsOver::Widget(w_id) => {
@@ -213,6 +218,55 @@ fn cropped_area_of_widget_maybe_within_depth(
     })
 }
 
+/// The opacity with which the widget with the given index should be rendered.
+///
+/// This is the product of the opacity of the widget and that of all of its depth parents.
+pub fn opacity_of_widget(graph: &Graph, id: widget::Id) -> f32 {
+    let mut opacity = graph.widget(id).map(|widget| widget.opacity).unwrap_or(1.0);
+    let mut depth_parents = graph.depth_parent_recursion(id);
+    while let Some(depth_parent) = depth_parents.next_node(graph) {
+        if let Some(depth_parent_widget) = graph.widget(depth_parent) {
+            opacity *= depth_parent_widget.opacity;
+        }
+    }
+    opacity
+}
+
+/// The transform with which the widget with the given index should be rendered.
+///
+/// This combines the transform of the widget with that of all of its depth parents, each applied
+/// about the centre of their own `Rect`. Returns `None` if neither the widget nor any of its depth
+/// parents are transformed.
+pub fn transform_of_widget(graph: &Graph, id: widget::Id) -> Option<Transform> {
+    let about_centre = |id| {
+        graph.widget(id).and_then(|widget| {
+            widget
+                .maybe_transform
+                .map(|transform| transform.about(widget.rect.xy()))
+        })
+    };
+    let mut maybe_transform = about_centre(id);
+    let mut depth_parents = graph.depth_parent_recursion(id);
+    while let Some(depth_parent) = depth_parents.next_node(graph) {
+        if let Some(parent_transform) = about_centre(depth_parent) {
+            let transform = maybe_transform.unwrap_or_default();
+            maybe_transform = Some(transform.then(parent_transform));
+        }
+    }
+    maybe_transform
+}
+
+/// Map the given point in window space into the untransformed space of the widget with the given
+/// index, in which its `Rect` is laid out.
+///
+/// Returns `None` if the widget's transform cannot be undone (e.g. it is scaled to nothing).
+pub fn untransformed_point(graph: &Graph, id: widget::Id, xy: Point) -> Option<Point> {
+    match transform_of_widget(graph, id) {
+        None => Some(xy),
+        Some(transform) => transform.inverse().map(|inverse| inverse.apply(xy)),
+    }
+}
+
 /// Find the absolute `Rect` that bounds all widgets that are `Depth` children of the widget at the
 /// given `idx`.
 ///
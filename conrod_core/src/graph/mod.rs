@@ -4,7 +4,7 @@
 //! The primary type of interest in this module is the [**Graph**](./struct.Graph) type.
 
 use daggy;
-use position::{Axis, Depth, Point, Rect};
+use position::{Axis, Depth, Point, Rect, Transform};
 use std;
 use std::any::Any;
 use std::ops::{Index, IndexMut};
@@ -87,6 +87,16 @@ pub struct Container {
     pub maybe_floating: Option<widget::Floating>,
     /// Whether or not children widgets should be cropped to the `kid_area`.
     pub crop_kids: bool,
+    /// The shape within the `kid_area` to which children widgets are cropped.
+    pub crop_shape: widget::CropShape,
+    /// The opacity of the widget and its descendants.
+    ///
+    /// See the `Widget::opacity` docs for an explanation of what this means.
+    pub opacity: f32,
+    /// The transform applied to the widget and its descendants about the centre of its `rect`.
+    ///
+    /// See the `Widget::transform` docs for an explanation of what this means.
+    pub maybe_transform: Option<Transform>,
     /// Scroll related state (is only `Some` if this axis is scrollable).
     pub maybe_x_scroll_state: Option<widget::scroll::StateX>,
     /// Scroll related state (is only `Some` if this axis is scrollable).
@@ -664,6 +674,9 @@ impl Graph {
             maybe_dragged_from,
             maybe_floating,
             crop_kids,
+            crop_shape,
+            opacity,
+            maybe_transform,
             maybe_x_scroll_state,
             maybe_y_scroll_state,
             maybe_graphics_for,
@@ -686,6 +699,9 @@ impl Graph {
             maybe_dragged_from: maybe_dragged_from,
             maybe_floating: maybe_floating,
             crop_kids: crop_kids,
+            crop_shape: crop_shape,
+            opacity: opacity,
+            maybe_transform: maybe_transform,
             maybe_x_scroll_state: maybe_x_scroll_state,
             maybe_y_scroll_state: maybe_y_scroll_state,
             instantiation_order_idx: instantiation_order_idx,
@@ -750,6 +766,9 @@ impl Graph {
                 container.maybe_dragged_from = maybe_dragged_from;
                 container.maybe_floating = maybe_floating;
                 container.crop_kids = crop_kids;
+                container.crop_shape = crop_shape;
+                container.opacity = opacity;
+                container.maybe_transform = maybe_transform;
                 container.maybe_x_scroll_state = maybe_x_scroll_state;
                 container.maybe_y_scroll_state = maybe_y_scroll_state;
                 container.instantiation_order_idx = instantiation_order_idx;
@@ -186,7 +186,7 @@ the UI every time round the loop. Here's the main loop:
 'main: loop {
     // Render the `Ui` and then display it on the screen.
     if let Some(primitives) = ui.draw_if_changed() {
-        renderer.fill(&display, primitives, &image_map);
+        renderer.fill(&display, primitives, &image_map).unwrap();
         let mut target = display.draw();
         target.clear_color(0.0, 1.0, 0.0, 1.0);
         renderer.draw(&display, &mut target, &image_map).unwrap();
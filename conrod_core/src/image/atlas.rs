@@ -0,0 +1,253 @@
+//! Packing many small images into a few shared textures.
+//!
+//! Each `image::Id` usually refers to its own texture, requiring a separate draw command for each
+//! image that is drawn. An **Atlas** instead packs many images into a few larger "page" textures
+//! and rewrites the `Image` primitives that refer to them so that they draw the area of the page
+//! at which the image was placed. Consecutive images on the same page may then be drawn with a
+//! single command.
+//!
+//! ```ignore
+//! let atlas = image::atlas::Builder::new([1024, 1024])
+//!     .padding(1)
+//!     .build(&mut image_map, icon_ids, |page, image_map| {
+//!         // Create a texture with the page's dimensions and copy the pixels of each of the
+//!         // page's `placements` from their images within the `image_map`.
+//!     });
+//!
+//! // Draw the `Ui` as usual, although via the atlas.
+//! let primitives = atlas.primitives(ui.draw());
+//! ```
+
+use super::{Id, Map};
+use fnv;
+use mesh::{self, ImageDimensions};
+use position::{Range, Rect};
+use render::{Primitive, PrimitiveKind, PrimitiveWalker};
+use std;
+
+/// Packs images into pages, producing an **Atlas**.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Builder {
+    /// The dimensions of each page in pixels.
+    pub page_dimensions: [u32; 2],
+    /// The number of pixels left empty around each image.
+    pub padding: u32,
+}
+
+/// A single texture onto which images are packed.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Page {
+    /// The dimensions of the page in pixels.
+    pub dimensions: [u32; 2],
+    /// The location of each image on the page.
+    pub placements: Vec<Placement>,
+}
+
+/// The location of an image on a **Page**.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Placement {
+    /// The image that is placed.
+    pub image_id: Id,
+    /// The position of the image's top left pixel, in pixels from the top left of the page as is
+    /// usual for texture data.
+    pub top_left: [u32; 2],
+    /// The dimensions of the image in pixels.
+    pub dimensions: [u32; 2],
+}
+
+/// Maps packed images to the area of the page on which they were placed.
+#[derive(Clone, Debug, Default)]
+pub struct Atlas {
+    images: fnv::FnvHashMap<Id, (Id, Rect)>,
+    page_ids: Vec<Id>,
+}
+
+/// A `PrimitiveWalker` that draws each packed image from its page, produced by the
+/// `Atlas::primitives` method.
+pub struct Primitives<'a, P> {
+    atlas: &'a Atlas,
+    primitives: P,
+}
+
+impl Builder {
+    /// Begin building an atlas whose pages have the given dimensions in pixels.
+    pub fn new(page_dimensions: [u32; 2]) -> Self {
+        Builder {
+            page_dimensions: page_dimensions,
+            padding: 0,
+        }
+    }
+
+    /// The number of pixels to leave empty around each image.
+    ///
+    /// Without padding, sampling near the edge of an image that is scaled may blend in the pixels
+    /// of its neighbours.
+    pub fn padding(mut self, padding: u32) -> Self {
+        self.padding = padding;
+        self
+    }
+
+    /// Place the images with the given ids and dimensions onto as few pages as possible.
+    ///
+    /// Images are placed in rows from the tallest to the shortest. Images that are empty or that
+    /// do not fit within a single page along with their padding are not placed.
+    pub fn pack<I>(&self, images: I) -> Vec<Page>
+    where
+        I: IntoIterator<Item = (Id, [u32; 2])>,
+    {
+        let [page_w, page_h] = self.page_dimensions;
+        let pad = self.padding;
+        let padded = |len: u32| len.saturating_add(pad.saturating_mul(2));
+        let mut images: Vec<_> = images
+            .into_iter()
+            .filter(|&(_, [w, h])| w > 0 && h > 0 && padded(w) <= page_w && padded(h) <= page_h)
+            .collect();
+        images.sort_by_key(|&(_, [w, h])| std::cmp::Reverse((h, w)));
+
+        let mut pages = vec![];
+        let mut page = Page {
+            dimensions: self.page_dimensions,
+            placements: vec![],
+        };
+        // The top left of the next image within the current row and the height of the row.
+        let (mut x, mut y, mut row_h) = (0, 0, 0);
+        for (image_id, [w, h]) in images {
+            if x + padded(w) > page_w {
+                x = 0;
+                y += row_h;
+                row_h = 0;
+            }
+            if y + padded(h) > page_h {
+                let full_page = Page {
+                    dimensions: self.page_dimensions,
+                    placements: vec![],
+                };
+                pages.push(std::mem::replace(&mut page, full_page));
+                x = 0;
+                y = 0;
+                row_h = 0;
+            }
+            page.placements.push(Placement {
+                image_id: image_id,
+                top_left: [x + pad, y + pad],
+                dimensions: [w, h],
+            });
+            x += padded(w);
+            row_h = std::cmp::max(row_h, padded(h));
+        }
+        if !page.placements.is_empty() {
+            pages.push(page);
+        }
+        pages
+    }
+
+    /// Pack the images within the `image_map` that have the given ids and insert a texture for
+    /// each page into the map.
+    ///
+    /// `create_page` is called with each page and the map, and should produce a texture of the
+    /// page's dimensions containing each of the placed images. The packed images remain within
+    /// the map and may be removed once all pages have been created, as the primitives produced
+    /// by the atlas only refer to the pages.
+    pub fn build<Img, I, F>(&self, image_map: &mut Map<Img>, ids: I, mut create_page: F) -> Atlas
+    where
+        Img: ImageDimensions,
+        I: IntoIterator<Item = Id>,
+        F: FnMut(&Page, &Map<Img>) -> Img,
+    {
+        let images: Vec<_> = ids
+            .into_iter()
+            .filter_map(|id| image_map.get(&id).map(|img| (id, img.dimensions())))
+            .collect();
+        let mut atlas = Atlas::default();
+        for page in self.pack(images) {
+            let page_img = create_page(&page, image_map);
+            let page_id = image_map.insert(page_img);
+            for placement in &page.placements {
+                let source_rect = placement.source_rect(page.dimensions);
+                atlas
+                    .images
+                    .insert(placement.image_id, (page_id, source_rect));
+            }
+            atlas.page_ids.push(page_id);
+        }
+        atlas
+    }
+}
+
+impl Placement {
+    /// The area of a page with the given dimensions covered by the image, in pixels from the
+    /// bottom left of the page as with the `source_rect` of an `Image` primitive.
+    pub fn source_rect(&self, page_dimensions: [u32; 2]) -> Rect {
+        let [left, top] = self.top_left;
+        let [w, h] = self.dimensions;
+        let bottom = page_dimensions[1] - (top + h);
+        Rect {
+            x: Range::new(left as f64, (left + w) as f64),
+            y: Range::new(bottom as f64, (bottom + h) as f64),
+        }
+    }
+}
+
+impl Atlas {
+    /// The ids of the pages within the `image::Map`.
+    pub fn page_ids(&self) -> &[Id] {
+        &self.page_ids
+    }
+
+    /// The page on which the given image was placed, along with the area of the page that it
+    /// covers in pixels from the bottom left of the page.
+    ///
+    /// Returns `None` if the image was not packed.
+    pub fn get(&self, image_id: Id) -> Option<(Id, Rect)> {
+        self.images.get(&image_id).cloned()
+    }
+
+    /// Draw each image that was packed from its page rather than its own texture.
+    pub fn primitives<P>(&self, primitives: P) -> Primitives<P>
+    where
+        P: PrimitiveWalker,
+    {
+        Primitives {
+            atlas: self,
+            primitives: primitives,
+        }
+    }
+
+    /// Refer an `Image` primitive of a packed image to the area of its page.
+    ///
+    /// Any source rectangle is given relative to the image and should lie within it, as any
+    /// pixels beyond the image belong to its neighbours on the page.
+    pub fn rewrite(&self, kind: &mut PrimitiveKind) {
+        if let PrimitiveKind::Image {
+            ref mut image_id,
+            ref mut source_rect,
+            ..
+        } = *kind
+        {
+            if let Some((page_id, rect)) = self.get(*image_id) {
+                *image_id = page_id;
+                *source_rect = Some(match *source_rect {
+                    Some(src) => src.shift([rect.left(), rect.bottom()]),
+                    None => rect,
+                });
+            }
+        }
+    }
+}
+
+impl<'a, P> PrimitiveWalker for Primitives<'a, P>
+where
+    P: PrimitiveWalker,
+{
+    fn next_primitive(&mut self) -> Option<Primitive> {
+        let atlas = self.atlas;
+        self.primitives.next_primitive().map(|mut primitive| {
+            atlas.rewrite(&mut primitive.kind);
+            primitive
+        })
+    }
+
+    fn filled(&mut self, stats: &mesh::FillStats) {
+        self.primitives.filled(stats);
+    }
+}
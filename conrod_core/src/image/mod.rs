@@ -1,10 +1,13 @@
 //! A type used to manage a user's image data and map them to `Image` widgets:
 //!
 //! - [Map](./struct.Map.html)
+//! - [atlas](./atlas/index.html)
 
 use fnv;
 use std;
 
+pub mod atlas;
+
 /// Unique image identifier.
 ///
 /// Throughout conrod, images are referred to via their unique `Id`. By referring to images via
@@ -63,6 +63,7 @@ pub enum Motion {
 
 /// Touch-related items.
 pub mod touch {
+    use position::Transform;
     use Point;
 
     /// A type for uniquely identifying the source of a touch interaction.
@@ -119,5 +120,13 @@ pub mod touch {
                 ..*self
             }
         }
+
+        /// Returns a copy of the `Touch` with its position transformed by the given `transform`.
+        pub fn transformed(&self, transform: &Transform) -> Self {
+            Touch {
+                xy: transform.apply(self.xy),
+                ..*self
+            }
+        }
     }
 }
@@ -5,6 +5,7 @@
 
 use event;
 use input;
+use position::Transform;
 use utils;
 use widget;
 use {Point, Rect};
@@ -20,6 +21,7 @@ use {Point, Rect};
 pub struct Widget<'a> {
     global: &'a input::Global,
     rect: Rect,
+    maybe_inverse_transform: Option<Transform>,
     idx: widget::Id,
 }
 
@@ -27,6 +29,7 @@ pub struct Widget<'a> {
 #[derive(Copy, Clone, Debug)]
 pub struct Mouse<'a> {
     rect: Rect,
+    maybe_inverse_transform: Option<Transform>,
     mouse_abs_xy: Point,
     /// The state of each `MouseButton`.
     pub buttons: &'a input::state::mouse::ButtonMap,
@@ -45,6 +48,7 @@ pub struct Events<'a> {
     capturing_keyboard: Option<widget::Id>,
     capturing_mouse: Option<widget::Id>,
     rect: Rect,
+    maybe_inverse_transform: Option<Transform>,
     idx: widget::Id,
 }
 
@@ -167,10 +171,23 @@ impl<'a> Widget<'a> {
         Widget {
             global: global,
             rect: rect,
+            maybe_inverse_transform: None,
             idx: idx,
         }
     }
 
+    /// Specify the transform with which the widget is drawn, as produced by
+    /// `graph::algo::transform_of_widget`.
+    ///
+    /// The coordinates of all events will first be transformed back into the untransformed space
+    /// in which the widget's `Rect` is laid out before being made relative to its middle.
+    ///
+    /// If the transform cannot be undone, event coordinates are left untransformed.
+    pub fn transform(mut self, transform: Transform) -> Self {
+        self.maybe_inverse_transform = transform.inverse();
+        self
+    }
+
     /// If the widget is currently capturing the mouse, this returns the state of the mouse.
     ///
     /// Returns `None` if the widget is not capturing the mouse.
@@ -180,6 +197,7 @@ impl<'a> Widget<'a> {
                 buttons: &self.global.current.mouse.buttons,
                 mouse_abs_xy: self.global.current.mouse.xy,
                 rect: self.rect,
+                maybe_inverse_transform: self.maybe_inverse_transform,
             };
             return Some(mouse);
         }
@@ -199,6 +217,7 @@ impl<'a> Widget<'a> {
             capturing_keyboard: self.global.start.widget_capturing_keyboard,
             capturing_mouse: self.global.start.widget_capturing_mouse,
             rect: self.rect,
+            maybe_inverse_transform: self.maybe_inverse_transform,
             idx: self.idx,
         }
     }
@@ -267,18 +286,24 @@ impl<'a> Widget<'a> {
 
 impl<'a> Mouse<'a> {
     /// The absolute position of the mouse within the window.
+    ///
+    /// If the widget is transformed, this is the position within the untransformed space in which
+    /// the widget's `Rect` is laid out.
     pub fn abs_xy(&self) -> Point {
-        self.mouse_abs_xy
+        match self.maybe_inverse_transform {
+            Some(ref inverse) => inverse.apply(self.mouse_abs_xy),
+            None => self.mouse_abs_xy,
+        }
     }
 
     /// The position of the mouse relative to the middle of the widget's `Rect`.
     pub fn rel_xy(&self) -> Point {
-        utils::vec2_sub(self.mouse_abs_xy, self.rect.xy())
+        utils::vec2_sub(self.abs_xy(), self.rect.xy())
     }
 
     /// Is the mouse currently over the widget.
     pub fn is_over(&self) -> bool {
-        self.rect.is_over(self.mouse_abs_xy)
+        self.rect.is_over(self.abs_xy())
     }
 }
 
@@ -413,6 +438,24 @@ impl<'a> Iterator for Events<'a> {
     type Item = event::Widget;
 
     fn next(&mut self) -> Option<event::Widget> {
+        let Events {
+            rect,
+            maybe_inverse_transform,
+            ..
+        } = *self;
+
+        // Transform the given event back into the widget's untransformed space and make it
+        // relative to the middle of its `Rect`.
+        macro_rules! relative {
+            ($event:expr) => {
+                match maybe_inverse_transform {
+                    Some(ref inverse) => $event.transformed(inverse),
+                    None => $event.clone(),
+                }
+                .relative_to(rect.xy())
+            };
+        }
+
         // Loop through all events in the `ui_events` until we find one associated with our widget
         // that we can return.
         while let Some(ui_event) = self.ui_events.next() {
@@ -444,35 +487,39 @@ impl<'a> Iterator for Events<'a> {
                 }
 
                 event::Ui::Touch(idx, ref touch) if idx == Some(self.idx) => {
-                    return Some(touch.clone().relative_to(self.rect.xy()).into())
+                    return Some(relative!(touch).into())
                 }
 
                 event::Ui::Press(idx, ref press) if idx == Some(self.idx) => {
-                    return Some(press.clone().relative_to(self.rect.xy()).into())
+                    return Some(relative!(press).into())
                 }
 
                 event::Ui::Release(idx, ref release) if idx == Some(self.idx) => {
-                    return Some(release.clone().relative_to(self.rect.xy()).into())
+                    return Some(relative!(release).into())
                 }
 
                 event::Ui::Click(idx, ref click) if idx == Some(self.idx) => {
-                    return Some(click.clone().relative_to(self.rect.xy()).into())
+                    return Some(relative!(click).into())
                 }
 
                 event::Ui::DoubleClick(idx, ref double_click) if idx == Some(self.idx) => {
-                    return Some(double_click.clone().relative_to(self.rect.xy()).into())
+                    return Some(relative!(double_click).into())
                 }
 
                 event::Ui::Tap(idx, ref tap) if idx == Some(self.idx) => {
-                    return Some(tap.clone().relative_to(self.rect.xy()).into())
+                    return Some(relative!(tap).into())
                 }
 
                 event::Ui::Drag(idx, ref drag) if idx == Some(self.idx) => {
-                    return Some(drag.clone().relative_to(self.rect.xy()).into())
+                    return Some(relative!(drag).into())
                 }
 
                 event::Ui::Scroll(idx, ref scroll) if idx == Some(self.idx) => {
-                    return Some(scroll.clone().into())
+                    let scroll = match self.maybe_inverse_transform {
+                        Some(ref inverse) => scroll.transformed(inverse),
+                        None => scroll.clone(),
+                    };
+                    return Some(scroll.into());
                 }
 
                 _ => (),
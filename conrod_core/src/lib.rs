@@ -23,7 +23,7 @@ pub use conrod_derive::*;
 pub use label::{FontSize, Labelable};
 pub use position::{Dimensions, Point, Position, Positionable, Range, Rect, Scalar, Sizeable};
 pub use theme::Theme;
-pub use ui::{Ui, UiBuilder, UiCell};
+pub use ui::{Stats, Ui, UiBuilder, UiCell};
 pub use widget::{scroll, Widget};
 
 mod border;
@@ -38,6 +38,7 @@ mod label;
 pub mod mesh;
 pub mod position;
 pub mod render;
+pub mod svg;
 pub mod text;
 pub mod theme;
 mod ui;
@@ -5,9 +5,11 @@
 //! produce a sequence of commands describing the order in which draw commands should occur and
 //! whether or not the `Scizzor` should be updated between draws.
 
+use crate::position::Transform;
 use crate::text::{self, rt};
-use crate::{color, image, render};
+use crate::{color, image, render, widget};
 use crate::{Rect, Scalar};
+use fnv::{FnvHashMap, FnvHashSet};
 use std::{fmt, ops};
 
 /// Images within the given image map must know their dimensions in pixels.
@@ -16,6 +18,33 @@ pub trait ImageDimensions {
     fn dimensions(&self) -> [u32; 2];
 }
 
+/// Images may be described by their dimensions alone, e.g. by backends whose image types are
+/// defined by other crates.
+impl ImageDimensions for [u32; 2] {
+    fn dimensions(&self) -> [u32; 2] {
+        *self
+    }
+}
+
+/// A map from image IDs to images of known dimensions, from which the mesh may be filled.
+///
+/// Implemented for any `image::Map` whose images implement `ImageDimensions`. Backends whose
+/// image types are defined by other crates may implement this for a wrapper around their map
+/// rather than collecting the dimensions of each image into a new map.
+pub trait ImageMap {
+    /// The dimensions in pixels of the image at the given `id`, if there is one.
+    fn image_dimensions(&self, id: image::Id) -> Option<[u32; 2]>;
+}
+
+impl<I> ImageMap for image::Map<I>
+where
+    I: ImageDimensions,
+{
+    fn image_dimensions(&self, id: image::Id) -> Option<[u32; 2]> {
+        self.get(&id).map(|img| img.dimensions())
+    }
+}
+
 /// A mesh whose vertices may be populated by a list of render primitives.
 ///
 /// This is a convenience type for simplifying backend implementations.
@@ -23,9 +52,23 @@ pub trait ImageDimensions {
 pub struct Mesh {
     glyph_cache: GlyphCache,
     glyph_cache_pixel_buffer: Vec<u8>,
+    max_glyph_cache_dims: [u32; 2],
+    sdf_glyph_cache: Option<SdfGlyphCache>,
     commands: Vec<PreparedCommand>,
     vertices: Vec<Vertex>,
-    positioned_glyphs: Vec<text::PositionedGlyph>,
+    positioned_glyphs: Vec<render::HighlightedGlyph>,
+    drawn_glyphs: Vec<DrawnGlyph>,
+    // The regions of the glyph cache pixel buffer written to during the previous fill.
+    glyph_cache_updated_rects: Vec<rt::Rect<u32>>,
+    // The viewport and dpi factor of the previous successful fill.
+    filled: Option<(Rect, f64)>,
+    // The vertices produced for each widget during the current and the previous fill.
+    widget_vertices: FnvHashMap<widget::Id, WidgetVertices>,
+    prev_widget_vertices: FnvHashMap<widget::Id, WidgetVertices>,
+    // The vertex buffer and drawn glyphs of the previous fill, from which the vertices of
+    // unchanged widgets are copied.
+    prev_vertices: Vec<Vertex>,
+    prev_drawn_glyphs: Vec<DrawnGlyph>,
 }
 
 /// Represents the scizzor in pixel coordinates.
@@ -38,12 +81,23 @@ pub struct Scizzor {
 }
 
 /// A `Command` describing a step in the drawing process.
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq)]
 pub enum Command {
     /// Draw to the target.
     Draw(Draw),
     /// Update the scizzor within the pipeline.
     Scizzor(Scizzor),
+    /// Invoke the user's rendering code for a `CustomDraw` widget.
+    ///
+    /// Draws that precede this command should be submitted before the custom drawing occurs.
+    Custom(Custom),
+    /// Crop subsequent draws to the shapes of the `ClipMask` in addition to the scizzor.
+    ///
+    /// This is always preceded by a `Scizzor` covering the whole viewport, so that the mask may be
+    /// written to the whole viewport, and followed by the `Scizzor` for the subsequent draws.
+    /// Backends that do not support clip masks may ignore this command, cropping draws to the
+    /// bounding scizzor alone.
+    ClipMask(ClipMask),
 }
 
 /// An iterator yielding `Command`s, produced by the `Renderer::commands` method.
@@ -54,7 +108,7 @@ pub struct Commands<'a> {
 /// A `Command` for drawing to the target.
 ///
 /// Each variant describes how to draw the contents of the vertex buffer.
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq)]
 pub enum Draw {
     /// A range of vertices representing triangles textured with the image in the
     /// image_map at the given `widget::Id`.
@@ -63,6 +117,42 @@ pub enum Draw {
     Plain(std::ops::Range<usize>),
 }
 
+/// A widget to be drawn by the user's own rendering code, produced by a `CustomDraw` widget.
+#[derive(Clone, Debug)]
+pub struct Custom {
+    /// The unique identifier of the `CustomDraw` widget.
+    pub id: widget::Id,
+    /// The user data given to the `CustomDraw` widget.
+    pub data: widget::custom_draw::Data,
+    /// The widget's `Rect` within the untransformed space in which it was laid out.
+    pub rect: Rect,
+    /// The transform mapping the `rect` to the window, if any.
+    pub transform: Option<Transform>,
+    /// The opacity with which the widget should be drawn.
+    pub opacity: f32,
+    /// The scizzor to which the custom drawing should be cropped.
+    ///
+    /// This is always the most recent `Command::Scizzor`, or the whole viewport if there has been
+    /// none.
+    pub scizzor: Scizzor,
+}
+
+/// The shapes to which draws are cropped, produced for widgets that crop their children to a
+/// `widget::CropShape` other than a `Rectangle`.
+///
+/// Each shape is described by a range of vertices forming triangles that never overlap. Draws
+/// should be cropped to the intersection of all shapes, e.g. by clearing the stencil buffer,
+/// incrementing the stencil value of each pixel covered by each shape and then only drawing where
+/// the stencil value is equal to the number of shapes. The vertices of the shapes are never part
+/// of a `Draw` command.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ClipMask {
+    /// The range of vertices describing each shape.
+    ///
+    /// When empty, draws are cropped by the scizzor alone.
+    pub shapes: Vec<std::ops::Range<usize>>,
+}
+
 /// The data associated with a single vertex.
 #[repr(C)]
 #[derive(Clone, Copy, Debug, Default, PartialEq, PartialOrd)]
@@ -84,6 +174,7 @@ pub struct Vertex {
     /// `0` for rendering text.
     /// `1` for rendering an image.
     /// `2` for rendering non-textured 2D geometry.
+    /// `3` for rendering text from a signed distance field.
     ///
     /// If any other value is given, the fragment shader will not output any color.
     pub mode: u32,
@@ -97,16 +188,92 @@ pub struct Vertex {
 pub struct Fill {
     /// Whether or not the glyph cache pixel data should be written to the GPU.
     pub glyph_cache_requires_upload: bool,
+    /// The new dimensions of the glyph cache if it had to grow in order to fit the text.
+    ///
+    /// When `Some`, the glyph cache texture on the GPU should be re-created with these dimensions
+    /// before the pixel data is written to it.
+    pub glyph_cache_resized: Option<[u32; 2]>,
+    /// The amount of work required to draw the mesh.
+    pub stats: FillStats,
+}
+
+/// Counts describing the contents of a filled mesh, also given to the `PrimitiveWalker` from
+/// which the mesh was filled via `PrimitiveWalker::filled`.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct FillStats {
+    /// The number of vertices within the mesh.
+    pub vertices: usize,
+    /// The number of triangles described by the vertices.
+    pub triangles: usize,
+    /// The number of `Draw` commands that draw at least one vertex.
+    pub draw_commands: usize,
+    /// The number of `Scizzor` commands.
+    pub scizzor_changes: usize,
+    /// The number of times that the glyph cache must be uploaded to the GPU, either `0` or `1`.
+    pub glyph_cache_uploads: usize,
 }
 
 // A wrapper around an owned glyph cache, providing `Debug` and `Deref` impls.
 struct GlyphCache(text::GlyphCache<'static>);
 
+// A glyph drawn during the current `fill`, along with the index of its first vertex.
+//
+// Used to cache the glyph again and rewrite its texture coordinates if it is evicted from the
+// glyph cache by a later `Text` primitive.
+#[derive(Debug)]
+struct DrawnGlyph {
+    vertex: usize,
+    font_id: usize,
+    glyph: text::PositionedGlyph,
+}
+
+// The vertices produced for a single widget's primitive, which are copied into the vertex buffer
+// of the next fill if the widget is unchanged.
+//
+// Only recorded for primitives that produced a draw command.
+#[derive(Debug)]
+struct WidgetVertices {
+    // The range of the widget's vertices within the vertex buffer.
+    range: ops::Range<usize>,
+    // The range of the widget's glyphs within the drawn glyphs.
+    glyphs: ops::Range<usize>,
+    // The image and its dimensions if the vertices are drawn with an image.
+    image: Option<(image::Id, [u32; 2])>,
+    rect: Rect,
+    scizzor: Rect,
+    transform: Option<Transform>,
+    // The opacity inherited from the widget's depth parents, by which the colours are faded.
+    opacity: f32,
+}
+
+// Signed distance fields of glyphs, packed into rows within the glyph cache pixel buffer.
+//
+// Each glyph is rasterised once at `SDF_GLYPH_SCALE`, after which it may be drawn at any size.
+#[derive(Debug, Default)]
+struct SdfGlyphCache {
+    // The field of each glyph, or `None` for glyphs without an outline.
+    glyphs: FnvHashMap<(usize, text::GlyphId), Option<SdfGlyph>>,
+    // The position at which the next field will be placed.
+    cursor: [u32; 2],
+    // The height of the tallest field within the current row.
+    row_height: u32,
+}
+
+// The location of a glyph's signed distance field within the glyph cache pixel buffer.
+#[derive(Copy, Clone, Debug)]
+struct SdfGlyph {
+    tex_rect: rt::Rect<u32>,
+    // The bounds of the field in pixels at `SDF_GLYPH_SCALE`, relative to the glyph's origin.
+    bounds: rt::Rect<f32>,
+}
+
 #[derive(Debug)]
 enum PreparedCommand {
     Image(image::Id, std::ops::Range<usize>),
     Plain(std::ops::Range<usize>),
     Scizzor(Scizzor),
+    Custom(Custom),
+    ClipMask(ClipMask),
 }
 
 /// Draw text from the text cache texture `tex` in the fragment shader.
@@ -115,10 +282,23 @@ pub const MODE_TEXT: u32 = 0;
 pub const MODE_IMAGE: u32 = 1;
 /// Ignore `tex` and draw simple, colored 2D geometry.
 pub const MODE_GEOMETRY: u32 = 2;
+/// Draw text from the signed distance fields within the text cache texture `tex` in the fragment
+/// shader.
+///
+/// The field is `0.5` along the outline of each glyph, increasing towards `1.0` inside the glyph.
+pub const MODE_TEXT_SDF: u32 = 3;
 
 /// Default dimensions to use for the glyph cache.
 pub const DEFAULT_GLYPH_CACHE_DIMS: [u32; 2] = [1_024; 2];
 
+/// Default dimensions beyond which the glyph cache will not grow.
+pub const DEFAULT_MAX_GLYPH_CACHE_DIMS: [u32; 2] = [8_192; 2];
+
+// The pixel size at which glyphs are rasterised before computing their signed distance fields.
+const SDF_GLYPH_SCALE: f32 = 48.0;
+// The distance in pixels at `SDF_GLYPH_SCALE` over which the field falls from `1.0` to `0.0`.
+const SDF_SPREAD: i32 = 6;
+
 impl Mesh {
     /// Construct a new empty `Mesh` with default glyph cache dimensions.
     pub fn new() -> Self {
@@ -126,6 +306,9 @@ impl Mesh {
     }
 
     /// Construct a `Mesh` with the given glyph cache dimensions.
+    ///
+    /// If the text drawn within a single `fill` does not fit within the glyph cache, the cache is
+    /// doubled in size up to `DEFAULT_MAX_GLYPH_CACHE_DIMS`. See `max_glyph_cache_dimensions`.
     pub fn with_glyph_cache_dimensions(glyph_cache_dims: [u32; 2]) -> Self {
         const SCALE_TOLERANCE: f32 = 0.1;
         const POSITION_TOLERANCE: f32 = 0.1;
@@ -137,18 +320,59 @@ impl Mesh {
             .build()
             .into();
         let glyph_cache_pixel_buffer = vec![0u8; gc_width as usize * gc_height as usize];
+        let max_glyph_cache_dims = DEFAULT_MAX_GLYPH_CACHE_DIMS;
+        let sdf_glyph_cache = None;
         let commands = vec![];
         let vertices = vec![];
         let positioned_glyphs = vec![];
+        let drawn_glyphs = vec![];
+        let glyph_cache_updated_rects = vec![];
+        let filled = None;
+        let widget_vertices = FnvHashMap::default();
+        let prev_widget_vertices = FnvHashMap::default();
+        let prev_vertices = vec![];
+        let prev_drawn_glyphs = vec![];
         Mesh {
             glyph_cache,
             glyph_cache_pixel_buffer,
+            max_glyph_cache_dims,
+            sdf_glyph_cache,
             commands,
             vertices,
             positioned_glyphs,
+            drawn_glyphs,
+            glyph_cache_updated_rects,
+            filled,
+            widget_vertices,
+            prev_widget_vertices,
+            prev_vertices,
+            prev_drawn_glyphs,
         }
     }
 
+    /// Specify whether text should be drawn from signed distance fields.
+    ///
+    /// By default, glyphs are rasterised into the glyph cache for each size at which they are
+    /// drawn. With signed distance fields, each glyph is rasterised only once and its vertices use
+    /// `MODE_TEXT_SDF`, so that text may be scaled smoothly without filling the glyph cache. The
+    /// backend's fragment shader must support `MODE_TEXT_SDF`.
+    pub fn sdf_text(mut self, sdf_text: bool) -> Self {
+        self.sdf_glyph_cache = match sdf_text {
+            true => Some(SdfGlyphCache::default()),
+            false => None,
+        };
+        self
+    }
+
+    /// Specify the dimensions beyond which the glyph cache may not grow.
+    ///
+    /// Backends that cannot re-create their glyph cache texture may pass the current glyph cache
+    /// dimensions in order to keep the glyph cache at a fixed size.
+    pub fn max_glyph_cache_dimensions(mut self, max_glyph_cache_dims: [u32; 2]) -> Self {
+        self.max_glyph_cache_dims = max_glyph_cache_dims;
+        self
+    }
+
     /// Fill the inner vertex buffer from the given primitives.
     ///
     /// - `viewport`: the window in which the UI is drawn. The width and height should be the
@@ -157,27 +381,98 @@ impl Mesh {
     ///   pixel space of the viewport.
     /// - `image_map`: a map from image IDs to images.
     /// - `primitives`: the sequence of UI primitives in order of depth to be rendered.
-    pub fn fill<P, I>(
+    ///
+    /// If the glyphs of some `Text` primitive do not fit within the glyph cache, the cache is grown
+    /// and all glyphs drawn so far are cached again. An error is only returned if the glyphs still
+    /// do not fit once the cache has reached its maximum dimensions.
+    pub fn fill<P, M>(
+        &mut self,
+        viewport: Rect,
+        dpi_factor: f64,
+        image_map: &M,
+        primitives: P,
+    ) -> Result<Fill, rt::gpu_cache::CacheWriteErr>
+    where
+        P: render::PrimitiveWalker,
+        M: ImageMap,
+    {
+        self.fill_widgets(viewport, dpi_factor, image_map, primitives, None)
+    }
+
+    /// Fill the inner vertex buffer from the given primitives, only regenerating the vertices of
+    /// widgets that have changed since the previous fill.
+    ///
+    /// The vertices of every other widget are copied from the previous fill rather than being
+    /// tessellated again. `changed_widgets` must contain each widget whose style or state may have
+    /// changed since the previous fill, e.g. `Ui::changed_widgets` when the mesh is filled after
+    /// each call to `Ui::set_widgets`. Changes to a widget's `Rect`, scizzor, transform or opacity,
+    /// including those inherited from its depth parents, are detected by the mesh itself.
+    ///
+    /// All vertices are regenerated if the `viewport` or `dpi_factor` differ from those of the
+    /// previous fill. Changes that affect widgets without changing their style or state, such as
+    /// a change to the `Theme`, require a call to `fill` instead.
+    pub fn fill_incremental<P, M>(
         &mut self,
         viewport: Rect,
         dpi_factor: f64,
-        image_map: &image::Map<I>,
+        image_map: &M,
+        primitives: P,
+        changed_widgets: &FnvHashSet<widget::Id>,
+    ) -> Result<Fill, rt::gpu_cache::CacheWriteErr>
+    where
+        P: render::PrimitiveWalker,
+        M: ImageMap,
+    {
+        let changed_widgets = Some(changed_widgets);
+        self.fill_widgets(viewport, dpi_factor, image_map, primitives, changed_widgets)
+    }
+
+    // Fill the inner vertex buffer, reusing the vertices of widgets from the previous fill unless
+    // `changed_widgets` is `None` or contains the widget.
+    fn fill_widgets<P, M>(
+        &mut self,
+        viewport: Rect,
+        dpi_factor: f64,
+        image_map: &M,
         mut primitives: P,
+        changed_widgets: Option<&FnvHashSet<widget::Id>>,
     ) -> Result<Fill, rt::gpu_cache::CacheWriteErr>
     where
         P: render::PrimitiveWalker,
-        I: ImageDimensions,
+        M: ImageMap,
     {
         let Mesh {
             ref mut glyph_cache,
             ref mut glyph_cache_pixel_buffer,
+            max_glyph_cache_dims,
+            ref mut sdf_glyph_cache,
             ref mut commands,
             ref mut vertices,
             ref mut positioned_glyphs,
+            ref mut drawn_glyphs,
+            ref mut glyph_cache_updated_rects,
+            ref mut filled,
+            ref mut widget_vertices,
+            ref mut prev_widget_vertices,
+            ref mut prev_vertices,
+            ref mut prev_drawn_glyphs,
         } = *self;
 
+        // Widgets may only be reused if they were drawn to the same viewport.
+        let changed_widgets = match *filled {
+            Some(prev) if prev == (viewport, dpi_factor) => changed_widgets,
+            _ => None,
+        };
+        *filled = None;
+
+        std::mem::swap(vertices, prev_vertices);
+        std::mem::swap(drawn_glyphs, prev_drawn_glyphs);
+        std::mem::swap(widget_vertices, prev_widget_vertices);
         commands.clear();
         vertices.clear();
+        drawn_glyphs.clear();
+        glyph_cache_updated_rects.clear();
+        widget_vertices.clear();
 
         enum State {
             Image { image_id: image::Id, start: usize },
@@ -188,16 +483,14 @@ impl Mesh {
 
         // Keep track of whether or not the glyph cache texture needs to be updated.
         let mut glyph_cache_requires_upload = false;
+        let mut glyph_cache_resized = false;
+        let mut glyph_cache_cleared = false;
 
         // Viewport dimensions and the "dots per inch" factor.
         let (viewport_w, viewport_h) = viewport.w_h();
         let half_viewport_w = viewport_w / 2.0;
         let half_viewport_h = viewport_h / 2.0;
 
-        // Width of the glyph cache is useful when writing to the pixel buffer.
-        let (glyph_cache_w, _) = glyph_cache.dimensions();
-        let glyph_cache_w = glyph_cache_w as usize;
-
         // Functions for converting for conrod scalar coords to normalised vertex coords (-1.0 to 1.0).
         let vx = |x: Scalar| (x * dpi_factor / half_viewport_w) as f32;
         let vy = |y: Scalar| -1.0 * (y * dpi_factor / half_viewport_h) as f32;
@@ -221,6 +514,9 @@ impl Mesh {
         // Keep track of the scizzor as it changes.
         let mut current_scizzor = rect_to_scizzor(viewport);
 
+        // Keep track of the clips to which the current clip mask crops draws.
+        let mut current_clips: Vec<render::Clip> = vec![];
+
         // Switches to the `Plain` state and completes the previous `Command` if not already in the
         // `Plain` state.
         macro_rules! switch_to_plain_state {
@@ -237,15 +533,107 @@ impl Mesh {
             };
         }
 
+        // Switches to the `Image` state for the given image and completes the previous `Command` if
+        // not already in this state.
+        macro_rules! switch_to_image_state {
+            ($new_image_id:expr) => {
+                let new_image_id = $new_image_id;
+                match current_state {
+                    // If we're already in the drawing mode for this image, we're done.
+                    State::Image { image_id, .. } if image_id == new_image_id => (),
+
+                    // If we were in the `Plain` drawing state, switch to Image drawing state.
+                    State::Plain { start } => {
+                        commands.push(PreparedCommand::Plain(start..vertices.len()));
+                        current_state = State::Image {
+                            image_id: new_image_id,
+                            start: vertices.len(),
+                        };
+                    }
+
+                    // If we were drawing a different image, switch state to draw *this* image.
+                    State::Image { image_id, start } => {
+                        commands.push(PreparedCommand::Image(image_id, start..vertices.len()));
+                        current_state = State::Image {
+                            image_id: new_image_id,
+                            start: vertices.len(),
+                        };
+                    }
+                }
+            };
+        }
+
         // Draw each primitive in order of depth.
         while let Some(primitive) = primitives.next_primitive() {
             let render::Primitive {
+                id,
                 kind,
                 scizzor,
+                clips,
                 rect,
-                ..
+                transform,
+                opacity,
             } = primitive;
 
+            // Converts a point in conrod's layout space to a vertex position, applying the
+            // primitive's transform if there is one.
+            let position = |p: [Scalar; 2]| {
+                let [x, y] = match transform {
+                    Some(ref transform) => transform.apply(p),
+                    None => p,
+                };
+                [vx(x), vy(y)]
+            };
+
+            // Check for a `ClipMask` command.
+            if clips != &current_clips[..] {
+                // Finish the current command.
+                match current_state {
+                    State::Plain { start } => {
+                        commands.push(PreparedCommand::Plain(start..vertices.len()))
+                    }
+                    State::Image { image_id, start } => {
+                        commands.push(PreparedCommand::Image(image_id, start..vertices.len()))
+                    }
+                }
+
+                // The mask is written to the whole viewport, after which the scizzor of the
+                // primitive is restored below.
+                let viewport_scizzor = Scizzor {
+                    top_left: [0, 0],
+                    dimensions: [viewport_w as u32, viewport_h as u32],
+                };
+                commands.push(PreparedCommand::Scizzor(viewport_scizzor));
+                current_scizzor = Some(viewport_scizzor);
+
+                // The vertices of each shape follow those of all previous draws.
+                let shapes = clips
+                    .iter()
+                    .map(|clip| {
+                        let start = vertices.len();
+                        for triangle in clip.triangles() {
+                            for &[x, y] in &triangle.0 {
+                                vertices.push(Vertex {
+                                    position: [vx(x), vy(y)],
+                                    tex_coords: [0.0, 0.0],
+                                    rgba: [1.0; 4],
+                                    mode: MODE_GEOMETRY,
+                                });
+                            }
+                        }
+                        start..vertices.len()
+                    })
+                    .collect();
+                commands.push(PreparedCommand::ClipMask(ClipMask { shapes }));
+                current_clips.clear();
+                current_clips.extend_from_slice(clips);
+
+                // Set the state back to plain drawing.
+                current_state = State::Plain {
+                    start: vertices.len(),
+                };
+            }
+
             // Check for a `Scizzor` command.
             let new_scizzor = rect_to_scizzor(scizzor);
             if new_scizzor != current_scizzor {
@@ -276,6 +664,79 @@ impl Mesh {
                 continue;
             }
 
+            // Copy the vertices of the widget from the previous fill if it has not changed.
+            let reusable = changed_widgets
+                .filter(|changed| !changed.contains(&id))
+                .and_then(|_| prev_widget_vertices.remove(&id))
+                .filter(|prev| {
+                    let image_unchanged = match prev.image {
+                        None => true,
+                        Some((image_id, dims)) => {
+                            image_map.image_dimensions(image_id) == Some(dims)
+                        }
+                    };
+                    prev.rect == rect
+                        && prev.scizzor == scizzor
+                        && prev.transform == transform
+                        && prev.opacity == opacity
+                        && image_unchanged
+                });
+            if let Some(prev) = reusable {
+                match prev.image {
+                    None => {
+                        switch_to_plain_state!();
+                    }
+                    Some((image_id, _)) => {
+                        switch_to_image_state!(image_id);
+                    }
+                }
+                let start = vertices.len();
+                let glyphs_start = drawn_glyphs.len();
+                vertices.extend_from_slice(&prev_vertices[prev.range.clone()]);
+                drawn_glyphs.extend(prev_drawn_glyphs[prev.glyphs.clone()].iter().map(|g| {
+                    DrawnGlyph {
+                        vertex: g.vertex - prev.range.start + start,
+                        font_id: g.font_id,
+                        glyph: g.glyph.clone(),
+                    }
+                }));
+
+                // Glyphs from the previous fill may have since been evicted from the glyph cache,
+                // so the glyphs are cached again and their texture coordinates rewritten.
+                if !prev.glyphs.is_empty() {
+                    let (drawn, reused) = drawn_glyphs.split_at(glyphs_start);
+                    let queued = reused.iter().map(|g| (g.font_id, &g.glyph));
+                    let cached = cache_glyphs(
+                        glyph_cache,
+                        glyph_cache_pixel_buffer,
+                        max_glyph_cache_dims,
+                        sdf_glyph_cache,
+                        glyph_cache_updated_rects,
+                        drawn,
+                        queued,
+                    )?;
+                    glyph_cache_requires_upload |= cached.written;
+                    glyph_cache_resized |= cached.resized;
+                    glyph_cache_cleared |= cached.cleared;
+                    let rewrite = match cached.requeued {
+                        true => &drawn_glyphs[..],
+                        false => &drawn_glyphs[glyphs_start..],
+                    };
+                    rewrite_glyph_tex_coords(glyph_cache, sdf_glyph_cache, rewrite, vertices);
+                }
+                let widget = WidgetVertices {
+                    range: start..vertices.len(),
+                    glyphs: glyphs_start..drawn_glyphs.len(),
+                    ..prev
+                };
+                widget_vertices.insert(id, widget);
+                continue;
+            }
+
+            let start = vertices.len();
+            let glyphs_start = drawn_glyphs.len();
+            let mut widget_image = None;
+
             match kind {
                 render::PrimitiveKind::Rectangle { color } => {
                     switch_to_plain_state!();
@@ -286,7 +747,7 @@ impl Mesh {
                     let v = |x, y| {
                         // Convert from conrod Scalar range to GL range -1.0 to 1.0.
                         Vertex {
-                            position: [vx(x), vy(y)],
+                            position: position([x, y]),
                             tex_coords: [0.0, 0.0],
                             rgba: color,
                             mode: MODE_GEOMETRY,
@@ -316,7 +777,7 @@ impl Mesh {
                     let color = gamma_srgb_to_linear(color.into());
 
                     let v = |p: [Scalar; 2]| Vertex {
-                        position: [vx(p[0]), vy(p[1])],
+                        position: position(p),
                         tex_coords: [0.0, 0.0],
                         rgba: color,
                         mode: MODE_GEOMETRY,
@@ -337,7 +798,7 @@ impl Mesh {
                     switch_to_plain_state!();
 
                     let v = |(p, c): ([Scalar; 2], color::Rgba)| Vertex {
-                        position: [vx(p[0]), vy(p[1])],
+                        position: position(p),
                         tex_coords: [0.0, 0.0],
                         rgba: gamma_srgb_to_linear(c.into()),
                         mode: MODE_GEOMETRY,
@@ -358,83 +819,95 @@ impl Mesh {
                     switch_to_plain_state!();
 
                     positioned_glyphs.clear();
-                    positioned_glyphs.extend(text.positioned_glyphs(dpi_factor as f32));
-
-                    // Queue the glyphs to be cached
-                    for glyph in positioned_glyphs.iter() {
-                        glyph_cache.queue_glyph(font_id.index(), glyph.clone());
+                    positioned_glyphs.extend(text.highlighted_glyphs(dpi_factor as f32));
+
+                    // Cache the glyphs. If making room for them evicts the glyphs drawn so far,
+                    // these are cached again too and their texture coordinates are rewritten.
+                    let queued = positioned_glyphs
+                        .iter()
+                        .map(|g| (g.font_id.unwrap_or(font_id).index(), &g.glyph));
+                    let cached = cache_glyphs(
+                        glyph_cache,
+                        glyph_cache_pixel_buffer,
+                        max_glyph_cache_dims,
+                        sdf_glyph_cache,
+                        glyph_cache_updated_rects,
+                        drawn_glyphs,
+                        queued,
+                    )?;
+                    glyph_cache_requires_upload |= cached.written;
+                    glyph_cache_resized |= cached.resized;
+                    glyph_cache_cleared |= cached.cleared;
+                    if cached.requeued {
+                        rewrite_glyph_tex_coords(
+                            glyph_cache,
+                            sdf_glyph_cache,
+                            drawn_glyphs,
+                            vertices,
+                        );
                     }
 
-                    glyph_cache.cache_queued(|rect, data| {
-                        let width = (rect.max.x - rect.min.x) as usize;
-                        let height = (rect.max.y - rect.min.y) as usize;
-                        let mut dst_ix = rect.min.y as usize * glyph_cache_w + rect.min.x as usize;
-                        let mut src_ix = 0;
-                        for _ in 0..height {
-                            let dst_range = dst_ix..dst_ix + width;
-                            let src_range = src_ix..src_ix + width;
-                            let dst_slice = &mut glyph_cache_pixel_buffer[dst_range];
-                            let src_slice = &data[src_range];
-                            dst_slice.copy_from_slice(src_slice);
-                            dst_ix += glyph_cache_w;
-                            src_ix += width;
-                        }
-                        glyph_cache_requires_upload = true;
-                    })?;
-
                     let color = gamma_srgb_to_linear(color.to_fsa());
-                    let cache_id = font_id.index();
                     let origin = rt::point(0.0, 0.0);
+                    let mode = match *sdf_glyph_cache {
+                        Some(_) => MODE_TEXT_SDF,
+                        None => MODE_TEXT,
+                    };
 
                     // A closure to convert RustType rects to GL rects
-                    let to_vk_rect = |screen_rect: rt::Rect<i32>| rt::Rect {
+                    let to_vk_rect = |screen_rect: rt::Rect<f32>| rt::Rect {
                         min: origin
                             + (rt::vector(
-                                screen_rect.min.x as f32 / viewport_w as f32 - 0.5,
-                                screen_rect.min.y as f32 / viewport_h as f32 - 0.5,
+                                screen_rect.min.x / viewport_w as f32 - 0.5,
+                                screen_rect.min.y / viewport_h as f32 - 0.5,
                             )) * 2.0,
                         max: origin
                             + (rt::vector(
-                                screen_rect.max.x as f32 / viewport_w as f32 - 0.5,
-                                screen_rect.max.y as f32 / viewport_h as f32 - 0.5,
+                                screen_rect.max.x / viewport_w as f32 - 0.5,
+                                screen_rect.max.y / viewport_h as f32 - 0.5,
                             )) * 2.0,
                     };
 
+                    // Glyphs are rasterised untransformed, so their corners are transformed from
+                    // normalised vertex coordinates back through conrod's layout space.
+                    let corner = |x: f32, y: f32| match transform {
+                        None => [x, y],
+                        Some(_) => {
+                            let x = x as Scalar * half_viewport_w / dpi_factor;
+                            let y = -y as Scalar * half_viewport_h / dpi_factor;
+                            position([x, y])
+                        }
+                    };
+
                     for g in positioned_glyphs.drain(..) {
-                        if let Ok(Some((uv_rect, screen_rect))) = glyph_cache.rect_for(cache_id, &g)
+                        let cache_id = g.font_id.unwrap_or(font_id).index();
+                        let color = g
+                            .color
+                            .map(|color| gamma_srgb_to_linear(color.to_fsa()))
+                            .unwrap_or(color);
+                        if let Some((uv_rect, screen_rect)) =
+                            cached_glyph_rects(glyph_cache, sdf_glyph_cache, cache_id, &g.glyph)
                         {
                             let vk_rect = to_vk_rect(screen_rect);
+                            let tex_coords = glyph_tex_coords(uv_rect);
                             let v = |p, t| Vertex {
                                 position: p,
                                 tex_coords: t,
                                 rgba: color,
-                                mode: MODE_TEXT,
+                                mode,
                             };
+                            drawn_glyphs.push(DrawnGlyph {
+                                vertex: vertices.len(),
+                                font_id: cache_id,
+                                glyph: g.glyph,
+                            });
                             let mut push_v = |p, t| vertices.push(v(p, t));
-                            push_v(
-                                [vk_rect.min.x, vk_rect.max.y],
-                                [uv_rect.min.x, uv_rect.max.y],
-                            );
-                            push_v(
-                                [vk_rect.min.x, vk_rect.min.y],
-                                [uv_rect.min.x, uv_rect.min.y],
-                            );
-                            push_v(
-                                [vk_rect.max.x, vk_rect.min.y],
-                                [uv_rect.max.x, uv_rect.min.y],
-                            );
-                            push_v(
-                                [vk_rect.max.x, vk_rect.min.y],
-                                [uv_rect.max.x, uv_rect.min.y],
-                            );
-                            push_v(
-                                [vk_rect.max.x, vk_rect.max.y],
-                                [uv_rect.max.x, uv_rect.max.y],
-                            );
-                            push_v(
-                                [vk_rect.min.x, vk_rect.max.y],
-                                [uv_rect.min.x, uv_rect.max.y],
-                            );
+                            push_v(corner(vk_rect.min.x, vk_rect.max.y), tex_coords[0]);
+                            push_v(corner(vk_rect.min.x, vk_rect.min.y), tex_coords[1]);
+                            push_v(corner(vk_rect.max.x, vk_rect.min.y), tex_coords[2]);
+                            push_v(corner(vk_rect.max.x, vk_rect.min.y), tex_coords[3]);
+                            push_v(corner(vk_rect.max.x, vk_rect.max.y), tex_coords[4]);
+                            push_v(corner(vk_rect.min.x, vk_rect.max.y), tex_coords[5]);
                         }
                     }
                 }
@@ -443,48 +916,29 @@ impl Mesh {
                     image_id,
                     color,
                     source_rect,
+                    nine_slice,
+                    fit,
                 } => {
-                    let image_ref = match image_map.get(&image_id) {
+                    let [image_w, image_h] = match image_map.image_dimensions(image_id) {
                         None => continue,
-                        Some(img) => img,
+                        Some(dims) => dims,
                     };
 
                     // Switch to the `Image` state for this image if we're not in it already.
-                    let new_image_id = image_id;
-                    match current_state {
-                        // If we're already in the drawing mode for this image, we're done.
-                        State::Image { image_id, .. } if image_id == new_image_id => (),
-
-                        // If we were in the `Plain` drawing state, switch to Image drawing state.
-                        State::Plain { start } => {
-                            commands.push(PreparedCommand::Plain(start..vertices.len()));
-                            current_state = State::Image {
-                                image_id: new_image_id,
-                                start: vertices.len(),
-                            };
-                        }
-
-                        // If we were drawing a different image, switch state to draw *this* image.
-                        State::Image { image_id, start } => {
-                            commands.push(PreparedCommand::Image(image_id, start..vertices.len()));
-                            current_state = State::Image {
-                                image_id: new_image_id,
-                                start: vertices.len(),
-                            };
-                        }
-                    }
+                    switch_to_image_state!(image_id);
 
                     let color = color.unwrap_or(color::WHITE).to_fsa();
-                    let [image_w, image_h] = image_ref.dimensions();
+                    widget_image = Some((image_id, [image_w, image_h]));
                     let (image_w, image_h) = (image_w as Scalar, image_h as Scalar);
 
-                    // Get the sides of the source rectangle as uv coordinates.
-                    //
-                    // Texture coordinates range:
-                    // - left to right: 0.0 to 1.0
-                    // - bottom to top: 1.0 to 0.0
-                    let (uv_l, uv_r, uv_b, uv_t) = match source_rect {
-                        Some(src_rect) => {
+                    // Push the triangles drawing the given area of the image to the given rect.
+                    let mut push_quad = |rect: Rect, src_rect: Rect| {
+                        // Get the sides of the source rectangle as uv coordinates.
+                        //
+                        // Texture coordinates range:
+                        // - left to right: 0.0 to 1.0
+                        // - bottom to top: 1.0 to 0.0
+                        let (uv_l, uv_r, uv_b, uv_t) = {
                             let (l, r, b, t) = src_rect.l_r_b_t();
                             (
                                 (l / image_w) as f32,
@@ -492,41 +946,84 @@ impl Mesh {
                                 1.0 - (b / image_h) as f32,
                                 1.0 - (t / image_h) as f32,
                             )
-                        }
-                        None => (0.0, 1.0, 1.0, 0.0),
-                    };
+                        };
 
-                    let v = |x, y, t| {
-                        // Convert from conrod Scalar range to normalised range -1.0 to 1.0.
-                        let x = (x * dpi_factor / half_viewport_w) as f32;
-                        let y = -((y * dpi_factor / half_viewport_h) as f32);
-                        Vertex {
-                            position: [x, y],
-                            tex_coords: t,
-                            rgba: color,
-                            mode: MODE_IMAGE,
-                        }
-                    };
+                        let v = |x, y, t| {
+                            // Convert from conrod Scalar range to normalised range -1.0 to 1.0.
+                            Vertex {
+                                position: position([x, y]),
+                                tex_coords: t,
+                                rgba: color,
+                                mode: MODE_IMAGE,
+                            }
+                        };
 
-                    let mut push_v = |x, y, t| vertices.push(v(x, y, t));
+                        let mut push_v = |x, y, t| vertices.push(v(x, y, t));
 
-                    // Swap bottom and top to suit reversed vulkan coords.
-                    let (l, r, b, t) = rect.l_r_b_t();
+                        // Swap bottom and top to suit reversed vulkan coords.
+                        let (l, r, b, t) = rect.l_r_b_t();
 
-                    // Bottom left triangle.
-                    push_v(l, t, [uv_l, uv_t]);
-                    push_v(r, b, [uv_r, uv_b]);
-                    push_v(l, b, [uv_l, uv_b]);
+                        // Bottom left triangle.
+                        push_v(l, t, [uv_l, uv_t]);
+                        push_v(r, b, [uv_r, uv_b]);
+                        push_v(l, b, [uv_l, uv_b]);
 
-                    // Top right triangle.
-                    push_v(l, t, [uv_l, uv_t]);
-                    push_v(r, b, [uv_r, uv_b]);
-                    push_v(r, t, [uv_r, uv_t]);
+                        // Top right triangle.
+                        push_v(l, t, [uv_l, uv_t]);
+                        push_v(r, b, [uv_r, uv_b]);
+                        push_v(r, t, [uv_r, uv_t]);
+                    };
+
+                    let image_rect = Rect::from_corners([0.0, 0.0], [image_w, image_h]);
+                    let source_rect = source_rect.unwrap_or(image_rect);
+                    let quads = widget::image::quads(rect, source_rect, fit, nine_slice);
+                    for (rect, src_rect) in quads {
+                        push_quad(rect, src_rect);
+                    }
+                }
+
+                // Custom widgets are drawn by the user between the vertices that precede and
+                // follow them.
+                render::PrimitiveKind::Custom { data } => {
+                    match current_state {
+                        State::Plain { start } => {
+                            commands.push(PreparedCommand::Plain(start..vertices.len()))
+                        }
+                        State::Image { image_id, start } => {
+                            commands.push(PreparedCommand::Image(image_id, start..vertices.len()))
+                        }
+                    }
+                    if let Some(current_scizzor) = current_scizzor {
+                        commands.push(PreparedCommand::Custom(Custom {
+                            id,
+                            data: data.clone(),
+                            rect,
+                            transform,
+                            opacity,
+                            scizzor: current_scizzor,
+                        }));
+                    }
+                    current_state = State::Plain {
+                        start: vertices.len(),
+                    };
+                    continue;
                 }
 
                 // We have no special case widgets to handle.
-                render::PrimitiveKind::Other(_) => (),
+                render::PrimitiveKind::Other(_) => continue,
             }
+
+            // Record the widget's vertices so that they may be reused by the next fill.
+            let widget = WidgetVertices {
+                range: start..vertices.len(),
+                glyphs: glyphs_start..drawn_glyphs.len(),
+                image: widget_image,
+                rect,
+                scizzor,
+                transform,
+                opacity,
+            };
+            widget_vertices.insert(id, widget);
         }
 
         // Enter the final command.
@@ -537,14 +1034,57 @@ impl Mesh {
             }
         }
 
+        // If the whole glyph cache must be uploaded, there's no need to upload its parts.
+        if glyph_cache_resized || glyph_cache_cleared {
+            let (w, h) = glyph_cache.dimensions();
+            glyph_cache_updated_rects.clear();
+            glyph_cache_updated_rects.push(rt::Rect {
+                min: rt::point(0, 0),
+                max: rt::point(w, h),
+            });
+        }
+
+        let glyph_cache_resized = match glyph_cache_resized {
+            false => None,
+            true => {
+                let (w, h) = glyph_cache.dimensions();
+                Some([w, h])
+            }
+        };
+
+        *filled = Some((viewport, dpi_factor));
+
+        let mut stats = FillStats {
+            vertices: vertices.len(),
+            triangles: vertices.len() / 3,
+            glyph_cache_uploads: glyph_cache_requires_upload as usize,
+            ..FillStats::default()
+        };
+        for command in commands.iter() {
+            match *command {
+                PreparedCommand::Image(_, ref range) | PreparedCommand::Plain(ref range) => {
+                    if !range.is_empty() {
+                        stats.draw_commands += 1;
+                    }
+                }
+                PreparedCommand::Scizzor(_) => stats.scizzor_changes += 1,
+                PreparedCommand::Custom(_) | PreparedCommand::ClipMask(_) => (),
+            }
+        }
+        primitives.filled(&stats);
+
         let fill = Fill {
             glyph_cache_requires_upload,
+            glyph_cache_resized,
+            stats,
         };
 
         Ok(fill)
     }
 
     /// The rusttype glyph cache used for managing caching of glyphs into the pixel buffer.
+    ///
+    /// When drawing text from signed distance fields, only the dimensions of this cache are used.
     pub fn glyph_cache(&self) -> &text::GlyphCache {
         &self.glyph_cache.0
     }
@@ -554,6 +1094,15 @@ impl Mesh {
         &self.glyph_cache_pixel_buffer
     }
 
+    /// The regions of the glyph cache pixel buffer that were written to during the previous fill.
+    ///
+    /// When `Fill::glyph_cache_requires_upload` is `true`, backends may upload only these regions
+    /// of the pixel buffer rather than the whole glyph cache. If the glyph cache was resized or
+    /// cleared, this is a single rect covering the whole cache.
+    pub fn glyph_cache_updated_rects(&self) -> &[rt::Rect<u32>] {
+        &self.glyph_cache_updated_rects
+    }
+
     /// Produce an `Iterator` yielding `Command`s.
     ///
     /// These commands describe the order in which unique draw commands and scizzor updates should
@@ -581,10 +1130,169 @@ impl<'a> Iterator for Commands<'a> {
             PreparedCommand::Scizzor(scizzor) => Command::Scizzor(scizzor),
             PreparedCommand::Plain(ref range) => Command::Draw(Draw::Plain(range.clone())),
             PreparedCommand::Image(id, ref range) => Command::Draw(Draw::Image(id, range.clone())),
+            PreparedCommand::Custom(ref custom) => Command::Custom(custom.clone()),
+            PreparedCommand::ClipMask(ref clip_mask) => Command::ClipMask(clip_mask.clone()),
         })
     }
 }
 
+impl PartialEq for Custom {
+    fn eq(&self, other: &Self) -> bool {
+        self.id == other.id
+            && std::sync::Arc::ptr_eq(&self.data, &other.data)
+            && self.rect == other.rect
+            && self.transform == other.transform
+            && self.opacity == other.opacity
+            && self.scizzor == other.scizzor
+    }
+}
+
+impl SdfGlyphCache {
+    // Remove all fields from the cache.
+    fn clear(&mut self) {
+        self.glyphs.clear();
+        self.cursor = [0, 0];
+        self.row_height = 0;
+    }
+
+    // Compute the field of the given glyph and write it to the pixel buffer if not yet cached.
+    //
+    // Returns the region of the pixel buffer that was written to, if any.
+    fn cache(
+        &mut self,
+        font_id: usize,
+        glyph: &text::PositionedGlyph,
+        pixel_buffer: &mut [u8],
+        [width, height]: [u32; 2],
+    ) -> Result<Option<rt::Rect<u32>>, rt::gpu_cache::CacheWriteErr> {
+        let key = (font_id, glyph.id());
+        if self.glyphs.contains_key(&key) {
+            return Ok(None);
+        }
+        let (bounds, [w, h], field) = match sdf_field(glyph) {
+            None => {
+                self.glyphs.insert(key, None);
+                return Ok(None);
+            }
+            Some(field) => field,
+        };
+        if w >= width || h >= height {
+            return Err(rt::gpu_cache::CacheWriteErr::GlyphTooLarge);
+        }
+
+        // Start a new row if the field does not fit on the current one.
+        if self.cursor[0] + w > width {
+            self.cursor = [0, self.cursor[1] + self.row_height];
+            self.row_height = 0;
+        }
+        if self.cursor[1] + h > height {
+            return Err(rt::gpu_cache::CacheWriteErr::NoRoomForWholeQueue);
+        }
+        let [x, y] = self.cursor;
+        self.cursor[0] += w;
+        self.row_height = self.row_height.max(h);
+
+        for (row, pixels) in field.chunks(w as usize).enumerate() {
+            let start = (y as usize + row) * width as usize + x as usize;
+            pixel_buffer[start..start + w as usize].copy_from_slice(pixels);
+        }
+        let tex_rect = rt::Rect {
+            min: rt::point(x, y),
+            max: rt::point(x + w, y + h),
+        };
+        self.glyphs.insert(key, Some(SdfGlyph { tex_rect, bounds }));
+        Ok(Some(tex_rect))
+    }
+
+    // The texture coordinates of the given glyph's field and the rect in pixels at which it should
+    // be drawn.
+    fn rect_for(
+        &self,
+        font_id: usize,
+        glyph: &text::PositionedGlyph,
+        [width, height]: [u32; 2],
+    ) -> Option<(rt::Rect<f32>, rt::Rect<f32>)> {
+        let SdfGlyph { tex_rect, bounds } = (*self.glyphs.get(&(font_id, glyph.id()))?)?;
+        let uv =
+            |p: rt::Point<u32>| rt::point(p.x as f32 / width as f32, p.y as f32 / height as f32);
+        let uv_rect = rt::Rect {
+            min: uv(tex_rect.min),
+            max: uv(tex_rect.max),
+        };
+        let scale = glyph.scale();
+        let position = glyph.position();
+        let screen = |p: rt::Point<f32>| {
+            rt::point(
+                position.x + p.x * scale.x / SDF_GLYPH_SCALE,
+                position.y + p.y * scale.y / SDF_GLYPH_SCALE,
+            )
+        };
+        let screen_rect = rt::Rect {
+            min: screen(bounds.min),
+            max: screen(bounds.max),
+        };
+        Some((uv_rect, screen_rect))
+    }
+}
+
+// Rasterise the given glyph at `SDF_GLYPH_SCALE` and compute its signed distance field.
+//
+// Returns the bounds of the field relative to the glyph's origin, its dimensions and its pixels,
+// or `None` if the glyph has no outline.
+fn sdf_field(glyph: &text::PositionedGlyph) -> Option<(rt::Rect<f32>, [u32; 2], Vec<u8>)> {
+    let glyph = glyph
+        .unpositioned()
+        .unscaled()
+        .clone()
+        .scaled(text::Scale::uniform(SDF_GLYPH_SCALE))
+        .positioned(rt::point(0.0, 0.0));
+    let bb = glyph.pixel_bounding_box()?;
+    let w = bb.width() + SDF_SPREAD * 2;
+    let h = bb.height() + SDF_SPREAD * 2;
+
+    // Whether or not each pixel lies within the glyph.
+    let mut inside = vec![false; (w * h) as usize];
+    glyph.draw(|x, y, coverage| {
+        let (x, y) = (x as i32 + SDF_SPREAD, y as i32 + SDF_SPREAD);
+        inside[(y * w + x) as usize] = coverage >= 0.5;
+    });
+    let is_inside =
+        |x: i32, y: i32| x >= 0 && y >= 0 && x < w && y < h && inside[(y * w + x) as usize];
+
+    // The distance from each pixel to the nearest pixel on the other side of the outline, with
+    // the outline lying half way between them.
+    let mut field = Vec::with_capacity((w * h) as usize);
+    for y in 0..h {
+        for x in 0..w {
+            let inside = is_inside(x, y);
+            let mut nearest_sq = SDF_SPREAD * SDF_SPREAD * 4;
+            for dy in -SDF_SPREAD..SDF_SPREAD + 1 {
+                for dx in -SDF_SPREAD..SDF_SPREAD + 1 {
+                    if is_inside(x + dx, y + dy) != inside {
+                        nearest_sq = nearest_sq.min(dx * dx + dy * dy);
+                    }
+                }
+            }
+            let distance = (nearest_sq as f32).sqrt() - 0.5;
+            let signed = if inside { distance } else { -distance };
+            let value = 0.5 + signed / (SDF_SPREAD as f32 * 2.0);
+            field.push((value.clamp(0.0, 1.0) * 255.0).round() as u8);
+        }
+    }
+
+    let bounds = rt::Rect {
+        min: rt::point(
+            (bb.min.x - SDF_SPREAD) as f32,
+            (bb.min.y - SDF_SPREAD) as f32,
+        ),
+        max: rt::point(
+            (bb.max.x + SDF_SPREAD) as f32,
+            (bb.max.y + SDF_SPREAD) as f32,
+        ),
+    };
+    Some((bounds, [w as u32, h as u32], field))
+}
+
 impl ops::Deref for GlyphCache {
     type Target = text::GlyphCache<'static>;
     fn deref(&self) -> &Self::Target {
@@ -610,14 +1318,233 @@ impl From<text::GlyphCache<'static>> for GlyphCache {
     }
 }
 
-fn gamma_srgb_to_linear(c: [f32; 4]) -> [f32; 4] {
-    fn component(f: f32) -> f32 {
-        // Taken from https://github.com/PistonDevelopers/graphics/src/color.rs#L42
-        if f <= 0.04045 {
-            f / 12.92
-        } else {
-            ((f + 0.055) / 1.055).powf(2.4)
+// How the glyph cache was updated by `cache_glyphs`.
+#[derive(Default)]
+struct CachedGlyphs {
+    // Whether or not the pixel buffer was written to.
+    written: bool,
+    // Whether or not the glyph cache was grown.
+    resized: bool,
+    // Whether or not the whole pixel buffer was cleared.
+    cleared: bool,
+    // Whether or not the previously drawn glyphs were cached again.
+    requeued: bool,
+}
+
+// Cache the `queued` glyphs within whichever glyph cache is in use.
+//
+// If the glyph cache has no room for them, make room and try again, growing the glyph cache if
+// necessary. Making room may evict the `drawn` glyphs, in which case these are cached again too.
+//
+// Each region of the pixel buffer that is written to is pushed onto `updated_rects`.
+fn cache_glyphs<'a, I>(
+    glyph_cache: &mut GlyphCache,
+    glyph_cache_pixel_buffer: &mut Vec<u8>,
+    max_glyph_cache_dims: [u32; 2],
+    sdf_glyph_cache: &mut Option<SdfGlyphCache>,
+    updated_rects: &mut Vec<rt::Rect<u32>>,
+    drawn: &'a [DrawnGlyph],
+    queued: I,
+) -> Result<CachedGlyphs, rt::gpu_cache::CacheWriteErr>
+where
+    I: Iterator<Item = (usize, &'a text::PositionedGlyph)> + Clone,
+{
+    let mut cached = CachedGlyphs::default();
+    match *sdf_glyph_cache {
+        // Evict the fields of glyphs from previous fills before growing the cache.
+        Some(ref mut sdf_glyph_cache) => loop {
+            let requeued = cached.requeued;
+            let drawn = drawn
+                .iter()
+                .filter(|_| requeued)
+                .map(|g| (g.font_id, &g.glyph));
+            let (w, h) = glyph_cache.dimensions();
+            let result =
+                drawn
+                    .chain(queued.clone())
+                    .try_fold(false, |written, (cache_id, glyph)| {
+                        sdf_glyph_cache
+                            .cache(cache_id, glyph, glyph_cache_pixel_buffer, [w, h])
+                            .map(|rect| match rect {
+                                None => written,
+                                Some(rect) => {
+                                    updated_rects.push(rect);
+                                    true
+                                }
+                            })
+                    });
+
+            match result {
+                Ok(written) => {
+                    cached.written |= written;
+                    return Ok(cached);
+                }
+                Err(err) => {
+                    if cached.requeued {
+                        if !grow_glyph_cache(
+                            glyph_cache,
+                            glyph_cache_pixel_buffer,
+                            max_glyph_cache_dims,
+                        ) {
+                            return Err(err);
+                        }
+                        cached.resized = true;
+                    }
+                    sdf_glyph_cache.clear();
+                    for pixel in glyph_cache_pixel_buffer.iter_mut() {
+                        *pixel = 0;
+                    }
+                    cached.written = true;
+                    cached.cleared = true;
+                    cached.requeued = true;
+                }
+            }
+        },
+
+        // Rusttype evicts glyphs itself, so the cache only needs to grow.
+        None => loop {
+            if cached.requeued {
+                for g in drawn {
+                    glyph_cache.queue_glyph(g.font_id, g.glyph.clone());
+                }
+            }
+            for (cache_id, glyph) in queued.clone() {
+                glyph_cache.queue_glyph(cache_id, glyph.clone());
+            }
+
+            // Width of the glyph cache is useful when writing to the pixel buffer.
+            let glyph_cache_w = glyph_cache.dimensions().0 as usize;
+            let written = &mut cached.written;
+            let result = glyph_cache.cache_queued(|rect, data| {
+                let width = (rect.max.x - rect.min.x) as usize;
+                let height = (rect.max.y - rect.min.y) as usize;
+                let mut dst_ix = rect.min.y as usize * glyph_cache_w + rect.min.x as usize;
+                let mut src_ix = 0;
+                for _ in 0..height {
+                    let dst_range = dst_ix..dst_ix + width;
+                    let src_range = src_ix..src_ix + width;
+                    let dst_slice = &mut glyph_cache_pixel_buffer[dst_range];
+                    let src_slice = &data[src_range];
+                    dst_slice.copy_from_slice(src_slice);
+                    dst_ix += glyph_cache_w;
+                    src_ix += width;
+                }
+                updated_rects.push(rect);
+                *written = true;
+            });
+
+            match result {
+                Ok(rt::gpu_cache::CachedBy::Adding) => return Ok(cached),
+                Ok(rt::gpu_cache::CachedBy::Reordering) => {
+                    if cached.requeued || drawn.is_empty() {
+                        return Ok(cached);
+                    }
+                }
+                Err(err) => {
+                    glyph_cache.clear_queue();
+                    if !grow_glyph_cache(
+                        glyph_cache,
+                        glyph_cache_pixel_buffer,
+                        max_glyph_cache_dims,
+                    ) {
+                        return Err(err);
+                    }
+                    cached.resized = true;
+                }
+            }
+            cached.requeued = true;
+        },
+    }
+}
+
+// Rewrite the texture coordinates of the given glyphs' vertices from the glyph cache.
+fn rewrite_glyph_tex_coords(
+    glyph_cache: &GlyphCache,
+    sdf_glyph_cache: &Option<SdfGlyphCache>,
+    glyphs: &[DrawnGlyph],
+    vertices: &mut [Vertex],
+) {
+    for g in glyphs {
+        if let Some((uv_rect, _)) =
+            cached_glyph_rects(glyph_cache, sdf_glyph_cache, g.font_id, &g.glyph)
+        {
+            let glyph_vertices = &mut vertices[g.vertex..g.vertex + 6];
+            let tex_coords = glyph_tex_coords(uv_rect);
+            for (v, &t) in glyph_vertices.iter_mut().zip(tex_coords.iter()) {
+                v.tex_coords = t;
+            }
         }
     }
-    [component(c[0]), component(c[1]), component(c[2]), c[3]]
+}
+
+// The texture coordinates of the two triangles of a glyph's quad within the glyph cache.
+fn glyph_tex_coords(uv_rect: rt::Rect<f32>) -> [[f32; 2]; 6] {
+    let rt::Rect { min, max } = uv_rect;
+    [
+        [min.x, max.y],
+        [min.x, min.y],
+        [max.x, min.y],
+        [max.x, min.y],
+        [max.x, max.y],
+        [min.x, max.y],
+    ]
+}
+
+// The texture coordinates and the rect in pixels at which the given glyph should be drawn, from
+// whichever glyph cache is in use.
+fn cached_glyph_rects(
+    glyph_cache: &GlyphCache,
+    sdf_glyph_cache: &Option<SdfGlyphCache>,
+    font_id: usize,
+    glyph: &text::PositionedGlyph,
+) -> Option<(rt::Rect<f32>, rt::Rect<f32>)> {
+    match *sdf_glyph_cache {
+        Some(ref sdf_glyph_cache) => {
+            let (w, h) = glyph_cache.dimensions();
+            sdf_glyph_cache.rect_for(font_id, glyph, [w, h])
+        }
+        None => {
+            let (uv_rect, screen_rect) = glyph_cache.rect_for(font_id, glyph).ok()??;
+            let screen_rect = rt::Rect {
+                min: rt::point(screen_rect.min.x as f32, screen_rect.min.y as f32),
+                max: rt::point(screen_rect.max.x as f32, screen_rect.max.y as f32),
+            };
+            Some((uv_rect, screen_rect))
+        }
+    }
+}
+
+// Double the dimensions of the glyph cache, limited to the given maximum dimensions.
+//
+// Clears the glyph cache and its pixel buffer. Returns `false` if the glyph cache is already at
+// its maximum dimensions.
+fn grow_glyph_cache(
+    glyph_cache: &mut GlyphCache,
+    glyph_cache_pixel_buffer: &mut Vec<u8>,
+    max_glyph_cache_dims: [u32; 2],
+) -> bool {
+    let (w, h) = glyph_cache.dimensions();
+    let [max_w, max_h] = max_glyph_cache_dims;
+    let new_w = w.saturating_mul(2).min(max_w).max(w);
+    let new_h = h.saturating_mul(2).min(max_h).max(h);
+    if (new_w, new_h) == (w, h) {
+        return false;
+    }
+    glyph_cache
+        .to_builder()
+        .dimensions(new_w, new_h)
+        .rebuild(&mut glyph_cache.0);
+    glyph_cache_pixel_buffer.clear();
+    glyph_cache_pixel_buffer.resize(new_w as usize * new_h as usize, 0);
+    true
+}
+
+fn gamma_srgb_to_linear(c: [f32; 4]) -> [f32; 4] {
+    let [r, g, b, a] = c;
+    [
+        color::srgb_to_linear(r),
+        color::srgb_to_linear(g),
+        color::srgb_to_linear(b),
+        a,
+    ]
 }
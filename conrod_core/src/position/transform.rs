@@ -0,0 +1,144 @@
+//! Defines the 2D affine `Transform` type, used for rotating and scaling widgets.
+
+use super::{Point, Rect, Scalar};
+
+/// A 2D affine transform.
+///
+/// A point `[x, y]` is transformed to `x * x_axis + y * y_axis + translation`.
+///
+/// Transforms are used to rotate and scale widgets (and all of their descendants) when rendering
+/// and when determining which widget lies under some point. See the `Widget::transform` method.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Transform {
+    /// The vector to which the *x* axis unit vector is transformed.
+    pub x_axis: [Scalar; 2],
+    /// The vector to which the *y* axis unit vector is transformed.
+    pub y_axis: [Scalar; 2],
+    /// The translation applied after transforming by the axes.
+    pub translation: [Scalar; 2],
+}
+
+impl Transform {
+    /// The transform that leaves all points unchanged.
+    pub const IDENTITY: Transform = Transform {
+        x_axis: [1.0, 0.0],
+        y_axis: [0.0, 1.0],
+        translation: [0.0, 0.0],
+    };
+
+    /// A transform that translates points by the given vector.
+    pub fn translation(translation: [Scalar; 2]) -> Self {
+        Transform {
+            translation: translation,
+            ..Transform::IDENTITY
+        }
+    }
+
+    /// A transform that rotates points counter-clockwise about the origin by the given angle.
+    pub fn rotation(radians: Scalar) -> Self {
+        let (sin, cos) = radians.sin_cos();
+        Transform {
+            x_axis: [cos, sin],
+            y_axis: [-sin, cos],
+            translation: [0.0, 0.0],
+        }
+    }
+
+    /// A transform that scales points about the origin by the given factor along both axes.
+    pub fn scale(scale: Scalar) -> Self {
+        Self::scale_xy(scale, scale)
+    }
+
+    /// A transform that scales points about the origin by the given factor along each axis.
+    pub fn scale_xy(x: Scalar, y: Scalar) -> Self {
+        Transform {
+            x_axis: [x, 0.0],
+            y_axis: [0.0, y],
+            translation: [0.0, 0.0],
+        }
+    }
+
+    /// The transform that first applies `self` and then applies `other`.
+    pub fn then(self, other: Transform) -> Self {
+        Transform {
+            x_axis: other.apply_vector(self.x_axis),
+            y_axis: other.apply_vector(self.y_axis),
+            translation: other.apply(self.translation),
+        }
+    }
+
+    /// The same transform, but applied about the given `origin` rather than `[0.0, 0.0]`.
+    ///
+    /// For example, a rotation about the origin becomes a rotation about the given point.
+    pub fn about(self, origin: Point) -> Self {
+        let [x, y] = origin;
+        Transform::translation([-x, -y])
+            .then(self)
+            .then(Transform::translation(origin))
+    }
+
+    /// Transform the given point.
+    pub fn apply(&self, point: Point) -> Point {
+        let [x, y] = self.apply_vector(point);
+        [x + self.translation[0], y + self.translation[1]]
+    }
+
+    /// Transform the given vector, ignoring the translation.
+    pub fn apply_vector(&self, vector: [Scalar; 2]) -> [Scalar; 2] {
+        let [x, y] = vector;
+        [
+            x * self.x_axis[0] + y * self.y_axis[0],
+            x * self.x_axis[1] + y * self.y_axis[1],
+        ]
+    }
+
+    /// The determinant of the linear part of the transform.
+    ///
+    /// This is the factor by which the transform scales areas. It is negative if the transform
+    /// flips the orientation of shapes.
+    pub fn determinant(&self) -> Scalar {
+        self.x_axis[0] * self.y_axis[1] - self.y_axis[0] * self.x_axis[1]
+    }
+
+    /// The transform that undoes this transform.
+    ///
+    /// Returns `None` if the transform collapses the plane onto a line or point and thus cannot
+    /// be undone.
+    pub fn inverse(&self) -> Option<Transform> {
+        let det = self.determinant();
+        if det == 0.0 || !det.is_finite() {
+            return None;
+        }
+        let x_axis = [self.y_axis[1] / det, -self.x_axis[1] / det];
+        let y_axis = [-self.y_axis[0] / det, self.x_axis[0] / det];
+        let linear = Transform {
+            x_axis: x_axis,
+            y_axis: y_axis,
+            translation: [0.0, 0.0],
+        };
+        let [tx, ty] = linear.apply(self.translation);
+        Some(Transform {
+            translation: [-tx, -ty],
+            ..linear
+        })
+    }
+
+    /// The smallest axis-aligned `Rect` bounding the given `rect` once transformed.
+    pub fn bounding_rect(&self, rect: Rect) -> Rect {
+        let (l, r, b, t) = rect.l_r_b_t();
+        let corners = [[l, b], [l, t], [r, t], [r, b]];
+        let first = self.apply(corners[0]);
+        corners[1..]
+            .iter()
+            .fold(Rect::from_corners(first, first), |bounds, &corner| {
+                let corner = self.apply(corner);
+                bounds.max(Rect::from_corners(corner, corner))
+            })
+    }
+}
+
+impl Default for Transform {
+    fn default() -> Self {
+        Transform::IDENTITY
+    }
+}
@@ -11,10 +11,13 @@
 use color;
 use graph::{self, Graph};
 use image;
-use position::{Align, Dimensions};
+use mesh;
+use position::{Align, Dimensions, Padding, Transform};
 use std;
 use text;
 use theme::Theme;
+use ui;
+use widget::text::Highlight;
 use widget::triangles::{ColoredPoint, Triangle};
 use widget::{self, Widget};
 use {Color, FontSize, Point, Rect, Scalar};
@@ -28,7 +31,9 @@ use {Color, FontSize, Point, Rect, Scalar};
 /// require ownership over the sequence of primitives, consider using the `OwnedPrimitives` type.
 /// The `OwnedPrimitives` type can be produced by calling the `Primitives::owned` method.
 pub struct Primitives<'a> {
-    crop_stack: Vec<(widget::Id, Rect)>,
+    crop_stack: Vec<(widget::Id, Rect, Option<Clip>)>,
+    /// A buffer containing the `Clip`s within the `crop_stack` for the current `Primitive`.
+    clips: Vec<Clip>,
     depth_order: std::slice::Iter<'a, widget::Id>,
     graph: &'a Graph,
     theme: &'a Theme,
@@ -36,6 +41,12 @@ pub struct Primitives<'a> {
     window_rect: Rect,
     /// A buffer to use for triangulating polygons and lines for the `Triangles`.
     triangles: Vec<Triangle<Point>>,
+    /// A buffer to use for triangulating anti-aliased polygons and lines.
+    colored_triangles: Vec<Triangle<ColoredPoint>>,
+    /// The stats into which the yielded primitives are recorded, if any.
+    stats: Option<&'a std::sync::Mutex<ui::Stats>>,
+    /// The moment at which the first primitive was requested, if stats are being recorded.
+    first_requested: Option<std::time::Instant>,
 }
 
 /// An owned alternative to the `Primitives` type.
@@ -50,6 +61,8 @@ pub struct OwnedPrimitives {
     triangles_multi_color: Vec<Triangle<ColoredPoint>>,
     line_infos: Vec<text::line::Info>,
     texts_string: String,
+    highlights: Vec<Highlight>,
+    clips: Vec<Clip>,
 }
 
 /// A trait that allows the user to remain generic over types yielding `Primitive`s.
@@ -58,12 +71,32 @@ pub struct OwnedPrimitives {
 pub trait PrimitiveWalker {
     /// Yield the next `Primitive` in order of depth, bottom to top.
     fn next_primitive(&mut self) -> Option<Primitive>;
+
+    /// Called by a `mesh::Mesh` once it has been filled from the yielded primitives.
+    ///
+    /// Does nothing by default. The `Primitives` of a `Ui` with stats enabled record the given
+    /// `FillStats` within the `Ui`'s `Stats`.
+    fn filled(&mut self, _stats: &mesh::FillStats) {}
 }
 
 impl<'a> PrimitiveWalker for Primitives<'a> {
     fn next_primitive(&mut self) -> Option<Primitive> {
         self.next()
     }
+
+    fn filled(&mut self, fill: &mesh::FillStats) {
+        if let Some(stats) = self.stats {
+            let mut stats = stats.lock().unwrap();
+            stats.vertices += fill.vertices;
+            stats.triangles += fill.triangles;
+            stats.draw_commands += fill.draw_commands;
+            stats.scizzor_changes += fill.scizzor_changes;
+            stats.glyph_cache_uploads += fill.glyph_cache_uploads;
+            if let Some(start) = self.first_requested.take() {
+                stats.fill_duration += start.elapsed();
+            }
+        }
+    }
 }
 
 impl<'a> PrimitiveWalker for WalkOwnedPrimitives<'a> {
@@ -82,8 +115,45 @@ pub struct Primitive<'a> {
     ///
     /// Only parts of the widget within this `Rect` should be drawn.
     pub scizzor: Rect,
+    /// The non-rectangular regions to which the primitive widget should also be cropped, ordered
+    /// from the outermost cropping widget to the innermost.
+    ///
+    /// Only parts of the widget within all of these regions should be drawn. The `scizzor` always
+    /// bounds these regions, so backends that cannot crop to them may use the `scizzor` alone.
+    pub clips: &'a [Clip],
     /// The bounding rectangle for the `Primitive`.
+    ///
+    /// This is given in the untransformed space in which the widget was laid out.
     pub rect: Rect,
+    /// The transform mapping the primitive from the space in which it was laid out to the window.
+    ///
+    /// This is `None` if neither the widget nor any of its depth parents are transformed. Where
+    /// `Some`, all positions within the primitive (including those of triangles and glyphs)
+    /// should be transformed before drawing. Note that the `scizzor` is already given in window
+    /// space.
+    pub transform: Option<Transform>,
+    /// The opacity with which the primitive is drawn.
+    ///
+    /// This is the product of the opacity of the widget and that of all of its depth parents. The
+    /// colours within the `kind` have already been faded by it.
+    pub opacity: f32,
+}
+
+/// A non-rectangular region to which a `Primitive` is cropped in addition to its `scizzor`.
+///
+/// These are produced by widgets whose children are cropped to a `widget::CropShape` other than a
+/// `Rectangle`, as well as by rectangular crops that are rotated by a transform.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Clip {
+    /// The id of the widget cropping its children.
+    pub id: widget::Id,
+    /// The shape of the region.
+    pub shape: widget::CropShape,
+    /// The bounds of the shape (the cropping widget's `kid_area`) within the untransformed space in
+    /// which the widget was laid out.
+    pub rect: Rect,
+    /// The transform mapping the `rect` to the window, if any.
+    pub transform: Option<Transform>,
 }
 
 /// The unique kind for each primitive element in the Ui.
@@ -122,6 +192,13 @@ pub enum PrimitiveKind<'a> {
         color: Option<Color>,
         /// The area of the texture that will be drawn to the `Image`'s `Rect`.
         source_rect: Option<Rect>,
+        /// When `Some`, the source rectangle is divided into nine slices by these insets.
+        nine_slice: Option<Padding>,
+        /// The way in which the source rectangle is fitted to the `Image`'s `Rect`.
+        ///
+        /// See `widget::image::quads` for the rectangles that should be drawn given both the
+        /// `nine_slice` and the `fit`.
+        fit: widget::image::Fit,
     },
 
     /// A single block of `Text`, produced by the primitive `Text` widget.
@@ -136,6 +213,19 @@ pub enum PrimitiveKind<'a> {
         font_id: text::font::Id,
     },
 
+    /// A widget whose contents should be drawn by the user's own rendering code, produced by the
+    /// primitive `CustomDraw` widget.
+    ///
+    /// The widget should be drawn within its `Rect` (transformed by the primitive's `transform`)
+    /// with the primitive's `opacity` and cropped to the primitive's `scizzor`, after all
+    /// primitives that precede it and before all primitives that follow it.
+    Custom {
+        /// The user data given to the `CustomDraw` widget.
+        ///
+        /// This may be accessed via `downcast_ref` with the type with which the widget was built.
+        data: &'a widget::custom_draw::Data,
+    },
+
     /// An `Other` variant will be yielded for every non-primitive widget in the list.
     ///
     /// Most of the time, this variant can be ignored, however it is useful for users who need to
@@ -149,6 +239,38 @@ pub enum PrimitiveKind<'a> {
     Other(&'a graph::Container),
 }
 
+impl Clip {
+    /// Whether or not the given point within the window lies within the region.
+    ///
+    /// This is useful for software renderers that crop each pixel that they draw.
+    pub fn contains(&self, point: Point) -> bool {
+        let point = match self.transform {
+            None => Some(point),
+            Some(transform) => transform.inverse().map(|inverse| inverse.apply(point)),
+        };
+        match point {
+            Some(point) => self.shape.is_over(self.rect, point),
+            None => false,
+        }
+    }
+
+    /// The triangles describing the region within the window.
+    ///
+    /// The triangles never overlap, so that each point within the region is covered exactly once,
+    /// e.g. when incrementing the values of a stencil buffer.
+    pub fn triangles(&self) -> Vec<Triangle<Point>> {
+        let mut triangles = self.shape.triangles(self.rect);
+        if let Some(transform) = self.transform {
+            for triangle in &mut triangles {
+                for point in &mut triangle.0 {
+                    *point = transform.apply(*point);
+                }
+            }
+        }
+        triangles
+    }
+}
+
 /// A type used for producing a `PositionedGlyph` iterator.
 ///
 /// We produce this type rather than the `&[PositionedGlyph]`s directly so that we can properly
@@ -163,6 +285,26 @@ pub struct Text<'a> {
     justify: text::Justify,
     y_align: Align,
     line_spacing: Scalar,
+    highlights: &'a [Highlight],
+    // The fonts referred to by the `highlights`, if known.
+    highlight_fonts: Option<&'a text::font::Map>,
+    opacity: f32,
+}
+
+/// A single glyph yielded by the `Text::highlighted_glyphs` method.
+#[derive(Clone, Debug)]
+pub struct HighlightedGlyph {
+    /// The positioned glyph, produced by the highlight's font if it specifies one.
+    pub glyph: text::PositionedGlyph,
+    /// The colour of the highlight covering the glyph, if any.
+    ///
+    /// When `None`, the glyph should be drawn with the colour of the `Text` primitive.
+    pub color: Option<Color>,
+    /// The font of the highlight covering the glyph, if any.
+    ///
+    /// When `None`, the glyph was produced by the font of the `Text` primitive. This is useful as
+    /// the font ID when caching the glyph via the `conrod::text::GlyphCache`.
+    pub font_id: Option<text::font::Id>,
 }
 
 #[derive(Clone)]
@@ -170,7 +312,10 @@ struct OwnedPrimitive {
     id: widget::Id,
     kind: OwnedPrimitiveKind,
     scizzor: Rect,
+    clips_range: std::ops::Range<usize>,
     rect: Rect,
+    transform: Option<Transform>,
+    opacity: f32,
 }
 
 #[derive(Clone)]
@@ -189,12 +334,17 @@ enum OwnedPrimitiveKind {
         image_id: image::Id,
         color: Option<Color>,
         source_rect: Option<Rect>,
+        nine_slice: Option<Padding>,
+        fit: widget::image::Fit,
     },
     Text {
         color: Color,
         font_id: text::font::Id,
         text: OwnedText,
     },
+    Custom {
+        data: widget::custom_draw::Data,
+    },
 }
 
 #[derive(Clone)]
@@ -208,6 +358,8 @@ struct OwnedText {
     justify: text::Justify,
     y_align: Align,
     line_spacing: Scalar,
+    highlights_range: std::ops::Range<usize>,
+    opacity: f32,
 }
 
 /// An iterator-like type for yielding `Primitive`s from an `OwnedPrimitives`.
@@ -217,6 +369,9 @@ pub struct WalkOwnedPrimitives<'a> {
     triangles_multi_color: &'a [Triangle<ColoredPoint>],
     line_infos: &'a [text::line::Info],
     texts_str: &'a str,
+    highlights: &'a [Highlight],
+    fonts: Option<&'a text::font::Map>,
+    clips: &'a [Clip],
 }
 
 impl<'a> Text<'a> {
@@ -269,6 +424,130 @@ impl<'a> Text<'a> {
             font.layout(line, scale, point)
         })
     }
+
+    /// Produces a `HighlightedGlyph` for each character, describing the colour and font of any
+    /// `Highlight` covering that character.
+    ///
+    /// Glyphs are positioned exactly as they are by `positioned_glyphs`. Glyphs covered by a
+    /// highlight with a font are re-created from that font at the same position, unless the font
+    /// is unknown, as when walking `OwnedPrimitives` without a font map.
+    ///
+    /// See `positioned_glyphs` for details on the `dpi_factor`.
+    pub fn highlighted_glyphs(
+        self,
+        dpi_factor: f32,
+    ) -> impl 'a + Iterator<Item = HighlightedGlyph> {
+        let Text {
+            text,
+            line_infos,
+            highlights,
+            highlight_fonts,
+            opacity,
+            ..
+        } = self;
+
+        // The byte index of every character in the order in which glyphs are laid out.
+        let byte_indices = line_infos.iter().flat_map(move |info| {
+            let start_byte = info.start_byte;
+            text[info.byte_range()]
+                .char_indices()
+                .map(move |(i, ch)| (start_byte + i, ch))
+        });
+
+        self.positioned_glyphs(dpi_factor)
+            .zip(byte_indices)
+            .map(move |(glyph, (byte, ch))| {
+                let highlight = highlight_at_byte(highlights, byte);
+                let color = highlight.and_then(|h| h.color).map(|c| c.alpha(opacity));
+                let font = highlight.and_then(|h| h.font_id).and_then(|id| {
+                    highlight_fonts
+                        .and_then(|fonts| fonts.get(id))
+                        .map(|f| (id, f))
+                });
+                match font {
+                    None => HighlightedGlyph {
+                        glyph,
+                        color,
+                        font_id: None,
+                    },
+                    Some((font_id, font)) => {
+                        let glyph = font
+                            .glyph(ch)
+                            .scaled(glyph.scale())
+                            .positioned(glyph.position());
+                        HighlightedGlyph {
+                            glyph,
+                            color,
+                            font_id: Some(font_id),
+                        }
+                    }
+                }
+            })
+    }
+
+    /// The highlighted ranges of the text, sorted by the start of their byte range.
+    ///
+    /// Note that the colours of these highlights have not been faded by the `opacity` of the text.
+    pub fn highlights(&self) -> &'a [Highlight] {
+        self.highlights
+    }
+
+    /// The opacity by which the colours of the `highlights` should be multiplied.
+    ///
+    /// The colour of the `Text` primitive itself has already been faded.
+    pub fn opacity(&self) -> f32 {
+        self.opacity
+    }
+
+    /// The full string of the text.
+    pub fn text(&self) -> &'a str {
+        self.text
+    }
+
+    /// The byte range within the `text` and the bounding rectangle of each line.
+    pub fn lines(&self) -> impl 'a + Iterator<Item = (std::ops::Range<usize>, Rect)> {
+        let Text {
+            line_infos,
+            font_size,
+            rect,
+            justify,
+            y_align,
+            line_spacing,
+            ..
+        } = *self;
+        let line_infos = line_infos.iter().cloned();
+        let ranges = line_infos.clone().map(|info| info.byte_range());
+        let line_rects =
+            text::line::rects(line_infos, font_size, rect, justify, y_align, line_spacing);
+        ranges.zip(line_rects)
+    }
+
+    /// The font with which the text is laid out.
+    pub fn font(&self) -> &'a text::Font {
+        self.font
+    }
+
+    /// The font size of the text.
+    pub fn font_size(&self) -> FontSize {
+        self.font_size
+    }
+}
+
+/// Find the highlight covering the given byte within a slice sorted by byte range start.
+fn highlight_at_byte(highlights: &[Highlight], byte: usize) -> Option<&Highlight> {
+    use std::cmp::Ordering;
+    highlights
+        .binary_search_by(|h| {
+            if h.byte_range.end <= byte {
+                Ordering::Less
+            } else if h.byte_range.start > byte {
+                Ordering::Greater
+            } else {
+                Ordering::Equal
+            }
+        })
+        .ok()
+        .map(|i| &highlights[i])
 }
 
 impl<'a> Primitives<'a> {
@@ -282,28 +561,68 @@ impl<'a> Primitives<'a> {
     ) -> Self {
         Primitives {
             crop_stack: Vec::new(),
+            clips: Vec::new(),
             depth_order: depth_order.iter(),
             graph: graph,
             theme: theme,
             fonts: fonts,
             window_rect: Rect::from_xy_dim([0.0, 0.0], window_dim),
             triangles: Vec::new(),
+            colored_triangles: Vec::new(),
+            stats: None,
+            first_requested: None,
         }
     }
 
+    /// Record the number of primitives yielded and the time spent yielding them within the given
+    /// `Stats`, along with the `FillStats` of any `mesh::Mesh` filled from them.
+    ///
+    /// This is used by `Ui::draw` when the `Ui`'s stats are enabled.
+    #[doc(hidden)]
+    pub fn record_stats(mut self, stats: &'a std::sync::Mutex<ui::Stats>) -> Self {
+        self.stats = Some(stats);
+        self
+    }
+
     /// Yield the next `Primitive` for rendering.
     pub fn next(&mut self) -> Option<Primitive> {
+        let stats = match self.stats {
+            None => return self.next_unrecorded(),
+            Some(stats) => stats,
+        };
+        let start = std::time::Instant::now();
+        if self.first_requested.is_none() {
+            self.first_requested = Some(start);
+        }
+        let primitive = self.next_unrecorded();
+        let mut stats = stats.lock().unwrap();
+        stats.draw_duration += start.elapsed();
+        if primitive.is_some() {
+            stats.primitives += 1;
+        }
+        primitive
+    }
+
+    // Yield the next `Primitive` without recording it within the stats.
+    fn next_unrecorded(&mut self) -> Option<Primitive> {
         let Primitives {
             ref mut crop_stack,
+            ref mut clips,
             ref mut depth_order,
             ref mut triangles,
+            ref mut colored_triangles,
             graph,
             theme,
             fonts,
             window_rect,
+            ..
         } = *self;
 
+        // The width of the feathered edge of shapes and lines, if anti-aliasing is enabled.
+        let anti_aliasing = theme.anti_aliasing.filter(|&feather| feather > 0.0);
+
         while let Some(widget) = next_widget(depth_order, graph, crop_stack, window_rect) {
+            use widget::primitive::path::{State as PathState, Style as PathStyle};
             use widget::primitive::point_path::{State as PointPathState, Style as PointPathStyle};
             use widget::primitive::shape::polygon::State as PolygonState;
             use widget::primitive::shape::Style as ShapeStyle;
@@ -313,8 +632,19 @@ impl<'a> Primitives<'a> {
             type TrianglesMultiColorState =
                 widget::triangles::State<Vec<widget::triangles::Triangle<(Point, color::Rgba)>>>;
 
-            let (id, scizzor, container) = widget;
+            let (id, scizzor, opacity, transform, container) = widget;
             let rect = container.rect;
+            let context = WidgetContext {
+                id: id,
+                scizzor: scizzor,
+                rect: rect,
+                opacity: opacity,
+                transform: transform,
+            };
+            // A widget is only cropped by the clips of its depth parents, not its own.
+            clips.clear();
+            let parent_crops = crop_stack.iter().filter(|&&(crop_id, _, _)| crop_id != id);
+            clips.extend(parent_crops.filter_map(|&(_, _, clip)| clip));
 
             fn state_type_id<W>() -> std::any::TypeId
             where
@@ -328,32 +658,24 @@ impl<'a> Primitives<'a> {
                 if let Some(rectangle) = container.unique_widget_state::<widget::Rectangle>() {
                     let graph::UniqueWidgetState { ref style, .. } = *rectangle;
                     let color = style.get_color(theme);
-                    match *style {
-                        ShapeStyle::Fill(_) => {
-                            let kind = PrimitiveKind::Rectangle { color: color };
-                            return Some(new_primitive(id, kind, scizzor, rect));
-                        }
+                    let kind = match *style {
+                        ShapeStyle::Fill(_) => PrimitiveKind::Rectangle { color: color },
                         ShapeStyle::Outline(ref line_style) => {
                             let (l, r, b, t) = rect.l_r_b_t();
                             let array = [[l, b], [l, t], [r, t], [r, b], [l, b]];
-                            let cap = line_style.get_cap(theme);
-                            let thickness = line_style.get_thickness(theme);
-                            let points = array.iter().cloned();
-                            let triangles =
-                                match widget::point_path::triangles(points, cap, thickness) {
-                                    None => &[],
-                                    Some(iter) => {
-                                        triangles.extend(iter);
-                                        &triangles[..]
-                                    }
-                                };
-                            let kind = PrimitiveKind::TrianglesSingleColor {
-                                color: color.to_rgb(),
-                                triangles: &triangles,
-                            };
-                            return Some(new_primitive(id, kind, scizzor, rect));
+                            let stroke = Stroke::new(array.iter().cloned(), line_style, theme);
+                            let color = color.to_rgb();
+                            triangulate(
+                                stroke,
+                                color,
+                                opacity,
+                                anti_aliasing,
+                                triangles,
+                                colored_triangles,
+                            )
                         }
-                    }
+                    };
+                    return Some(context.primitive(kind, clips));
                 }
             } else if container.type_id == std::any::TypeId::of::<TrianglesSingleColorState>() {
                 type Style = widget::triangles::SingleColor;
@@ -368,16 +690,32 @@ impl<'a> Primitives<'a> {
                         color: color,
                         triangles: &state.triangles,
                     };
-                    return Some(new_primitive(id, kind, scizzor, rect));
+                    return Some(context.primitive(kind, clips));
                 }
             } else if container.type_id == std::any::TypeId::of::<TrianglesMultiColorState>() {
                 type Style = widget::triangles::MultiColor;
                 if let Some(tris) = container.state_and_style::<TrianglesMultiColorState, Style>() {
                     let graph::UniqueWidgetState { ref state, .. } = *tris;
-                    let kind = PrimitiveKind::TrianglesMultiColor {
-                        triangles: &state.triangles,
+                    // The vertex colours can only be faded within a buffer of our own.
+                    let kind = if opacity < 1.0 {
+                        let tris = state.triangles.iter().cloned();
+                        multi_color(colored_triangles, Some(tris), opacity)
+                    } else {
+                        PrimitiveKind::TrianglesMultiColor {
+                            triangles: &state.triangles,
+                        }
                     };
-                    return Some(new_primitive(id, kind, scizzor, rect));
+                    return Some(context.primitive(kind, clips));
+                }
+            } else if container.type_id == state_type_id::<widget::DropShadow>() {
+                if let Some(shadow) = container.unique_widget_state::<widget::DropShadow>() {
+                    let graph::UniqueWidgetState { ref style, .. } = *shadow;
+                    let color = style.color(theme).to_rgb();
+                    let blur = style.blur(theme);
+                    let corner_radius = style.corner_radius(theme);
+                    let tris = widget::drop_shadow::triangles(rect, corner_radius, blur, color);
+                    let kind = multi_color(colored_triangles, tris, opacity);
+                    return Some(context.primitive(kind, clips));
                 }
             } else if container.type_id == state_type_id::<widget::Oval<widget::oval::Full>>() {
                 if let Some(oval) =
@@ -387,40 +725,30 @@ impl<'a> Primitives<'a> {
                         ref style,
                         ref state,
                     } = *oval;
-                    triangles.clear();
                     let points = widget::oval::circumference(rect, state.resolution);
-                    let color = style.get_color(theme);
-                    match *style {
-                        ShapeStyle::Fill(_) => {
-                            let triangles = {
-                                triangles.extend(points.triangles());
-                                &triangles[..]
-                            };
-                            let kind = PrimitiveKind::TrianglesSingleColor {
-                                color: color.to_rgb(),
-                                triangles: &triangles,
-                            };
-                            return Some(new_primitive(id, kind, scizzor, rect));
-                        }
-
+                    let color = style.get_color(theme).to_rgb();
+                    let kind = match *style {
+                        ShapeStyle::Fill(_) => triangulate(
+                            points,
+                            color,
+                            opacity,
+                            anti_aliasing,
+                            triangles,
+                            colored_triangles,
+                        ),
                         ShapeStyle::Outline(ref line_style) => {
-                            let cap = line_style.get_cap(theme);
-                            let thickness = line_style.get_thickness(theme);
-                            let triangles =
-                                match widget::point_path::triangles(points, cap, thickness) {
-                                    None => &[],
-                                    Some(iter) => {
-                                        triangles.extend(iter);
-                                        &triangles[..]
-                                    }
-                                };
-                            let kind = PrimitiveKind::TrianglesSingleColor {
-                                color: color.to_rgb(),
-                                triangles: &triangles,
-                            };
-                            return Some(new_primitive(id, kind, scizzor, rect));
+                            let stroke = Stroke::new(points, line_style, theme);
+                            triangulate(
+                                stroke,
+                                color,
+                                opacity,
+                                anti_aliasing,
+                                triangles,
+                                colored_triangles,
+                            )
                         }
-                    }
+                    };
+                    return Some(context.primitive(kind, clips));
                 }
 
             // Oval subsection.
@@ -432,45 +760,35 @@ impl<'a> Primitives<'a> {
                         ref style,
                         ref state,
                     } = *oval;
-                    triangles.clear();
                     let points = widget::oval::circumference(rect, state.resolution)
                         .section(state.section.radians)
                         .offset_radians(state.section.offset_radians);
-                    let color = style.get_color(theme);
-                    match *style {
-                        ShapeStyle::Fill(_) => {
-                            let triangles = {
-                                triangles.extend(points.triangles());
-                                &triangles[..]
-                            };
-                            let kind = PrimitiveKind::TrianglesSingleColor {
-                                color: color.to_rgb(),
-                                triangles: &triangles,
-                            };
-                            return Some(new_primitive(id, kind, scizzor, rect));
-                        }
-
+                    let color = style.get_color(theme).to_rgb();
+                    let kind = match *style {
+                        ShapeStyle::Fill(_) => triangulate(
+                            points,
+                            color,
+                            opacity,
+                            anti_aliasing,
+                            triangles,
+                            colored_triangles,
+                        ),
                         ShapeStyle::Outline(ref line_style) => {
                             use std::iter::once;
-                            let cap = line_style.get_cap(theme);
-                            let thickness = line_style.get_thickness(theme);
                             let middle = rect.xy();
                             let points = once(middle).chain(points).chain(once(middle));
-                            let triangles =
-                                match widget::point_path::triangles(points, cap, thickness) {
-                                    None => &[],
-                                    Some(iter) => {
-                                        triangles.extend(iter);
-                                        &triangles[..]
-                                    }
-                                };
-                            let kind = PrimitiveKind::TrianglesSingleColor {
-                                color: color.to_rgb(),
-                                triangles: &triangles,
-                            };
-                            return Some(new_primitive(id, kind, scizzor, rect));
+                            let stroke = Stroke::new(points, line_style, theme);
+                            triangulate(
+                                stroke,
+                                color,
+                                opacity,
+                                anti_aliasing,
+                                triangles,
+                                colored_triangles,
+                            )
                         }
-                    }
+                    };
+                    return Some(context.primitive(kind, clips));
                 }
             } else if container.type_id == std::any::TypeId::of::<PolygonState>() {
                 use widget::primitive::shape::Style;
@@ -479,44 +797,35 @@ impl<'a> Primitives<'a> {
                         ref state,
                         ref style,
                     } = *polygon;
-                    triangles.clear();
-
-                    let color = style.get_color(theme);
+                    let color = style.get_color(theme).to_rgb();
                     let points = state.points.iter().cloned();
-                    match *style {
+                    let kind = match *style {
                         ShapeStyle::Fill(_) => {
-                            let triangles = match widget::polygon::triangles(points) {
-                                None => &[],
-                                Some(iter) => {
-                                    triangles.extend(iter);
-                                    &triangles[..]
-                                }
-                            };
-                            let kind = PrimitiveKind::TrianglesSingleColor {
-                                color: color.to_rgb(),
-                                triangles: &triangles,
-                            };
-                            return Some(new_primitive(id, kind, scizzor, rect));
+                            let polygon = Polygon(points);
+                            triangulate(
+                                polygon,
+                                color,
+                                opacity,
+                                anti_aliasing,
+                                triangles,
+                                colored_triangles,
+                            )
                         }
-
                         ShapeStyle::Outline(ref line_style) => {
-                            let cap = line_style.get_cap(theme);
-                            let thickness = line_style.get_thickness(theme);
-                            let triangles =
-                                match widget::point_path::triangles(points, cap, thickness) {
-                                    None => &[],
-                                    Some(iter) => {
-                                        triangles.extend(iter);
-                                        &triangles[..]
-                                    }
-                                };
-                            let kind = PrimitiveKind::TrianglesSingleColor {
-                                color: color.to_rgb(),
-                                triangles: &triangles,
-                            };
-                            return Some(new_primitive(id, kind, scizzor, rect));
+                            // Close the outline by returning to the first point.
+                            let first = state.points.first().cloned();
+                            let stroke = Stroke::new(points.chain(first), line_style, theme);
+                            triangulate(
+                                stroke,
+                                color,
+                                opacity,
+                                anti_aliasing,
+                                triangles,
+                                colored_triangles,
+                            )
                         }
-                    }
+                    };
+                    return Some(context.primitive(kind, clips));
                 }
             } else if container.type_id == state_type_id::<widget::Line>() {
                 if let Some(line) = container.unique_widget_state::<widget::Line>() {
@@ -524,23 +833,18 @@ impl<'a> Primitives<'a> {
                         ref state,
                         ref style,
                     } = *line;
-                    triangles.clear();
-                    let color = style.get_color(theme);
-                    let cap = style.get_cap(theme);
-                    let thickness = style.get_thickness(theme);
+                    let color = style.get_color(theme).to_rgb();
                     let points = std::iter::once(state.start).chain(std::iter::once(state.end));
-                    let triangles = match widget::point_path::triangles(points, cap, thickness) {
-                        None => &[],
-                        Some(iter) => {
-                            triangles.extend(iter);
-                            &triangles[..]
-                        }
-                    };
-                    let kind = PrimitiveKind::TrianglesSingleColor {
-                        color: color.to_rgb(),
-                        triangles: triangles,
-                    };
-                    return Some(new_primitive(id, kind, scizzor, rect));
+                    let stroke = Stroke::new(points, style, theme);
+                    let kind = triangulate(
+                        stroke,
+                        color,
+                        opacity,
+                        anti_aliasing,
+                        triangles,
+                        colored_triangles,
+                    );
+                    return Some(context.primitive(kind, clips));
                 }
             } else if container.type_id == std::any::TypeId::of::<PointPathState>() {
                 if let Some(point_path) =
@@ -550,23 +854,55 @@ impl<'a> Primitives<'a> {
                         ref state,
                         ref style,
                     } = *point_path;
-                    triangles.clear();
-                    let color = style.get_color(theme);
-                    let cap = style.get_cap(theme);
-                    let thickness = style.get_thickness(theme);
-                    let points = state.points.iter().map(|&t| t);
-                    let triangles = match widget::point_path::triangles(points, cap, thickness) {
-                        None => &[],
-                        Some(iter) => {
-                            triangles.extend(iter);
-                            &triangles[..]
+                    let color = style.get_color(theme).to_rgb();
+                    let stroke = Stroke::new(state.points.iter().cloned(), style, theme);
+                    let kind = triangulate(
+                        stroke,
+                        color,
+                        opacity,
+                        anti_aliasing,
+                        triangles,
+                        colored_triangles,
+                    );
+                    return Some(context.primitive(kind, clips));
+                }
+            } else if container.type_id == std::any::TypeId::of::<PathState>() {
+                if let Some(path) = container.state_and_style::<PathState, PathStyle>() {
+                    let graph::UniqueWidgetState {
+                        ref state,
+                        ref style,
+                    } = *path;
+                    let color = style.get_color(theme).to_rgb();
+                    let subpaths = &state.subpaths;
+                    let kind = match style.shape {
+                        ShapeStyle::Fill(_) => {
+                            let rule = style.get_fill_rule(theme);
+                            let fill = PathFill(subpaths, rule);
+                            triangulate(
+                                fill,
+                                color,
+                                opacity,
+                                anti_aliasing,
+                                triangles,
+                                colored_triangles,
+                            )
+                        }
+                        ShapeStyle::Outline(ref line_style) => {
+                            let subpaths = subpaths
+                                .iter()
+                                .map(|s| Stroke::new(s.iter().cloned(), line_style, theme));
+                            let strokes = Strokes(subpaths);
+                            triangulate(
+                                strokes,
+                                color,
+                                opacity,
+                                anti_aliasing,
+                                triangles,
+                                colored_triangles,
+                            )
                         }
                     };
-                    let kind = PrimitiveKind::TrianglesSingleColor {
-                        color: color.to_rgb(),
-                        triangles: triangles,
-                    };
-                    return Some(new_primitive(id, kind, scizzor, rect));
+                    return Some(context.primitive(kind, clips));
                 }
             } else if container.type_id == state_type_id::<widget::Text>() {
                 if let Some(text) = container.unique_widget_state::<widget::Text>() {
@@ -600,6 +936,9 @@ impl<'a> Primitives<'a> {
                         justify: justify,
                         y_align: y_align,
                         line_spacing: line_spacing,
+                        highlights: &state.highlights,
+                        highlight_fonts: Some(fonts),
+                        opacity: 1.0,
                     };
 
                     let kind = PrimitiveKind::Text {
@@ -607,7 +946,7 @@ impl<'a> Primitives<'a> {
                         text: text,
                         font_id: font_id,
                     };
-                    return Some(new_primitive(id, kind, scizzor, rect));
+                    return Some(context.primitive(kind, clips));
                 }
             } else if container.type_id == state_type_id::<widget::Image>() {
                 use widget::primitive::image::{State, Style};
@@ -621,14 +960,24 @@ impl<'a> Primitives<'a> {
                         color: color,
                         image_id: state.image_id,
                         source_rect: state.src_rect,
+                        nine_slice: state.nine_slice,
+                        fit: state.fit,
                     };
-                    return Some(new_primitive(id, kind, scizzor, rect));
+                    return Some(context.primitive(kind, clips));
+                }
+            } else if container.type_id == state_type_id::<widget::CustomDraw<()>>() {
+                use widget::custom_draw::{State, Style};
+                if let Some(custom) = container.state_and_style::<State, Style>() {
+                    let kind = PrimitiveKind::Custom {
+                        data: &custom.state.data,
+                    };
+                    return Some(context.primitive(kind, clips));
                 }
 
             // Return an `Other` variant for all non-primitive widgets.
             } else {
                 let kind = PrimitiveKind::Other(container);
-                return Some(new_primitive(id, kind, scizzor, rect));
+                return Some(context.primitive(kind, clips));
             }
         }
 
@@ -644,19 +993,34 @@ impl<'a> Primitives<'a> {
         let mut primitive_triangles_single_color = Vec::new();
         let mut primitive_line_infos = Vec::new();
         let mut texts_string = String::new();
+        let mut primitive_highlights = Vec::new();
+        let mut primitive_clips: Vec<Clip> = Vec::new();
+        let mut clips_range = 0..0;
 
         while let Some(Primitive {
             id,
             rect,
             scizzor,
+            clips,
             kind,
+            transform,
+            opacity,
         }) = self.next()
         {
+            // Consecutive primitives within the same crop share their clips.
+            if &primitive_clips[clips_range.clone()] != clips {
+                let start = primitive_clips.len();
+                primitive_clips.extend_from_slice(clips);
+                clips_range = start..primitive_clips.len();
+            }
             let new = |kind| OwnedPrimitive {
                 id: id,
                 rect: rect,
                 scizzor: scizzor,
+                clips_range: clips_range.clone(),
                 kind: kind,
+                transform: transform,
+                opacity: opacity,
             };
 
             match kind {
@@ -690,11 +1054,15 @@ impl<'a> Primitives<'a> {
                     image_id,
                     color,
                     source_rect,
+                    nine_slice,
+                    fit,
                 } => {
                     let kind = OwnedPrimitiveKind::Image {
                         image_id: image_id,
                         color: color,
                         source_rect: source_rect,
+                        nine_slice: nine_slice,
+                        fit: fit,
                     };
                     primitives.push(new(kind));
                 }
@@ -714,6 +1082,8 @@ impl<'a> Primitives<'a> {
                         justify,
                         y_align,
                         line_spacing,
+                        highlights,
+                        opacity,
                         ..
                     } = text;
 
@@ -727,6 +1097,11 @@ impl<'a> Primitives<'a> {
                     primitive_line_infos.extend(line_infos.iter().cloned());
                     let end_line_info_idx = primitive_line_infos.len();
 
+                    // Pack the `highlights`. Their fonts are resolved by ID when walked.
+                    let start_highlight_idx = primitive_highlights.len();
+                    primitive_highlights.extend(highlights.iter().cloned());
+                    let end_highlight_idx = primitive_highlights.len();
+
                     let owned_text = OwnedText {
                         str_byte_range: start_str_byte..end_str_byte,
                         line_infos_range: start_line_info_idx..end_line_info_idx,
@@ -737,6 +1112,8 @@ impl<'a> Primitives<'a> {
                         justify: justify,
                         y_align: y_align,
                         line_spacing: line_spacing,
+                        highlights_range: start_highlight_idx..end_highlight_idx,
+                        opacity: opacity,
                     };
 
                     let kind = OwnedPrimitiveKind::Text {
@@ -747,6 +1124,11 @@ impl<'a> Primitives<'a> {
                     primitives.push(new(kind));
                 }
 
+                PrimitiveKind::Custom { data } => {
+                    let kind = OwnedPrimitiveKind::Custom { data: data.clone() };
+                    primitives.push(new(kind));
+                }
+
                 // TODO: Not sure how we should handle this yet.
                 PrimitiveKind::Other(_) => (),
             }
@@ -758,19 +1140,36 @@ impl<'a> Primitives<'a> {
             triangles_multi_color: primitive_triangles_multi_color,
             line_infos: primitive_line_infos,
             texts_string: texts_string,
+            highlights: primitive_highlights,
+            clips: primitive_clips,
         }
     }
 }
 
 impl OwnedPrimitives {
     /// Produce an iterator-like type for yielding `Primitive`s.
+    ///
+    /// The fonts of text highlights are not known, so highlighted glyphs are produced by the font
+    /// of their `Text`. Use `walk_with_fonts` to draw them with their highlight's font.
     pub fn walk(&self) -> WalkOwnedPrimitives {
+        self.walk_inner(None)
+    }
+
+    /// Produce an iterator-like type for yielding `Primitive`s, resolving the fonts of text
+    /// highlights within the given `font::Map`.
+    pub fn walk_with_fonts<'a>(&'a self, fonts: &'a text::font::Map) -> WalkOwnedPrimitives<'a> {
+        self.walk_inner(Some(fonts))
+    }
+
+    fn walk_inner<'a>(&'a self, fonts: Option<&'a text::font::Map>) -> WalkOwnedPrimitives<'a> {
         let OwnedPrimitives {
             ref primitives,
             ref triangles_single_color,
             ref triangles_multi_color,
             ref line_infos,
             ref texts_string,
+            ref highlights,
+            ref clips,
         } = *self;
         WalkOwnedPrimitives {
             primitives: primitives.iter(),
@@ -778,6 +1177,9 @@ impl OwnedPrimitives {
             triangles_multi_color: triangles_multi_color,
             line_infos: line_infos,
             texts_str: texts_string,
+            highlights: highlights,
+            fonts: fonts,
+            clips: clips,
         }
     }
 }
@@ -791,6 +1193,9 @@ impl<'a> WalkOwnedPrimitives<'a> {
             triangles_multi_color,
             line_infos,
             texts_str,
+            highlights,
+            fonts,
+            clips,
         } = *self;
 
         primitives.next().map(
@@ -798,13 +1203,19 @@ impl<'a> WalkOwnedPrimitives<'a> {
                       id,
                       rect,
                       scizzor,
+                      ref clips_range,
                       ref kind,
+                      transform,
+                      opacity,
                   }| {
                 let new = |kind| Primitive {
                     id: id,
                     rect: rect,
                     scizzor: scizzor,
+                    clips: &clips[clips_range.clone()],
                     kind: kind,
+                    transform: transform,
+                    opacity: opacity,
                 };
 
                 match *kind {
@@ -846,10 +1257,13 @@ impl<'a> WalkOwnedPrimitives<'a> {
                             justify,
                             y_align,
                             line_spacing,
+                            ref highlights_range,
+                            opacity,
                         } = *text;
 
                         let text_str = &texts_str[str_byte_range.clone()];
                         let line_infos = &line_infos[line_infos_range.clone()];
+                        let highlights = &highlights[highlights_range.clone()];
 
                         let text = Text {
                             window_dim: window_dim,
@@ -861,6 +1275,9 @@ impl<'a> WalkOwnedPrimitives<'a> {
                             justify: justify,
                             y_align: y_align,
                             line_spacing: line_spacing,
+                            highlights: highlights,
+                            highlight_fonts: fonts,
+                            opacity: opacity,
                         };
 
                         let kind = PrimitiveKind::Text {
@@ -875,38 +1292,343 @@ impl<'a> WalkOwnedPrimitives<'a> {
                         image_id,
                         color,
                         source_rect,
+                        nine_slice,
+                        fit,
                     } => {
                         let kind = PrimitiveKind::Image {
                             image_id: image_id,
                             color: color,
                             source_rect: source_rect,
+                            nine_slice: nine_slice,
+                            fit: fit,
                         };
                         new(kind)
                     }
+
+                    OwnedPrimitiveKind::Custom { ref data } => {
+                        let kind = PrimitiveKind::Custom { data: data };
+                        new(kind)
+                    }
                 }
             },
         )
     }
 }
 
-/// Simplify the constructor for a `Primitive`.
-fn new_primitive(id: widget::Id, kind: PrimitiveKind, scizzor: Rect, rect: Rect) -> Primitive {
-    Primitive {
-        id: id,
-        kind: kind,
-        scizzor: scizzor,
-        rect: rect,
+// The properties shared by all primitives produced for a single widget.
+#[derive(Copy, Clone)]
+struct WidgetContext {
+    id: widget::Id,
+    scizzor: Rect,
+    rect: Rect,
+    opacity: f32,
+    transform: Option<Transform>,
+}
+
+impl WidgetContext {
+    // Produce the widget's `Primitive` of the given `kind`, cropped by the given `clips`.
+    //
+    // The colour of the primitive is faded by the widget's `opacity`. `TrianglesMultiColor` kinds
+    // are expected to have already been faded via `multi_color`.
+    //
+    // The `clips` are given separately as they borrow the buffer that `Primitives` refills for
+    // each widget.
+    fn primitive<'a>(self, kind: PrimitiveKind<'a>, clips: &'a [Clip]) -> Primitive<'a> {
+        let WidgetContext {
+            id,
+            scizzor,
+            rect,
+            opacity,
+            transform,
+        } = self;
+        let kind = match kind {
+            _ if opacity >= 1.0 => kind,
+            PrimitiveKind::Rectangle { color } => PrimitiveKind::Rectangle {
+                color: color.alpha(opacity),
+            },
+            PrimitiveKind::TrianglesSingleColor { color, triangles } => {
+                PrimitiveKind::TrianglesSingleColor {
+                    color: fade(color, opacity),
+                    triangles: triangles,
+                }
+            }
+            PrimitiveKind::Image {
+                image_id,
+                color,
+                source_rect,
+                nine_slice,
+                fit,
+            } => PrimitiveKind::Image {
+                image_id: image_id,
+                color: Some(color.unwrap_or(color::WHITE).alpha(opacity)),
+                source_rect: source_rect,
+                nine_slice: nine_slice,
+                fit: fit,
+            },
+            PrimitiveKind::Text {
+                color,
+                text,
+                font_id,
+            } => PrimitiveKind::Text {
+                color: color.alpha(opacity),
+                text: Text {
+                    opacity: text.opacity * opacity,
+                    ..text
+                },
+                font_id: font_id,
+            },
+            kind => kind,
+        };
+        Primitive {
+            id: id,
+            kind: kind,
+            scizzor: scizzor,
+            clips: clips,
+            rect: rect,
+            transform: transform,
+            opacity: opacity,
+        }
+    }
+}
+
+/// Fill the given buffer with the given multi-coloured triangles faded by the given `opacity`,
+/// producing a `TrianglesMultiColor` kind that borrows them.
+fn multi_color<'a, I>(
+    buffer: &'a mut Vec<Triangle<ColoredPoint>>,
+    triangles: Option<I>,
+    opacity: f32,
+) -> PrimitiveKind<'a>
+where
+    I: Iterator<Item = Triangle<ColoredPoint>>,
+{
+    buffer.clear();
+    buffer.extend(triangles.into_iter().flat_map(|tris| tris));
+    fade_triangles(buffer, opacity);
+    PrimitiveKind::TrianglesMultiColor {
+        triangles: &buffer[..],
     }
 }
 
-/// Retrieves the next visible widget from the `depth_order`, updating the `crop_stack` as
-/// necessary.
+/// Multiply the alpha of every vertex of the given triangles by the given `opacity`.
+fn fade_triangles(triangles: &mut [Triangle<ColoredPoint>], opacity: f32) {
+    if opacity < 1.0 {
+        for triangle in triangles.iter_mut() {
+            for &mut (_, ref mut color) in triangle.0.iter_mut() {
+                *color = fade(*color, opacity);
+            }
+        }
+    }
+}
+
+/// Shapes and lines that may be triangulated either in a single colour or, when anti-aliasing is
+/// enabled, along with a feathered edge.
+trait Triangulate {
+    /// Extend the buffer with the triangles of the shape.
+    fn triangles(self, buffer: &mut Vec<Triangle<Point>>);
+    /// Extend the buffer with the triangles of the shape in the given colour, surrounded by a
+    /// fringe of the given width within which the alpha falls off to zero.
+    fn triangles_anti_aliased(
+        self,
+        feather: Scalar,
+        color: color::Rgba,
+        buffer: &mut Vec<Triangle<ColoredPoint>>,
+    );
+}
+
+/// A line along some points, triangulated via the `point_path` module.
+struct Stroke<I> {
+    points: I,
+    cap: widget::line::Cap,
+    join: widget::line::Join,
+    thickness: Scalar,
+}
+
+/// A polygon filled via the `polygon` module.
+struct Polygon<I>(I);
+
+/// The subpaths of a flattened `Path` filled according to the fill rule.
+struct PathFill<'a>(&'a [Vec<Point>], widget::path::FillRule);
+
+/// A line along each of the subpaths of a flattened `Path`.
+struct Strokes<I>(I);
+
+/// Triangulate the given shape, producing a `TrianglesSingleColor` kind or, when anti-aliasing is
+/// enabled, a `TrianglesMultiColor` kind with a feathered edge of the given width.
+fn triangulate<'a, S>(
+    shape: S,
+    color: color::Rgba,
+    opacity: f32,
+    anti_aliasing: Option<Scalar>,
+    triangles: &'a mut Vec<Triangle<Point>>,
+    colored_triangles: &'a mut Vec<Triangle<ColoredPoint>>,
+) -> PrimitiveKind<'a>
+where
+    S: Triangulate,
+{
+    match anti_aliasing {
+        Some(feather) => {
+            colored_triangles.clear();
+            shape.triangles_anti_aliased(feather, color, colored_triangles);
+            fade_triangles(colored_triangles, opacity);
+            PrimitiveKind::TrianglesMultiColor {
+                triangles: &colored_triangles[..],
+            }
+        }
+        None => {
+            triangles.clear();
+            shape.triangles(triangles);
+            PrimitiveKind::TrianglesSingleColor {
+                color: color,
+                triangles: &triangles[..],
+            }
+        }
+    }
+}
+
+impl<I> Stroke<I> {
+    /// A line along the given points with the cap, join and thickness of the given style.
+    fn new(points: I, style: &widget::line::Style, theme: &Theme) -> Self {
+        Stroke {
+            points: points,
+            cap: style.get_cap(theme),
+            join: style.get_join(theme),
+            thickness: style.get_thickness(theme),
+        }
+    }
+}
+
+impl<I> Triangulate for Stroke<I>
+where
+    I: Iterator<Item = Point>,
+{
+    fn triangles(self, buffer: &mut Vec<Triangle<Point>>) {
+        let Stroke {
+            points,
+            cap,
+            join,
+            thickness,
+        } = self;
+        let tris = widget::point_path::triangles_with_join(points, cap, join, thickness);
+        buffer.extend(tris.into_iter().flat_map(|tris| tris));
+    }
+
+    fn triangles_anti_aliased(
+        self,
+        feather: Scalar,
+        color: color::Rgba,
+        buffer: &mut Vec<Triangle<ColoredPoint>>,
+    ) {
+        let Stroke {
+            points,
+            cap,
+            join,
+            thickness,
+        } = self;
+        let tris = widget::point_path::triangles_anti_aliased(
+            points, cap, join, thickness, feather, color,
+        );
+        buffer.extend(tris.into_iter().flat_map(|tris| tris));
+    }
+}
+
+impl Triangulate for widget::oval::Circumference {
+    fn triangles(self, buffer: &mut Vec<Triangle<Point>>) {
+        buffer.extend(widget::oval::Circumference::triangles(self));
+    }
+
+    fn triangles_anti_aliased(
+        self,
+        feather: Scalar,
+        color: color::Rgba,
+        buffer: &mut Vec<Triangle<ColoredPoint>>,
+    ) {
+        buffer.extend(widget::oval::Circumference::triangles_anti_aliased(
+            self, feather, color,
+        ));
+    }
+}
+
+impl<I> Triangulate for Polygon<I>
+where
+    I: Iterator<Item = Point>,
+{
+    fn triangles(self, buffer: &mut Vec<Triangle<Point>>) {
+        let tris = widget::polygon::triangles(self.0);
+        buffer.extend(tris.into_iter().flat_map(|tris| tris));
+    }
+
+    fn triangles_anti_aliased(
+        self,
+        feather: Scalar,
+        color: color::Rgba,
+        buffer: &mut Vec<Triangle<ColoredPoint>>,
+    ) {
+        let tris = widget::polygon::triangles_anti_aliased(self.0, feather, color);
+        buffer.extend(tris.into_iter().flat_map(|tris| tris));
+    }
+}
+
+impl<'a> Triangulate for PathFill<'a> {
+    fn triangles(self, buffer: &mut Vec<Triangle<Point>>) {
+        let PathFill(subpaths, rule) = self;
+        buffer.extend(widget::path::fill_triangles(subpaths, rule));
+    }
+
+    fn triangles_anti_aliased(
+        self,
+        feather: Scalar,
+        color: color::Rgba,
+        buffer: &mut Vec<Triangle<ColoredPoint>>,
+    ) {
+        let PathFill(subpaths, rule) = self;
+        buffer.extend(widget::path::fill_triangles_anti_aliased(
+            subpaths, rule, feather, color,
+        ));
+    }
+}
+
+impl<I, S> Triangulate for Strokes<I>
+where
+    I: Iterator<Item = S>,
+    S: Triangulate,
+{
+    fn triangles(self, buffer: &mut Vec<Triangle<Point>>) {
+        for stroke in self.0 {
+            stroke.triangles(buffer);
+        }
+    }
+
+    fn triangles_anti_aliased(
+        self,
+        feather: Scalar,
+        color: color::Rgba,
+        buffer: &mut Vec<Triangle<ColoredPoint>>,
+    ) {
+        for stroke in self.0 {
+            stroke.triangles_anti_aliased(feather, color, buffer);
+        }
+    }
+}
+
+/// Multiply the alpha of the given colour by the given `opacity`.
+fn fade(color::Rgba(r, g, b, a): color::Rgba, opacity: f32) -> color::Rgba {
+    color::Rgba(r, g, b, a * opacity)
+}
+
+/// Retrieves the next visible widget from the `depth_order` along with its opacity and transform,
+/// updating the `crop_stack` as necessary.
 fn next_widget<'a>(
     depth_order: &mut std::slice::Iter<widget::Id>,
     graph: &'a Graph,
-    crop_stack: &mut Vec<(widget::Id, Rect)>,
+    crop_stack: &mut Vec<(widget::Id, Rect, Option<Clip>)>,
     window_rect: Rect,
-) -> Option<(widget::Id, Rect, &'a graph::Container)> {
+) -> Option<(
+    widget::Id,
+    Rect,
+    f32,
+    Option<Transform>,
+    &'a graph::Container,
+)> {
     while let Some(&id) = depth_order.next() {
         let container = match graph.widget(id) {
             Some(container) => container,
@@ -916,7 +1638,7 @@ fn next_widget<'a>(
         // If we're currently using a cropped context and the current `crop_parent_idx` is
         // *not* a depth-wise parent of the widget at the current `idx`, we should pop that
         // cropped context from the stack as we are done with it.
-        while let Some(&(crop_parent_idx, _)) = crop_stack.last() {
+        while let Some(&(crop_parent_idx, _, _)) = crop_stack.last() {
             if graph.does_recursive_depth_edge_exist(crop_parent_idx, id) {
                 break;
             } else {
@@ -927,28 +1649,51 @@ fn next_widget<'a>(
         // Check the stack for the current Context.
         let scizzor = crop_stack
             .last()
-            .map(|&(_, scizzor)| scizzor)
+            .map(|&(_, scizzor, _)| scizzor)
             .unwrap_or(window_rect);
 
+        // Scizzor rects are in window space, so we use the bounds of transformed widgets.
+        let transform = graph::algo::transform_of_widget(graph, id);
+        let window_space = |rect: Rect| match transform {
+            Some(ref transform) => transform.bounding_rect(rect),
+            None => rect,
+        };
+
         // If the current widget should crop its children, we need to add a rect for it to
-        // the top of the crop stack.
+        // the top of the crop stack, along with a `Clip` if the rect alone cannot describe the
+        // crop.
         if container.crop_kids {
-            let scizzor_rect = container
-                .kid_area
-                .rect
+            let kid_area = container.kid_area.rect;
+            let scizzor_rect = window_space(kid_area)
                 .overlap(scizzor)
                 .unwrap_or_else(|| Rect::from_xy_dim([0.0, 0.0], [0.0, 0.0]));
-            crop_stack.push((id, scizzor_rect));
+            let is_axis_aligned = |t: &Transform| t.x_axis[1] == 0.0 && t.y_axis[0] == 0.0;
+            let clip = match container.crop_shape {
+                widget::CropShape::Rectangle if transform.iter().all(is_axis_aligned) => None,
+                shape => Some(Clip {
+                    id: id,
+                    shape: shape,
+                    rect: kid_area,
+                    transform: transform,
+                }),
+            };
+            crop_stack.push((id, scizzor_rect, clip));
         }
 
         // We only want to return primitives that are actually visible.
-        let is_visible = container.rect.overlap(scizzor).is_some()
+        let is_visible = window_space(container.rect).overlap(scizzor).is_some()
             && graph::algo::cropped_area_of_widget(graph, id).is_some();
         if !is_visible {
             continue;
         }
 
-        return Some((id, scizzor, container));
+        // Widgets within a fully transparent subtree are also invisible.
+        let opacity = graph::algo::opacity_of_widget(graph, id);
+        if opacity <= 0.0 {
+            continue;
+        }
+
+        return Some((id, scizzor, opacity, transform, container));
     }
 
     None
@@ -0,0 +1,620 @@
+//! Writing sequences of `render::Primitive`s to an SVG document.
+//!
+//! This allows for capturing the output of a `Ui` without a GPU, e.g. for documentation
+//! screenshots or for comparing the output of a UI within tests.
+//!
+//! ```ignore
+//! let svg = conrod_core::svg::Svg::new([ui.win_w, ui.win_h])
+//!     .font_family(font_id, "Noto Sans")
+//!     .to_string(ui.draw());
+//! ```
+
+use crate::position::Transform;
+use crate::render::{self, PrimitiveKind};
+use crate::text;
+use crate::widget::triangles::Triangle;
+use crate::{color, image, widget};
+use crate::{Color, Dimensions, Point, Rect, Scalar};
+use fnv::FnvHashMap;
+use std::fmt::{self, Write};
+
+/// How the glyphs of `Text` primitives are written to the document.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum TextMode {
+    /// Each line is written as a `<text>` element, referring to its font by family name.
+    ///
+    /// The text remains selectable and the document small, though its appearance depends upon
+    /// the fonts available to the viewer.
+    Text,
+    /// Each glyph is written as a `<path>` tracing its outline.
+    ///
+    /// The text appears exactly as it would within the `Ui`, regardless of the available fonts.
+    Outlines,
+}
+
+/// The source of an image referred to by the document.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ImageSource {
+    /// The location of the image, e.g. a file path, URL or base64 encoded `data:` URI.
+    pub href: String,
+    /// The dimensions of the image in pixels, used to crop the image to its `source_rect`.
+    pub dimensions: [u32; 2],
+}
+
+/// Writes the `render::Primitive`s yielded by a `render::PrimitiveWalker` to an SVG document.
+///
+/// The document uses conrod's DPI-agnostic coordinates, with its origin at the top-left of the
+/// window.
+#[derive(Clone, Debug)]
+pub struct Svg {
+    dimensions: Dimensions,
+    text_mode: TextMode,
+    font_families: FnvHashMap<text::font::Id, String>,
+    images: FnvHashMap<image::Id, ImageSource>,
+}
+
+impl Svg {
+    /// Begin building an SVG document for a window of the given dimensions.
+    pub fn new(dimensions: Dimensions) -> Self {
+        Svg {
+            dimensions,
+            text_mode: TextMode::Text,
+            font_families: FnvHashMap::default(),
+            images: FnvHashMap::default(),
+        }
+    }
+
+    /// Specify how the glyphs of `Text` primitives are written.
+    ///
+    /// By default, `TextMode::Text` is used.
+    pub fn text_mode(mut self, mode: TextMode) -> Self {
+        self.text_mode = mode;
+        self
+    }
+
+    /// The family name by which the font with the given ID is referred to in `TextMode::Text`.
+    ///
+    /// Text whose font has no family name is written with the viewer's default font.
+    pub fn font_family<S>(mut self, font_id: text::font::Id, family: S) -> Self
+    where
+        S: Into<String>,
+    {
+        self.font_families.insert(font_id, family.into());
+        self
+    }
+
+    /// The source of the image with the given ID.
+    ///
+    /// `Image` primitives whose image has no source are omitted from the document.
+    pub fn image(mut self, image_id: image::Id, source: ImageSource) -> Self {
+        self.images.insert(image_id, source);
+        self
+    }
+
+    /// Write the SVG document describing the given primitives to a `String`.
+    pub fn to_string<P>(&self, primitives: P) -> String
+    where
+        P: render::PrimitiveWalker,
+    {
+        let mut string = String::new();
+        self.write(primitives, &mut string)
+            .expect("writing to a `String` should never fail");
+        string
+    }
+
+    /// Write the SVG document describing the given primitives to the given output.
+    ///
+    /// Each `scizzor` that crops a primitive to less than the whole window is written as a
+    /// `<clipPath>` applied to a group containing the primitive, as are the exact shapes of any
+    /// `clips`.
+    pub fn write<P, W>(&self, mut primitives: P, out: &mut W) -> fmt::Result
+    where
+        P: render::PrimitiveWalker,
+        W: Write,
+    {
+        let [w, h] = self.dimensions;
+        writeln!(
+            out,
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" \
+             xmlns:xlink=\"http://www.w3.org/1999/xlink\" \
+             width=\"{w}\" height=\"{h}\" viewBox=\"0 0 {w} {h}\">",
+            w = Num(w),
+            h = Num(h),
+        )?;
+
+        let window = Rect::from_xy_dim([0.0, 0.0], self.dimensions);
+        let mut current_crop = (None, vec![]);
+        let mut is_cropped = false;
+        let mut next_id = 0;
+        while let Some(primitive) = primitives.next_primitive() {
+            let render::Primitive {
+                kind,
+                scizzor,
+                clips,
+                rect,
+                transform,
+                ..
+            } = primitive;
+
+            // Group consecutive primitives that share the same crop.
+            let scizzor = if covers(scizzor, window) {
+                None
+            } else {
+                Some(scizzor)
+            };
+            if scizzor != current_crop.0 || clips != &current_crop.1[..] {
+                if is_cropped {
+                    writeln!(out, "</g>")?;
+                }
+
+                // Each clip path is itself clipped by the previous, cropping the group to the
+                // intersection of the scizzor and all clips.
+                let mut clip_id = None;
+                if let Some(scizzor) = scizzor {
+                    next_id += 1;
+                    writeln!(out, "<clipPath id=\"clip{}\">", next_id)?;
+                    writeln!(out, "<rect {}/>", self.rect_attrs(scizzor))?;
+                    writeln!(out, "</clipPath>")?;
+                    clip_id = Some(next_id);
+                }
+                for clip in clips {
+                    next_id += 1;
+                    write!(out, "<clipPath id=\"clip{}\"", next_id)?;
+                    if let Some(clip_id) = clip_id {
+                        write!(out, " clip-path=\"url(#clip{})\"", clip_id)?;
+                    }
+                    writeln!(out, ">")?;
+                    self.write_clip_shape(out, clip)?;
+                    writeln!(out, "</clipPath>")?;
+                    clip_id = Some(next_id);
+                }
+                if let Some(clip_id) = clip_id {
+                    writeln!(out, "<g clip-path=\"url(#clip{})\">", clip_id)?;
+                }
+                is_cropped = clip_id.is_some();
+                current_crop = (scizzor, clips.to_vec());
+            }
+
+            // Transformed primitives are written within a group describing the transform.
+            let transform = match kind {
+                PrimitiveKind::Custom { .. } | PrimitiveKind::Other(_) => None,
+                _ => transform,
+            };
+            if let Some(transform) = transform {
+                writeln!(out, "<g transform=\"{}\">", self.matrix(transform))?;
+            }
+
+            match kind {
+                PrimitiveKind::Rectangle { color } => {
+                    let attrs = self.rect_attrs(rect);
+                    writeln!(out, "<rect {}{}/>", attrs, Fill(color.to_rgb()))?;
+                }
+
+                PrimitiveKind::TrianglesSingleColor { color, triangles } => {
+                    let points = triangles.iter().map(|t| t.0);
+                    self.write_triangles(out, points, color)?;
+                }
+
+                // SVG has no notion of per-vertex colour, so each triangle is filled with the
+                // average colour of its vertices.
+                PrimitiveKind::TrianglesMultiColor { triangles } => {
+                    let mut start = 0;
+                    while start < triangles.len() {
+                        let color = average_color(&triangles[start]);
+                        let len = triangles[start..]
+                            .iter()
+                            .take_while(|t| average_color(t) == color)
+                            .count();
+                        let run = &triangles[start..start + len];
+                        let points = run.iter().map(|&Triangle([a, b, c])| [a.0, b.0, c.0]);
+                        self.write_triangles(out, points, color)?;
+                        start += len;
+                    }
+                }
+
+                PrimitiveKind::Image {
+                    image_id,
+                    color,
+                    source_rect,
+                    nine_slice,
+                    fit,
+                } => {
+                    if let Some(source) = self.images.get(&image_id) {
+                        match (nine_slice, fit) {
+                            (None, widget::image::Fit::Stretch) => {
+                                next_id += 1;
+                                self.write_image(out, source, rect, color, source_rect, next_id)?;
+                            }
+                            _ => {
+                                let [w, h] = source.dimensions;
+                                let image_dim = [w as Scalar, h as Scalar];
+                                let image_rect = Rect::from_corners([0.0, 0.0], image_dim);
+                                let source_rect = source_rect.unwrap_or(image_rect);
+                                let quads =
+                                    widget::image::quads(rect, source_rect, fit, nine_slice);
+                                for (rect, src_rect) in quads {
+                                    next_id += 1;
+                                    let src_rect = Some(src_rect);
+                                    self.write_image(out, source, rect, color, src_rect, next_id)?;
+                                }
+                            }
+                        }
+                    }
+                }
+
+                PrimitiveKind::Text {
+                    color,
+                    text,
+                    font_id,
+                } => match self.text_mode {
+                    TextMode::Text => self.write_text(out, text, color, font_id)?,
+                    TextMode::Outlines => write_glyph_outlines(out, text, color)?,
+                },
+
+                // Custom widgets are drawn by the user's own rendering code, which has no
+                // equivalent within an SVG document.
+                PrimitiveKind::Custom { .. } | PrimitiveKind::Other(_) => (),
+            }
+
+            if transform.is_some() {
+                writeln!(out, "</g>")?;
+            }
+        }
+
+        if is_cropped {
+            writeln!(out, "</g>")?;
+        }
+        writeln!(out, "</svg>")
+    }
+
+    // Convert the given point from conrod's coordinates to those of the document.
+    fn point(&self, [x, y]: Point) -> Point {
+        [x + self.dimensions[0] / 2.0, self.dimensions[1] / 2.0 - y]
+    }
+
+    // The `matrix` function describing the given transform within the coordinates of the document.
+    fn matrix(&self, transform: Transform) -> String {
+        let [w, h] = self.dimensions;
+        let to_document = Transform {
+            x_axis: [1.0, 0.0],
+            y_axis: [0.0, -1.0],
+            translation: [w / 2.0, h / 2.0],
+        };
+        let from_document = to_document.inverse().unwrap_or_default();
+        let Transform {
+            x_axis: [a, b],
+            y_axis: [c, d],
+            translation: [e, f],
+        } = from_document.then(transform).then(to_document);
+        format!(
+            "matrix({} {} {} {} {} {})",
+            Num(a),
+            Num(b),
+            Num(c),
+            Num(d),
+            Num(e),
+            Num(f)
+        )
+    }
+
+    // The `x`, `y`, `width` and `height` attributes describing the given rect.
+    fn rect_attrs(&self, rect: Rect) -> String {
+        let [x, y] = self.point([rect.left(), rect.top()]);
+        format!(
+            "x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\"",
+            Num(x),
+            Num(y),
+            Num(rect.w()),
+            Num(rect.h())
+        )
+    }
+
+    // Write the element describing the shape of the given clip.
+    fn write_clip_shape<W>(&self, out: &mut W, clip: &render::Clip) -> fmt::Result
+    where
+        W: Write,
+    {
+        let transform = match clip.transform {
+            Some(transform) => format!(" transform=\"{}\"", self.matrix(transform)),
+            None => String::new(),
+        };
+        let rect = clip.rect;
+        match clip.shape {
+            widget::CropShape::Rectangle => {
+                writeln!(out, "<rect {}{}/>", self.rect_attrs(rect), transform)
+            }
+            widget::CropShape::RoundedRectangle { radius } => {
+                let radius = radius.max(0.0).min(rect.w().min(rect.h()) / 2.0);
+                writeln!(
+                    out,
+                    "<rect {} rx=\"{r}\" ry=\"{r}\"{}/>",
+                    self.rect_attrs(rect),
+                    transform,
+                    r = Num(radius)
+                )
+            }
+            widget::CropShape::Ellipse => {
+                let [cx, cy] = self.point(rect.xy());
+                writeln!(
+                    out,
+                    "<ellipse cx=\"{}\" cy=\"{}\" rx=\"{}\" ry=\"{}\"{}/>",
+                    Num(cx),
+                    Num(cy),
+                    Num(rect.w() / 2.0),
+                    Num(rect.h() / 2.0),
+                    transform
+                )
+            }
+        }
+    }
+
+    fn write_triangles<W, I>(&self, out: &mut W, triangles: I, color: color::Rgba) -> fmt::Result
+    where
+        W: Write,
+        I: IntoIterator<Item = [Point; 3]>,
+    {
+        if color.3 <= 0.0 {
+            return Ok(());
+        }
+        let mut data = String::new();
+        for triangle in triangles {
+            let [a, b, c] = triangle;
+            let ([ax, ay], [bx, by], [cx, cy]) = (self.point(a), self.point(b), self.point(c));
+            write!(
+                data,
+                "M{} {}L{} {}L{} {}Z",
+                Num(ax),
+                Num(ay),
+                Num(bx),
+                Num(by),
+                Num(cx),
+                Num(cy)
+            )?;
+        }
+        if data.is_empty() {
+            return Ok(());
+        }
+        writeln!(out, "<path d=\"{}\"{}/>", data, Fill(color))
+    }
+
+    fn write_image<W>(
+        &self,
+        out: &mut W,
+        source: &ImageSource,
+        rect: Rect,
+        color: Option<Color>,
+        source_rect: Option<Rect>,
+        id: usize,
+    ) -> fmt::Result
+    where
+        W: Write,
+    {
+        // Tint the image by multiplying each channel by that of the colour.
+        let filter = match color {
+            None => String::new(),
+            Some(color) => {
+                let color::Rgba(r, g, b, a) = color.to_rgb();
+                writeln!(
+                    out,
+                    "<filter id=\"tint{}\"><feColorMatrix type=\"matrix\" \
+                     values=\"{} 0 0 0 0 0 {} 0 0 0 0 0 {} 0 0 0 0 0 {} 0\"/></filter>",
+                    id, r, g, b, a
+                )?;
+                format!(" filter=\"url(#tint{})\"", id)
+            }
+        };
+        let href = Escaped(&source.href);
+        let [img_w, img_h] = [
+            source.dimensions[0] as Scalar,
+            source.dimensions[1] as Scalar,
+        ];
+        match source_rect {
+            None => writeln!(
+                out,
+                "<image {} preserveAspectRatio=\"none\" href=\"{}\" xlink:href=\"{}\"{}/>",
+                self.rect_attrs(rect),
+                href,
+                href,
+                filter
+            ),
+            // Source rects are given in pixels from the bottom-left of the image.
+            Some(src) => writeln!(
+                out,
+                "<svg {} viewBox=\"{} {} {} {}\" preserveAspectRatio=\"none\">\
+                 <image width=\"{}\" height=\"{}\" href=\"{}\" xlink:href=\"{}\"{}/></svg>",
+                self.rect_attrs(rect),
+                Num(src.left()),
+                Num(img_h - src.top()),
+                Num(src.w()),
+                Num(src.h()),
+                Num(img_w),
+                Num(img_h),
+                href,
+                href,
+                filter
+            ),
+        }
+    }
+
+    fn write_text<W>(
+        &self,
+        out: &mut W,
+        text: render::Text,
+        color: Color,
+        font_id: text::font::Id,
+    ) -> fmt::Result
+    where
+        W: Write,
+    {
+        // `rusttype` scales fonts by the height from descent to ascent, rather than by the em.
+        let font = text.font();
+        let v_metrics = font.v_metrics_unscaled();
+        let em_per_height = font.units_per_em() as f32 / (v_metrics.ascent - v_metrics.descent);
+        let font_size = text::pt_to_px(text.font_size()) * em_per_height;
+        let family = |id| self.font_families.get(&id).map(|f| Escaped(f));
+
+        let string = text.text();
+        let highlights = text.highlights();
+        for (line, line_rect) in text.lines() {
+            let [x, y] = self.point([line_rect.left(), line_rect.bottom()]);
+            write!(
+                out,
+                "<text x=\"{}\" y=\"{}\" font-size=\"{}\" xml:space=\"preserve\"{}",
+                Num(x),
+                Num(y),
+                Num(font_size as Scalar),
+                Fill(color.to_rgb())
+            )?;
+            if let Some(family) = family(font_id) {
+                write!(out, " font-family=\"{}\"", family)?;
+            }
+            write!(out, ">")?;
+
+            // Split the line into spans covered by the same highlight.
+            let mut start = line.start;
+            let overlapping = highlights
+                .iter()
+                .filter(|h| h.byte_range.start < line.end && h.byte_range.end > line.start);
+            for highlight in overlapping {
+                let span_start = std::cmp::max(highlight.byte_range.start, line.start);
+                let span_end = std::cmp::min(highlight.byte_range.end, line.end);
+                write!(out, "{}", Escaped(&string[start..span_start]))?;
+                write!(out, "<tspan")?;
+                if let Some(color) = highlight.color {
+                    let color = color.alpha(text.opacity());
+                    write!(out, "{}", Fill(color.to_rgb()))?;
+                }
+                if let Some(family) = highlight.font_id.and_then(&family) {
+                    write!(out, " font-family=\"{}\"", family)?;
+                }
+                write!(out, ">{}</tspan>", Escaped(&string[span_start..span_end]))?;
+                start = span_end;
+            }
+            writeln!(out, "{}</text>", Escaped(&string[start..line.end]))?;
+        }
+        Ok(())
+    }
+}
+
+// Write each glyph as a path tracing its outline, grouping consecutive glyphs of the same colour.
+fn write_glyph_outlines<W>(out: &mut W, text: render::Text, color: Color) -> fmt::Result
+where
+    W: Write,
+{
+    fn finish<W: Write>(out: &mut W, data: &mut String, color: Option<color::Rgba>) -> fmt::Result {
+        if let Some(color) = color {
+            if !data.is_empty() {
+                writeln!(out, "<path d=\"{}\"{}/>", data, Fill(color))?;
+            }
+        }
+        data.clear();
+        Ok(())
+    }
+
+    let text_color = color.to_rgb();
+    let mut data = String::new();
+    let mut current_color = None;
+    for glyph in text.highlighted_glyphs(1.0) {
+        let color = glyph.color.map(|c| c.to_rgb()).unwrap_or(text_color);
+        if current_color != Some(color) {
+            finish(out, &mut data, current_color)?;
+            current_color = Some(color);
+        }
+        for contour in glyph.glyph.shape().unwrap_or_default() {
+            let mut segments = contour.segments.iter().peekable();
+            let start = match segments.peek() {
+                Some(text::rt::Segment::Line(line)) => line.p[0],
+                Some(text::rt::Segment::Curve(curve)) => curve.p[0],
+                None => continue,
+            };
+            write!(
+                data,
+                "M{} {}",
+                Num(start.x as Scalar),
+                Num(start.y as Scalar)
+            )?;
+            for segment in segments {
+                match *segment {
+                    text::rt::Segment::Line(line) => {
+                        let p = line.p[1];
+                        write!(data, "L{} {}", Num(p.x as Scalar), Num(p.y as Scalar))?;
+                    }
+                    text::rt::Segment::Curve(curve) => {
+                        let [_, c, p] = curve.p;
+                        write!(
+                            data,
+                            "Q{} {} {} {}",
+                            Num(c.x as Scalar),
+                            Num(c.y as Scalar),
+                            Num(p.x as Scalar),
+                            Num(p.y as Scalar)
+                        )?;
+                    }
+                }
+            }
+            write!(data, "Z")?;
+        }
+    }
+    finish(out, &mut data, current_color)
+}
+
+// Whether or not `a` covers the entirety of `b`.
+fn covers(a: Rect, b: Rect) -> bool {
+    a.left() <= b.left() && a.right() >= b.right() && a.bottom() <= b.bottom() && a.top() >= b.top()
+}
+
+fn average_color(triangle: &Triangle<(Point, color::Rgba)>) -> color::Rgba {
+    let [(_, a), (_, b), (_, c)] = triangle.0;
+    let avg = |a: f32, b: f32, c: f32| (a + b + c) / 3.0;
+    color::Rgba(
+        avg(a.0, b.0, c.0),
+        avg(a.1, b.1, c.1),
+        avg(a.2, b.2, c.2),
+        avg(a.3, b.3, c.3),
+    )
+}
+
+// A number written with at most three decimal places.
+struct Num(Scalar);
+
+impl fmt::Display for Num {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let rounded = (self.0 * 1000.0).round() / 1000.0;
+        // Avoid writing negative zero.
+        write!(f, "{}", if rounded == 0.0 { 0.0 } else { rounded })
+    }
+}
+
+// The `fill` and `fill-opacity` attributes for the given colour.
+struct Fill(color::Rgba);
+
+impl fmt::Display for Fill {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let color::Rgba(r, g, b, a) = self.0;
+        let byte = |c: f32| (c.max(0.0).min(1.0) * 255.0).round() as u8;
+        write!(f, " fill=\"rgb({},{},{})\"", byte(r), byte(g), byte(b))?;
+        if a < 1.0 {
+            write!(f, " fill-opacity=\"{}\"", Num(a.max(0.0) as Scalar))?;
+        }
+        Ok(())
+    }
+}
+
+// A string with the characters reserved by XML escaped.
+struct Escaped<'a>(&'a str);
+
+impl<'a> fmt::Display for Escaped<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for ch in self.0.chars() {
+            match ch {
+                '&' => f.write_str("&amp;")?,
+                '<' => f.write_str("&lt;")?,
+                '>' => f.write_str("&gt;")?,
+                '"' => f.write_str("&quot;")?,
+                '\'' => f.write_str("&apos;")?,
+                ch => f.write_char(ch)?,
+            }
+        }
+        Ok(())
+    }
+}
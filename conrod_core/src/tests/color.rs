@@ -1,5 +1,8 @@
-use color::{hsl_to_rgb, rgb_to_hsl};
+use color::{
+    self, hsl_to_rgb, rgb_to_hsl, rgba, rgba_bytes, BlendMode, Color, Oklaba, ParseColorError,
+};
 use std::cmp::Ordering::Equal;
+use theme::Theme;
 
 ///// Test assist code.
 
@@ -17,6 +20,10 @@ fn compare_rgb_pairs(expected: (f32, f32, f32), actual: (f32, f32, f32)) -> bool
     r_comp == Equal && g_comp == Equal && b_comp == Equal
 }
 
+fn assert_approx_eq(a: f32, b: f32) {
+    assert!((a - b).abs() < 1e-3, "{} is not approximately {}", a, b);
+}
+
 ///// Actual tests.
 
 #[test]
@@ -58,3 +65,137 @@ fn rgb_to_hsl_silver() {
     let actual = convert_rgb_to_hsl_to_rgb(r, g, b);
     assert!(compare_rgb_pairs((r, g, b), actual));
 }
+
+#[test]
+fn hex_should_round_trip() {
+    let color = rgba_bytes(255, 0, 128, 204.0 / 255.0);
+    assert_eq!(color.to_hex(), "#FF0080CC");
+    assert_eq!(Color::from_hex("#FF0080CC").unwrap().to_hex(), "#FF0080CC");
+    assert_eq!(Color::from_hex("ff0080").unwrap().to_hex(), "#FF0080FF");
+    assert_eq!(Color::from_hex("#F08").unwrap().to_hex(), "#FF0088FF");
+    assert_eq!(Color::from_hex("#F08C").unwrap().to_hex(), "#FF0088CC");
+    assert_eq!(Color::from_hex("#FF000"), Err(ParseColorError::InvalidHex));
+    assert_eq!(Color::from_hex("#GG0000"), Err(ParseColorError::InvalidHex));
+}
+
+#[test]
+fn colors_should_parse_from_css_strings() {
+    let hex = |s: &str| s.parse::<Color>().map(Color::to_hex);
+    assert_eq!(hex("RebeccaPurple"), Ok("#663399FF".to_string()));
+    assert_eq!(hex("transparent"), Ok("#00000000".to_string()));
+    assert_eq!(hex(" #abc "), Ok("#AABBCCFF".to_string()));
+    assert_eq!(hex("rgb(255, 136, 0)"), Ok("#FF8800FF".to_string()));
+    assert_eq!(hex("rgba(100%, 0%, 0%, 0.5)"), Ok("#FF000080".to_string()));
+    assert_eq!(hex("rgb(255 136 0 / 80%)"), Ok("#FF8800CC".to_string()));
+    assert_eq!(hex("hsl(120, 100%, 50%)"), Ok("#00FF00FF".to_string()));
+    assert_eq!(
+        hex("hsla(0.5turn, 100%, 50%, 1)"),
+        Ok("#00FFFFFF".to_string())
+    );
+    assert_eq!(hex(""), Err(ParseColorError::Empty));
+    assert_eq!(hex("reddish"), Err(ParseColorError::UnknownName));
+    assert_eq!(hex("rgb(1, 2)"), Err(ParseColorError::InvalidArgumentCount));
+    assert_eq!(hex("rgb(1, x, 2)"), Err(ParseColorError::InvalidArgument));
+    assert_eq!(hex("rgb(1,,2,3)"), Err(ParseColorError::InvalidArgument));
+    assert_eq!(hex("rgb(1, 2, 3,)"), Err(ParseColorError::InvalidArgument));
+    assert_eq!(hex("rgb(1 2,3)"), Err(ParseColorError::InvalidArgument));
+    assert_eq!(
+        hex("rgb(1, 2, 3 / 1)"),
+        Err(ParseColorError::InvalidArgument)
+    );
+    assert_eq!(hex("rgb(1/2/3)"), Err(ParseColorError::InvalidArgument));
+    assert_eq!(hex("rgb(1 2 / 3)"), Err(ParseColorError::InvalidArgument));
+    assert_eq!(hex("rgb(1 2 3 /)"), Err(ParseColorError::InvalidArgument));
+    assert_eq!(
+        hex("cmyk(1, 2, 3, 4)"),
+        Err(ParseColorError::InvalidFunction)
+    );
+    assert_eq!(color::named("RED"), Some(color::rgb(1.0, 0.0, 0.0)));
+}
+
+#[test]
+fn hsv_should_round_trip() {
+    for &(r, g, b) in &[
+        (1.0, 0.5, 0.0),
+        (0.2, 0.4, 0.6),
+        (0.5, 0.5, 0.5),
+        (0.0, 0.0, 0.0),
+    ] {
+        let color::Hsva(h, s, v, _) = color::rgb(r, g, b).to_hsv();
+        let color::Rgba(r2, g2, b2, _) = color::hsv(h, s, v).to_rgb();
+        assert_approx_eq(r, r2);
+        assert_approx_eq(g, g2);
+        assert_approx_eq(b, b2);
+    }
+}
+
+#[test]
+fn oklab_should_match_reference_values() {
+    let Oklaba(l, a, b, _) = color::rgb(1.0, 0.0, 0.0).to_oklab();
+    assert_approx_eq(l, 0.628);
+    assert_approx_eq(a, 0.2249);
+    assert_approx_eq(b, 0.1258);
+
+    let Oklaba(l, a, b, _) = color::WHITE.to_oklab();
+    assert_approx_eq(l, 1.0);
+    assert_approx_eq(a, 0.0);
+    assert_approx_eq(b, 0.0);
+
+    let orange = color::rgb(1.0, 0.5, 0.0);
+    let color::Rgba(r, g, b, _) = Color::from(orange.to_oklch()).to_rgb();
+    assert_approx_eq(r, 1.0);
+    assert_approx_eq(g, 0.5);
+    assert_approx_eq(b, 0.0);
+}
+
+#[test]
+fn interpolation_should_reach_both_ends() {
+    let (red, blue) = (color::rgb(1.0, 0.0, 0.0), color::rgb(0.0, 0.0, 1.0));
+    for &lerp in &[Color::lerp, Color::lerp_oklab, Color::lerp_oklch] {
+        assert_eq!(lerp(red, blue, 0.0).to_hex(), red.to_hex());
+        assert_eq!(lerp(red, blue, 1.0).to_hex(), blue.to_hex());
+    }
+    // Interpolating in OkLCH retains the chroma of vivid colors, unlike sRGB.
+    let chroma = |c: Color| c.to_oklch().1;
+    assert!(chroma(red.lerp_oklch(blue, 0.5)) > chroma(red.lerp(blue, 0.5)));
+}
+
+#[test]
+fn blending_should_composite_over_the_backdrop() {
+    let half_red = rgba(1.0, 0.0, 0.0, 0.5);
+    assert_eq!(half_red.over(color::WHITE).to_hex(), "#FF8080FF");
+    assert_eq!(color::TRANSPARENT.over(color::WHITE).to_hex(), "#FFFFFFFF");
+    let grey = color::rgb(0.5, 0.5, 0.5);
+    let multiplied = grey.blend(grey, BlendMode::Multiply).to_rgb();
+    assert_approx_eq(multiplied.0, 0.25);
+    let screened = grey.blend(grey, BlendMode::Screen).to_rgb();
+    assert_approx_eq(screened.0, 0.75);
+}
+
+#[test]
+fn contrast_ratio_should_follow_wcag() {
+    assert_approx_eq(color::BLACK.contrast_ratio(color::WHITE), 21.0);
+    assert_approx_eq(color::WHITE.contrast_ratio(color::WHITE), 1.0);
+    let yellow = color::rgb(1.0, 1.0, 0.0);
+    let candidates = vec![color::WHITE, color::BLACK, yellow];
+    assert_eq!(yellow.best_contrast(candidates), Some(color::BLACK));
+    assert_eq!(yellow.best_contrast(vec![]), None);
+    assert_eq!(color::rgb(0.0, 0.0, 0.6).plain_contrast(), color::WHITE);
+}
+
+#[test]
+fn theme_from_accent_should_have_readable_labels() {
+    let accents = [
+        color::rgb(1.0, 1.0, 0.0),
+        color::rgb(0.0, 0.0, 0.6),
+        color::rgb(0.5, 0.5, 0.5),
+        color::rgb(0.29, 0.9, 0.29),
+        color::rgba(0.8, 0.2, 0.4, 0.5),
+    ];
+    for &accent in accents.iter() {
+        let theme = Theme::from_accent(accent);
+        assert_eq!(theme.shape_color, accent.with_alpha(1.0));
+        assert!(theme.label_color.contrast_ratio(theme.shape_color) >= 4.5);
+        assert!(theme.label_color.contrast_ratio(theme.background_color) >= 4.5);
+    }
+}
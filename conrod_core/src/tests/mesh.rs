@@ -0,0 +1,567 @@
+use color::{self, Color};
+use image;
+use mesh::{self, Mesh};
+use text;
+use widget;
+use {Colorable, Positionable, Rect, Sizeable, Ui, UiBuilder, Widget};
+
+///// Test assist code.
+
+struct NoImage;
+
+impl mesh::ImageDimensions for NoImage {
+    fn dimensions(&self) -> [u32; 2] {
+        [0, 0]
+    }
+}
+
+// A `Ui` with some small text followed by some larger text.
+fn ui_with_small_and_large_text(small: &str, large: &str) -> Ui {
+    let mut ui = UiBuilder::new([400.0, 200.0]).build();
+    let path = concat!(
+        env!("CARGO_MANIFEST_DIR"),
+        "/../assets/fonts/NotoSans/NotoSans-Regular.ttf"
+    );
+    let font_id = ui.fonts.insert_from_file(path).unwrap();
+    let ids = [
+        ui.widget_id_generator().next(),
+        ui.widget_id_generator().next(),
+    ];
+    {
+        let ui = &mut ui.set_widgets();
+        widget::Text::new(small)
+            .font_id(font_id)
+            .font_size(12)
+            .top_left()
+            .set(ids[0], ui);
+        widget::Text::new(large)
+            .font_id(font_id)
+            .font_size(48)
+            .middle()
+            .set(ids[1], ui);
+    }
+    ui
+}
+
+// A list of rows, each with a background and a label, followed by an image positioned beside the
+// highlighted row.
+struct Rows {
+    font_id: text::font::Id,
+    image_id: image::Id,
+    backgrounds: Vec<widget::Id>,
+    labels: Vec<widget::Id>,
+    image: widget::Id,
+}
+
+impl Rows {
+    fn new(ui: &mut Ui, n: usize, image_id: image::Id) -> Self {
+        let path = concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/../assets/fonts/NotoSans/NotoSans-Regular.ttf"
+        );
+        let font_id = ui.fonts.insert_from_file(path).unwrap();
+        let mut id_generator = ui.widget_id_generator();
+        Rows {
+            font_id,
+            image_id,
+            backgrounds: (0..n).map(|_| id_generator.next()).collect(),
+            labels: (0..n).map(|_| id_generator.next()).collect(),
+            image: id_generator.next(),
+        }
+    }
+
+    // Set the rows, with the given row highlighted and labelled differently.
+    fn set(&self, ui: &mut Ui, highlighted: usize, label: &str) {
+        let ui = &mut ui.set_widgets();
+        for (i, (&background, &text)) in self.backgrounds.iter().zip(&self.labels).enumerate() {
+            let (color, label): (Color, &str) = match i == highlighted {
+                true => (color::RED, label),
+                false => (color::GREY, "row"),
+            };
+            widget::Rectangle::fill([200.0, 20.0])
+                .color(color)
+                .x_y(0.0, 80.0 - i as f64 * 20.0)
+                .set(background, ui);
+            widget::Text::new(label)
+                .font_id(self.font_id)
+                .middle_of(background)
+                .set(text, ui);
+        }
+        widget::Image::new(self.image_id)
+            .w_h(32.0, 32.0)
+            .x_y(150.0, highlighted as f64 * 10.0)
+            .set(self.image, ui);
+    }
+}
+
+fn fill(mesh: &mut Mesh, ui: &mut Ui) -> Result<mesh::Fill, text::rt::gpu_cache::CacheWriteErr> {
+    let viewport = Rect::from_xy_dim([0.0, 0.0], [ui.win_w, ui.win_h]);
+    let image_map = image::Map::<NoImage>::new();
+    mesh.fill(viewport, 1.0, &image_map, ui.draw())
+}
+
+// The pixels of the glyph cache sampled by each text quad within the mesh.
+fn glyph_pixels(mesh: &Mesh) -> Vec<Vec<u8>> {
+    let (w, h) = mesh.glyph_cache().dimensions();
+    let pixels = mesh.glyph_cache_pixel_buffer();
+    mesh.vertices()
+        .chunks(6)
+        .filter(|quad| quad[0].mode == mesh::MODE_TEXT || quad[0].mode == mesh::MODE_TEXT_SDF)
+        .map(|quad| {
+            let px = |t: f32, len: u32| (t * len as f32).round() as usize;
+            let (l, r) = (px(quad[0].tex_coords[0], w), px(quad[2].tex_coords[0], w));
+            let (t, b) = (px(quad[1].tex_coords[1], h), px(quad[0].tex_coords[1], h));
+            (t..b)
+                .flat_map(|y| pixels[y * w as usize + l..y * w as usize + r].to_vec())
+                .collect()
+        })
+        .collect()
+}
+
+///// Actual tests.
+
+#[test]
+fn glyph_cache_should_grow_to_fit_text_and_recache_earlier_glyphs() {
+    let mut ui = ui_with_small_and_large_text("Conrod", "glyphs");
+
+    let mut expected = Mesh::new();
+    let fill_expected = self::fill(&mut expected, &mut ui).unwrap();
+    assert_eq!(fill_expected.glyph_cache_resized, None);
+
+    let mut mesh = Mesh::with_glyph_cache_dimensions([64, 64]);
+    let fill = self::fill(&mut mesh, &mut ui).unwrap();
+    let (w, h) = mesh.glyph_cache().dimensions();
+    assert!(w > 64 && h > 64);
+    assert_eq!(fill.glyph_cache_resized, Some([w, h]));
+    assert!(fill.glyph_cache_requires_upload);
+    assert_eq!(mesh.glyph_cache_pixel_buffer().len(), (w * h) as usize);
+
+    // Every glyph, including those cached before the cache grew, samples the same pixels.
+    let pixels = glyph_pixels(&mesh);
+    assert_eq!(pixels.len(), "Conrod".len() + "glyphs".len());
+    assert_eq!(pixels, glyph_pixels(&expected));
+    assert!(pixels.iter().all(|glyph| glyph.iter().any(|&p| p > 0)));
+
+    // Once grown, the cache keeps its size.
+    let fill = self::fill(&mut mesh, &mut ui).unwrap();
+    assert_eq!(fill.glyph_cache_resized, None);
+}
+
+#[test]
+fn glyph_cache_updated_rects_should_cover_every_written_pixel() {
+    let mut ui = ui_with_small_and_large_text("Conrod", "glyphs");
+
+    let mut mesh = Mesh::new();
+    let fill = self::fill(&mut mesh, &mut ui).unwrap();
+    assert!(fill.glyph_cache_requires_upload);
+    let (w, h) = mesh.glyph_cache().dimensions();
+    let rects = mesh.glyph_cache_updated_rects().to_vec();
+    assert!(!rects.is_empty());
+    assert!(rects.iter().all(|r| r.max.x <= w && r.max.y <= h));
+    assert!(!rects
+        .iter()
+        .any(|r| r.min.x == 0 && r.max.x == w && r.max.y == h));
+    let pixels = mesh.glyph_cache_pixel_buffer();
+    for (i, &p) in pixels.iter().enumerate() {
+        let (x, y) = (i as u32 % w, i as u32 / w);
+        let updated = rects
+            .iter()
+            .any(|r| r.min.x <= x && x < r.max.x && r.min.y <= y && y < r.max.y);
+        assert!(p == 0 || updated);
+    }
+
+    // Nothing is written when the same glyphs are drawn again.
+    let fill = self::fill(&mut mesh, &mut ui).unwrap();
+    assert!(!fill.glyph_cache_requires_upload);
+    assert!(mesh.glyph_cache_updated_rects().is_empty());
+
+    // The whole cache is updated once it grows.
+    let mut mesh = Mesh::with_glyph_cache_dimensions([64, 64]);
+    self::fill(&mut mesh, &mut ui).unwrap();
+    let (w, h) = mesh.glyph_cache().dimensions();
+    let rects = mesh.glyph_cache_updated_rects();
+    assert_eq!(rects.len(), 1);
+    assert_eq!((rects[0].min.x, rects[0].min.y), (0, 0));
+    assert_eq!((rects[0].max.x, rects[0].max.y), (w, h));
+}
+
+#[test]
+fn glyph_cache_should_not_grow_beyond_its_maximum_dimensions() {
+    let mut ui = ui_with_small_and_large_text("Conrod", "glyphs");
+    let mut mesh = Mesh::with_glyph_cache_dimensions([64, 64]).max_glyph_cache_dimensions([64, 64]);
+    assert!(self::fill(&mut mesh, &mut ui).is_err());
+    assert_eq!(mesh.glyph_cache().dimensions(), (64, 64));
+}
+
+#[test]
+fn sdf_text_should_draw_glyphs_of_any_size_from_a_single_field() {
+    let mut ui = ui_with_small_and_large_text("Conrod", "Conrod");
+    let mut mesh = Mesh::new().sdf_text(true);
+    let fill = self::fill(&mut mesh, &mut ui).unwrap();
+    assert!(fill.glyph_cache_requires_upload);
+
+    let quads: Vec<_> = mesh
+        .vertices()
+        .chunks(6)
+        .filter(|quad| quad[0].mode == mesh::MODE_TEXT_SDF)
+        .collect();
+    assert_eq!(quads.len(), "Conrod".len() * 2);
+    assert!(mesh.vertices().iter().all(|v| v.mode != mesh::MODE_TEXT));
+
+    // Each glyph of the large text samples the same field as the small text with a quad four
+    // times the size.
+    let width = |quad: &[mesh::Vertex]| quad[2].position[0] - quad[0].position[0];
+    let (small, large) = quads.split_at("Conrod".len());
+    for (small, large) in small.iter().zip(large) {
+        let tex_coords =
+            |quad: &[mesh::Vertex]| quad.iter().map(|v| v.tex_coords).collect::<Vec<_>>();
+        assert_eq!(tex_coords(small), tex_coords(large));
+        assert!((width(large) / width(small) - 4.0).abs() < 1e-3);
+    }
+
+    // The fields fade out over the edges of each glyph.
+    let pixels = glyph_pixels(&mesh);
+    assert!(pixels
+        .iter()
+        .all(|field| field.contains(&0) && field.iter().any(|&p| p > 128)));
+
+    // Once grown, the cache still holds a single field per glyph.
+    let mut grown = Mesh::with_glyph_cache_dimensions([64, 64]).sdf_text(true);
+    let fill = self::fill(&mut grown, &mut ui).unwrap();
+    assert!(fill.glyph_cache_resized.is_some());
+    assert_eq!(glyph_pixels(&grown), pixels);
+}
+
+#[test]
+fn fill_incremental_should_match_a_full_fill() {
+    let mut image_map = image::Map::new();
+    let image_id = image_map.insert([16, 16]);
+    let mut ui = UiBuilder::new([400.0, 200.0]).build();
+    let rows = Rows::new(&mut ui, 8, image_id);
+    let viewport = Rect::from_xy_dim([0.0, 0.0], [ui.win_w, ui.win_h]);
+
+    let mut incremental = Mesh::new();
+    let mut full = Mesh::new();
+    for &(highlighted, label) in &[(0, "first"), (3, "fourth"), (3, "Fourth"), (7, "last")] {
+        rows.set(&mut ui, highlighted, label);
+        let changed = ui.changed_widgets().clone();
+        incremental
+            .fill_incremental(viewport, 1.0, &image_map, ui.draw(), &changed)
+            .unwrap();
+        full.fill(viewport, 1.0, &image_map, ui.draw()).unwrap();
+        assert_eq!(incremental.vertices(), full.vertices());
+        assert!(incremental.commands().eq(full.commands()));
+        assert_eq!(
+            incremental.glyph_cache_pixel_buffer(),
+            full.glyph_cache_pixel_buffer()
+        );
+    }
+}
+
+#[test]
+fn fill_incremental_should_fade_children_when_only_a_parent_opacity_changes() {
+    let image_map = image::Map::<NoImage>::new();
+    let mut ui = UiBuilder::new([400.0, 200.0]).build();
+    let ids = [
+        ui.widget_id_generator().next(),
+        ui.widget_id_generator().next(),
+    ];
+    let viewport = Rect::from_xy_dim([0.0, 0.0], [ui.win_w, ui.win_h]);
+
+    let mut incremental = Mesh::new();
+    let mut full = Mesh::new();
+    for (i, &opacity) in [1.0, 0.5, 0.5, 1.0].iter().enumerate() {
+        {
+            let ui = &mut ui.set_widgets();
+            widget::Canvas::new()
+                .w_h(200.0, 100.0)
+                .middle()
+                .color(color::BLUE)
+                .opacity(opacity)
+                .set(ids[0], ui);
+            widget::Rectangle::fill([50.0, 50.0])
+                .color(color::RED)
+                .middle_of(ids[0])
+                .set(ids[1], ui);
+        }
+        let changed = ui.changed_widgets().clone();
+        assert!(i == 0 || !changed.contains(&ids[1]));
+        incremental
+            .fill_incremental(viewport, 1.0, &image_map, ui.draw(), &changed)
+            .unwrap();
+        full.fill(viewport, 1.0, &image_map, ui.draw()).unwrap();
+        assert_eq!(incremental.vertices(), full.vertices());
+        let alpha = incremental.vertices().last().unwrap().rgba[3];
+        assert_eq!(alpha, opacity);
+    }
+}
+
+#[test]
+fn fill_incremental_should_reuse_the_vertices_of_unchanged_widgets() {
+    let mut image_map = image::Map::new();
+    let image_id = image_map.insert([16, 16]);
+    let mut ui = UiBuilder::new([400.0, 200.0]).build();
+    let rows = Rows::new(&mut ui, 4, image_id);
+    let viewport = Rect::from_xy_dim([0.0, 0.0], [ui.win_w, ui.win_h]);
+    let mut mesh = Mesh::new();
+    rows.set(&mut ui, 0, "row");
+    mesh.fill(viewport, 1.0, &image_map, ui.draw()).unwrap();
+    let vertices = mesh.vertices().to_vec();
+
+    // Only the widgets within `changed_widgets` or whose rect changed are regenerated.
+    rows.set(&mut ui, 1, "row");
+    let no_widgets = Default::default();
+    mesh.fill_incremental(viewport, 1.0, &image_map, ui.draw(), &no_widgets)
+        .unwrap();
+    let image_start = vertices.len() - 6;
+    assert_eq!(mesh.vertices()[..image_start], vertices[..image_start]);
+    assert!(mesh.vertices()[image_start..] != vertices[image_start..]);
+    let changed = ui.changed_widgets().clone();
+    assert!(changed.contains(&rows.backgrounds[1]));
+    mesh.fill_incremental(viewport, 1.0, &image_map, ui.draw(), &changed)
+        .unwrap();
+    assert!(mesh.vertices() != &vertices[..]);
+
+    // All vertices are regenerated for a different viewport.
+    rows.set(&mut ui, 2, "row");
+    let viewport = Rect::from_xy_dim([0.0, 0.0], [ui.win_w * 2.0, ui.win_h * 2.0]);
+    mesh.fill_incremental(viewport, 2.0, &image_map, ui.draw(), &no_widgets)
+        .unwrap();
+    let mut full = Mesh::new();
+    full.fill(viewport, 2.0, &image_map, ui.draw()).unwrap();
+    let positions = |mesh: &Mesh| {
+        mesh.vertices()
+            .iter()
+            .map(|v| v.position)
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(positions(&mesh), positions(&full));
+}
+
+#[test]
+fn custom_draw_should_produce_a_command_between_the_surrounding_draws() {
+    let mut ui = UiBuilder::new([400.0, 200.0]).build();
+    let ids = [
+        ui.widget_id_generator().next(),
+        ui.widget_id_generator().next(),
+        ui.widget_id_generator().next(),
+    ];
+    {
+        let ui = &mut ui.set_widgets();
+        widget::Rectangle::fill([10.0, 10.0]).set(ids[0], ui);
+        widget::CustomDraw::new("viewport")
+            .w_h(40.0, 20.0)
+            .x_y(0.0, 0.0)
+            .set(ids[1], ui);
+        widget::Rectangle::fill([10.0, 10.0]).set(ids[2], ui);
+    }
+
+    let mut mesh = Mesh::new();
+    fill(&mut mesh, &mut ui).unwrap();
+    let len = mesh.vertices().len();
+    let commands: Vec<_> = mesh.commands().collect();
+    assert_eq!(commands.len(), 3);
+    assert_eq!(
+        commands[0],
+        mesh::Command::Draw(mesh::Draw::Plain(0..len - 6))
+    );
+    assert_eq!(
+        commands[2],
+        mesh::Command::Draw(mesh::Draw::Plain(len - 6..len))
+    );
+    match commands[1] {
+        mesh::Command::Custom(ref custom) => {
+            assert_eq!(custom.id, ids[1]);
+            assert_eq!(custom.data.downcast_ref(), Some(&"viewport"));
+            assert_eq!(custom.rect, Rect::from_xy_dim([0.0, 0.0], [40.0, 20.0]));
+            let viewport = mesh::Scizzor {
+                top_left: [0, 0],
+                dimensions: [400, 200],
+            };
+            assert_eq!(custom.scizzor, viewport);
+        }
+        ref command => panic!("expected a custom command, found {:?}", command),
+    }
+}
+
+#[test]
+fn crop_shapes_should_produce_clip_masks_over_the_whole_viewport() {
+    let mut ui = UiBuilder::new([400.0, 200.0]).build();
+    let ids = [
+        ui.widget_id_generator().next(),
+        ui.widget_id_generator().next(),
+        ui.widget_id_generator().next(),
+    ];
+    {
+        let ui = &mut ui.set_widgets();
+        widget::Rectangle::fill([100.0, 100.0])
+            .x_y(0.0, 0.0)
+            .crop_kids_to_shape(widget::CropShape::Ellipse)
+            .set(ids[0], ui);
+        widget::Rectangle::fill([100.0, 100.0])
+            .middle_of(ids[0])
+            .parent(ids[0])
+            .set(ids[1], ui);
+        widget::Rectangle::fill([10.0, 10.0])
+            .x_y(150.0, 0.0)
+            .parent(ui.window)
+            .set(ids[2], ui);
+    }
+
+    let mut mesh = Mesh::new();
+    fill(&mut mesh, &mut ui).unwrap();
+    let commands: Vec<_> = mesh
+        .commands()
+        .filter(|command| match *command {
+            mesh::Command::Draw(mesh::Draw::Plain(ref range)) => !range.is_empty(),
+            _ => true,
+        })
+        .collect();
+
+    // The mask follows the vertices of the window's background and the cropping widget, and
+    // precedes those of its child.
+    let rect = Rect::from_xy_dim([0.0, 0.0], [100.0, 100.0]);
+    let mask_len = widget::CropShape::Ellipse.triangles(rect).len() * 3;
+    let (mask_start, mask_end) = (12, 12 + mask_len);
+    let viewport = mesh::Scizzor {
+        top_left: [0, 0],
+        dimensions: [400, 200],
+    };
+    let bounds = mesh::Scizzor {
+        top_left: [150, 50],
+        dimensions: [100, 100],
+    };
+    let expected = vec![
+        mesh::Command::Draw(mesh::Draw::Plain(0..12)),
+        mesh::Command::Scizzor(viewport),
+        mesh::Command::ClipMask(mesh::ClipMask {
+            shapes: vec![mask_start..mask_end],
+        }),
+        mesh::Command::Scizzor(bounds),
+        mesh::Command::Draw(mesh::Draw::Plain(mask_end..mask_end + 6)),
+        mesh::Command::Scizzor(viewport),
+        mesh::Command::ClipMask(mesh::ClipMask { shapes: vec![] }),
+        mesh::Command::Draw(mesh::Draw::Plain(mask_end + 6..mask_end + 12)),
+    ];
+    assert_eq!(commands, expected);
+    assert_eq!(mesh.vertices().len(), mask_end + 12);
+
+    // The vertices of the mask describe the ellipse within the viewport.
+    for v in &mesh.vertices()[mask_start..mask_end] {
+        let [x, y] = v.position;
+        assert!(x * x * 16.0 + y * y * 4.0 <= 1.0 + 1e-4);
+    }
+}
+
+#[test]
+fn atlas_should_merge_the_draws_of_packed_images() {
+    let mut ui = UiBuilder::new([400.0, 200.0]).build();
+    let ids = [
+        ui.widget_id_generator().next(),
+        ui.widget_id_generator().next(),
+    ];
+    let mut image_map = image::Map::new();
+    let icons = [image_map.insert([16, 16]), image_map.insert([32, 8])];
+    {
+        let ui = &mut ui.set_widgets();
+        for (&id, &icon) in ids.iter().zip(&icons) {
+            widget::Image::new(icon)
+                .w_h(32.0, 32.0)
+                .x_y(0.0, 0.0)
+                .parent(ui.window)
+                .set(id, ui);
+        }
+    }
+    let viewport = Rect::from_xy_dim([0.0, 0.0], [ui.win_w, ui.win_h]);
+    let mut mesh = Mesh::new();
+
+    // Without an atlas, each image requires its own draw.
+    mesh.fill(viewport, 1.0, &image_map, ui.draw()).unwrap();
+    let commands: Vec<_> = mesh.commands().collect();
+    assert_eq!(
+        commands,
+        vec![
+            mesh::Command::Draw(mesh::Draw::Plain(0..6)),
+            mesh::Command::Draw(mesh::Draw::Image(icons[0], 6..12)),
+            mesh::Command::Draw(mesh::Draw::Image(icons[1], 12..18)),
+        ]
+    );
+
+    let atlas = image::atlas::Builder::new([64, 64]).padding(1).build(
+        &mut image_map,
+        icons.iter().cloned(),
+        |page, _| page.dimensions,
+    );
+    assert_eq!(atlas.page_ids().len(), 1);
+    let page_id = atlas.page_ids()[0];
+    let (icon_page_id, icon_rect) = atlas.get(icons[0]).unwrap();
+    assert_eq!(icon_page_id, page_id);
+    assert_eq!(icon_rect, Rect::from_corners([1.0, 47.0], [17.0, 63.0]));
+
+    // With the atlas, both images are drawn from the same page.
+    mesh.fill(viewport, 1.0, &image_map, atlas.primitives(ui.draw()))
+        .unwrap();
+    let commands: Vec<_> = mesh.commands().collect();
+    assert_eq!(
+        commands,
+        vec![
+            mesh::Command::Draw(mesh::Draw::Plain(0..6)),
+            mesh::Command::Draw(mesh::Draw::Image(page_id, 6..18)),
+        ]
+    );
+    let (min_u, max_u) = mesh.vertices()[6..12]
+        .iter()
+        .fold((1.0, 0.0), |(min, max), v| {
+            (v.tex_coords[0].min(min), v.tex_coords[0].max(max))
+        });
+    assert_eq!((min_u, max_u), (1.0 / 64.0, 17.0 / 64.0));
+}
+
+#[test]
+fn atlas_builder_should_pack_images_without_overlap() {
+    let mut image_map = image::Map::new();
+    let ids: Vec<_> = image_map
+        .extend(vec![
+            [20, 10],
+            [30, 30],
+            [100, 1],
+            [0, 5],
+            [20, 20],
+            [30, 30],
+        ])
+        .collect();
+    let images = ids.iter().map(|&id| (id, image_map[&id]));
+    let pages = image::atlas::Builder::new([64, 64]).padding(1).pack(images);
+
+    // The empty image and the image that is wider than a page are not placed.
+    let placed: Vec<_> = pages
+        .iter()
+        .flat_map(|page| page.placements.iter().map(|p| p.image_id))
+        .collect();
+    assert_eq!(placed.len(), 4);
+    assert!(!placed.contains(&ids[2]) && !placed.contains(&ids[3]));
+
+    // The tallest images are placed first and each placement keeps its padding.
+    assert_eq!(pages.len(), 1);
+    let placements = &pages[0].placements;
+    assert_eq!(placements[0].image_id, ids[1]);
+    assert_eq!(placements[0].top_left, [1, 1]);
+    assert_eq!(placements[1].top_left, [33, 1]);
+    assert_eq!(placements[2].top_left, [1, 33]);
+    for (i, a) in placements.iter().enumerate() {
+        let rect = |p: &image::atlas::Placement| p.source_rect([64, 64]).pad(-1.0);
+        let a_rect = rect(a);
+        assert!(a_rect.left() >= 0.0 && a_rect.right() <= 64.0);
+        assert!(a_rect.bottom() >= 0.0 && a_rect.top() <= 64.0);
+        for b in &placements[i + 1..] {
+            if let Some(overlap) = a_rect.overlap(rect(b)) {
+                assert!(overlap.w() == 0.0 || overlap.h() == 0.0);
+            }
+        }
+    }
+
+    // Images that no longer fit begin a new page.
+    let images = ids.iter().map(|&id| (id, image_map[&id]));
+    let pages = image::atlas::Builder::new([32, 32]).pack(images);
+    assert_eq!(pages.len(), 3);
+}
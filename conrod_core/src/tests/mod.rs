@@ -1,4 +1,8 @@
 mod color;
 mod global_input;
+mod mesh;
+mod render;
+mod svg;
+mod text;
 mod ui;
 mod widget_input;
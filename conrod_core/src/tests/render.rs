@@ -0,0 +1,617 @@
+use color;
+use image;
+use position::Padding;
+use render::{self, PrimitiveKind, PrimitiveWalker};
+use std;
+use widget;
+use widget::line::{Cap, Join};
+use widget::path::{Command, FillRule};
+use widget::triangles::Triangle;
+use {Borderable, Colorable, Point, Positionable, Range, Rect, Sizeable, Ui, UiBuilder, Widget};
+
+///// Test assist code.
+
+// Collect the alpha of every vertex of each multi-colored primitive drawn by the `Ui`.
+fn multi_color_alphas(ui: &mut Ui) -> Vec<Vec<f32>> {
+    let mut alphas = vec![];
+    let mut primitives = ui.draw();
+    while let Some(primitive) = primitives.next() {
+        if let PrimitiveKind::TrianglesMultiColor { triangles } = primitive.kind {
+            let iter = triangles.iter().flat_map(|t| t.iter().map(|&(_, c)| c.3));
+            alphas.push(iter.collect());
+        }
+    }
+    alphas
+}
+
+// Whether the point lies over a path two units thick, turning left at `[10.0, 0.0]`.
+fn is_over_corner(cap: Cap, join: Join, point: Point) -> bool {
+    let points = [[0.0, 0.0], [10.0, 0.0], [10.0, 10.0]];
+    widget::point_path::is_over_with_join(points.iter().cloned(), cap, join, 2.0, point)
+}
+
+// The total area covered by the given triangles.
+fn area(triangles: &[Triangle<Point>]) -> f64 {
+    triangles
+        .iter()
+        .map(|&Triangle([a, b, c])| {
+            ((b[0] - a[0]) * (c[1] - a[1]) - (c[0] - a[0]) * (b[1] - a[1])).abs() / 2.0
+        })
+        .sum()
+}
+
+// A square subpath of the given size about the origin.
+fn square(half: f64) -> Vec<Command> {
+    vec![
+        Command::MoveTo([-half, -half]),
+        Command::LineTo([half, -half]),
+        Command::LineTo([half, half]),
+        Command::LineTo([-half, half]),
+        Command::Close,
+    ]
+}
+
+///// Actual tests.
+
+#[test]
+fn anti_aliased_shapes_should_fade_out_at_their_edges() {
+    let mut ui = UiBuilder::new([800.0, 600.0]).build();
+    ui.theme.anti_aliasing = Some(1.0);
+    let ids = [
+        ui.widget_id_generator().next(),
+        ui.widget_id_generator().next(),
+        ui.widget_id_generator().next(),
+    ];
+
+    {
+        let ui = &mut ui.set_widgets();
+        let points = vec![[0.0, 0.0], [100.0, 0.0], [50.0, 100.0]];
+        widget::Oval::fill([100.0, 50.0]).middle().set(ids[0], ui);
+        widget::Polygon::fill(points)
+            .color(color::RED)
+            .middle()
+            .set(ids[1], ui);
+        widget::Line::centred([-50.0, 0.0], [50.0, 0.0])
+            .thickness(3.0)
+            .w_h(100.0, 3.0)
+            .middle()
+            .set(ids[2], ui);
+    }
+
+    let alphas = multi_color_alphas(&mut ui);
+    assert_eq!(alphas.len(), 3);
+    for shape_alphas in alphas {
+        assert!(!shape_alphas.is_empty());
+        assert!(shape_alphas.iter().any(|&a| a == 0.0));
+        assert!(shape_alphas.iter().any(|&a| a == 1.0));
+        assert!(shape_alphas.iter().all(|&a| a == 0.0 || a == 1.0));
+    }
+}
+
+#[test]
+fn point_paths_should_be_mitered_by_default() {
+    let points = [[0.0, 0.0], [10.0, 0.0], [10.0, 10.0]];
+    let points = || points.iter().cloned();
+    let default: Vec<_> = widget::point_path::triangles(points(), Cap::Flat, 2.0)
+        .unwrap()
+        .collect();
+    let miter: Vec<_> =
+        widget::point_path::triangles_with_join(points(), Cap::Flat, Join::Miter, 2.0)
+            .unwrap()
+            .collect();
+    assert_eq!(default, miter);
+    let is_over = |p| widget::point_path::is_over(points(), Cap::Flat, 2.0, p);
+    assert!(is_over([10.9, -0.9]));
+    assert!(!is_over([11.1, -1.1]));
+}
+
+#[test]
+fn thick_paths_should_be_joined_and_capped() {
+    // The outside of the corner is filled according to the join.
+    let (tip, near_tip) = ([10.9, -0.9], [10.6, -0.6]);
+    assert!(is_over_corner(Cap::Flat, Join::Miter, tip));
+    assert!(!is_over_corner(Cap::Flat, Join::Round, tip));
+    assert!(is_over_corner(Cap::Flat, Join::Round, near_tip));
+    assert!(!is_over_corner(Cap::Flat, Join::Bevel, near_tip));
+    assert!(is_over_corner(Cap::Flat, Join::Bevel, [10.4, -0.4]));
+
+    // Only round caps extend beyond the ends of the path.
+    assert!(!is_over_corner(Cap::Flat, Join::Miter, [-0.5, 0.1]));
+    assert!(is_over_corner(Cap::Round, Join::Miter, [-0.5, 0.1]));
+    assert!(is_over_corner(Cap::Round, Join::Miter, [10.1, 10.5]));
+
+    // Closed paths are joined where they begin and end rather than capped.
+    let square = [
+        [0.0, 0.0],
+        [10.0, 0.0],
+        [10.0, 10.0],
+        [0.0, 10.0],
+        [0.0, 0.0],
+    ];
+    let is_over = |p| {
+        widget::point_path::is_over_with_join(
+            square.iter().cloned(),
+            Cap::Flat,
+            Join::Miter,
+            2.0,
+            p,
+        )
+    };
+    assert!(is_over([-0.9, -0.9]));
+    assert!(!is_over([5.0, 5.0]));
+}
+
+#[test]
+fn path_fill_rules_should_decide_which_regions_are_filled() {
+    let mut commands = square(5.0);
+    commands.extend(square(2.0));
+    let subpaths = widget::path::flatten(commands, 0.25);
+    assert_eq!(subpaths.len(), 2);
+
+    let non_zero = widget::path::fill_triangles(&subpaths, FillRule::NonZero);
+    let even_odd = widget::path::fill_triangles(&subpaths, FillRule::EvenOdd);
+    assert!((area(&non_zero) - 100.0).abs() < 1e-9);
+    assert!((area(&even_odd) - 84.0).abs() < 1e-9);
+    assert!(widget::path::is_over_fill(
+        &subpaths,
+        FillRule::NonZero,
+        [0.5, 0.5]
+    ));
+    assert!(!widget::path::is_over_fill(
+        &subpaths,
+        FillRule::EvenOdd,
+        [0.5, 0.5]
+    ));
+    assert!(widget::path::is_over_fill(
+        &subpaths,
+        FillRule::EvenOdd,
+        [3.5, 0.5]
+    ));
+
+    // A self-intersecting bow tie is filled on both sides of the crossing.
+    let bow_tie = vec![vec![[0.0, 0.0], [2.0, 2.0], [2.0, 0.0], [0.0, 2.0]]];
+    let tris = widget::path::fill_triangles(&bow_tie, FillRule::NonZero);
+    assert!((area(&tris) - 2.0).abs() < 1e-9);
+}
+
+#[test]
+fn path_curves_should_be_flattened_within_tolerance() {
+    let tolerance = 0.1;
+    let commands = vec![
+        Command::MoveTo([0.0, 0.0]),
+        Command::CubicTo([0.0, 100.0], [100.0, 100.0], [100.0, 0.0]),
+        Command::Arc {
+            centre: [50.0, 0.0],
+            radii: [50.0, 50.0],
+            start_radians: 0.0,
+            sweep_radians: -std::f64::consts::PI,
+        },
+    ];
+    let subpaths = widget::path::flatten(commands, tolerance);
+    assert_eq!(subpaths.len(), 1);
+    let points = &subpaths[0];
+    assert_eq!(points[0], [0.0, 0.0]);
+    assert!(points.len() > 20);
+
+    // The midpoint of each line across the arc lies close to the circle.
+    let arc_start = points.iter().rposition(|&p| p == [100.0, 0.0]).unwrap();
+    for pair in points[arc_start..].windows(2) {
+        let mid = [
+            (pair[0][0] + pair[1][0]) / 2.0,
+            (pair[0][1] + pair[1][1]) / 2.0,
+        ];
+        let distance = ((mid[0] - 50.0).powi(2) + mid[1].powi(2)).sqrt();
+        assert!(50.0 - distance <= tolerance);
+    }
+}
+
+#[test]
+fn paths_should_draw_filled_and_outlined() {
+    let mut ui = UiBuilder::new([800.0, 600.0]).build();
+    let ids = [
+        ui.widget_id_generator().next(),
+        ui.widget_id_generator().next(),
+    ];
+    {
+        let ui = &mut ui.set_widgets();
+        widget::Path::fill(square(20.0)).middle().set(ids[0], ui);
+        widget::Path::outline(square(20.0)).middle().set(ids[1], ui);
+    }
+    let mut primitives = ui.draw();
+    let mut areas = vec![];
+    while let Some(primitive) = primitives.next() {
+        if let PrimitiveKind::TrianglesSingleColor { triangles, .. } = primitive.kind {
+            areas.push(area(triangles));
+        }
+    }
+    assert_eq!(areas.len(), 2);
+    assert!((areas[0] - 1600.0).abs() < 1e-9);
+    // Four sides of thickness 1 that overlap at the corners, plus the outside of each miter.
+    assert!((areas[1] - (4.0 * 40.0 + 4.0 * 0.25)).abs() < 1e-9);
+}
+
+#[test]
+fn paths_should_cache_their_flattened_curves_until_the_tolerance_changes() {
+    let mut ui = UiBuilder::new([800.0, 600.0]).build();
+    let id = ui.widget_id_generator().next();
+    let commands = vec![
+        Command::MoveTo([0.0, 0.0]),
+        Command::QuadraticTo([50.0, 100.0], [100.0, 0.0]),
+    ];
+    let mut subpaths_with_tolerance = |tolerance| {
+        {
+            let ui = &mut ui.set_widgets();
+            widget::Path::outline(commands.iter().cloned())
+                .tolerance(tolerance)
+                .set(id, ui);
+        }
+        let container = ui.widget_graph().widget(id).unwrap();
+        let path = container
+            .state_and_style::<widget::path::State, widget::path::Style>()
+            .unwrap();
+        path.state.subpaths.clone()
+    };
+    let coarse = subpaths_with_tolerance(1.0);
+    assert_eq!(coarse, widget::path::flatten(commands.iter().cloned(), 1.0));
+    assert_eq!(subpaths_with_tolerance(1.0), coarse);
+    let fine = subpaths_with_tolerance(0.01);
+    assert_eq!(fine, widget::path::flatten(commands.iter().cloned(), 0.01));
+    assert!(fine[0].len() > coarse[0].len());
+}
+
+#[test]
+fn opacity_should_fade_widgets_and_their_descendants() {
+    let mut ui = UiBuilder::new([800.0, 600.0]).build();
+    let ids = [
+        ui.widget_id_generator().next(),
+        ui.widget_id_generator().next(),
+        ui.widget_id_generator().next(),
+        ui.widget_id_generator().next(),
+        ui.widget_id_generator().next(),
+    ];
+    let mut image_map = image::Map::new();
+    let image_id = image_map.insert(());
+    {
+        let ui = &mut ui.set_widgets();
+        widget::Canvas::new().opacity(0.5).set(ids[0], ui);
+        widget::Rectangle::fill([10.0, 10.0])
+            .middle_of(ids[0])
+            .set(ids[1], ui);
+        widget::Canvas::new()
+            .w_h(100.0, 100.0)
+            .middle_of(ids[0])
+            .opacity(0.5)
+            .set(ids[2], ui);
+        widget::Image::new(image_id)
+            .w_h(10.0, 10.0)
+            .middle_of(ids[2])
+            .set(ids[3], ui);
+        widget::Rectangle::fill([10.0, 10.0])
+            .middle_of(ids[2])
+            .opacity(0.0)
+            .set(ids[4], ui);
+    }
+
+    let mut primitives = ui.draw();
+    let mut drawn = vec![];
+    while let Some(primitive) = primitives.next() {
+        drawn.push(primitive.id);
+        match primitive.kind {
+            PrimitiveKind::Rectangle { color } if primitive.id == ids[1] => {
+                assert_eq!(color.to_rgb().3, 0.5);
+            }
+            PrimitiveKind::Image { color, .. } => {
+                assert_eq!(primitive.id, ids[3]);
+                assert_eq!(color.map(|c| c.to_rgb().3), Some(0.25));
+            }
+            _ => (),
+        }
+    }
+    assert!(drawn.contains(&ids[1]));
+    assert!(drawn.contains(&ids[3]));
+    assert!(!drawn.contains(&ids[4]));
+}
+
+#[test]
+fn transforms_should_apply_to_primitives_of_widgets_and_their_descendants() {
+    let mut ui = UiBuilder::new([800.0, 600.0]).build();
+    let ids = [
+        ui.widget_id_generator().next(),
+        ui.widget_id_generator().next(),
+    ];
+    {
+        let ui = &mut ui.set_widgets();
+        widget::Canvas::new()
+            .w_h(100.0, 100.0)
+            .x_y(100.0, 0.0)
+            .scale(2.0)
+            .crop_kids()
+            .set(ids[0], ui);
+        widget::Rectangle::fill([10.0, 10.0])
+            .x_y_relative_to(ids[0], 20.0, 0.0)
+            .parent(ids[0])
+            .rotate(std::f64::consts::PI)
+            .set(ids[1], ui);
+    }
+    let kid_area = ui.kid_area_of(ids[0]).unwrap();
+    let mut primitives = ui.draw();
+    while let Some(primitive) = primitives.next() {
+        if primitive.id != ids[1] {
+            continue;
+        }
+        // Rotated about its own centre, then scaled about that of the canvas.
+        let transform = primitive.transform.unwrap();
+        let [x, y] = transform.apply([primitive.rect.left(), primitive.rect.bottom()]);
+        assert!((x - 150.0).abs() < 1e-9 && (y - 10.0).abs() < 1e-9);
+        // The canvas crops its children to its scaled area within the window.
+        let (w, h) = kid_area.w_h();
+        let scizzor = Rect::from_xy_dim(kid_area.xy(), [w * 2.0, h * 2.0]);
+        assert_eq!(primitive.scizzor, scizzor);
+        return;
+    }
+    panic!("no primitive was produced for the rectangle");
+}
+
+#[test]
+fn drop_shadows_should_be_drawn_beneath_and_fade_out_over_their_blur() {
+    let mut ui = UiBuilder::new([800.0, 600.0]).build();
+    let canvas = ui.widget_id_generator().next();
+    let shadow = widget::drop_shadow::Shadow::new([10.0, -10.0], 8.0, color::BLACK);
+    {
+        let ui = &mut ui.set_widgets();
+        widget::Canvas::new()
+            .w_h(100.0, 50.0)
+            .middle()
+            .border(0.0)
+            .shadow(shadow)
+            .set(canvas, ui);
+    }
+
+    // The window's backdrop is drawn before the shadow.
+    let mut primitives = ui.draw();
+    let primitive = loop {
+        let primitive = primitives.next().unwrap();
+        if let PrimitiveKind::TrianglesMultiColor { .. } = primitive.kind {
+            break primitive;
+        }
+    };
+    let expected = Rect::from_xy_dim([10.0, -10.0], [108.0, 58.0]);
+    assert_eq!(primitive.rect, expected);
+    match primitive.kind {
+        PrimitiveKind::TrianglesMultiColor { triangles } => {
+            let vertices = || triangles.iter().flat_map(|t| t.iter().cloned());
+            assert!(vertices().all(|(p, _)| expected.pad(-1e-9).is_over(p)));
+            assert!(vertices().any(|(_, c)| c.3 == 1.0));
+            assert!(vertices().any(|(_, c)| c.3 == 0.0));
+            // The fully opaque region is inset from the bounds by the width of the blur.
+            let opaque = expected.pad(8.0 - 1e-9);
+            assert!(vertices()
+                .filter(|&(_, c)| c.3 == 1.0)
+                .all(|(p, _)| opaque.is_over(p)));
+        }
+        _ => panic!("expected the shadow to be drawn first"),
+    }
+    // The canvas' backdrop is drawn over its shadow.
+    let mut backdrop = None;
+    while let Some(primitive) = primitives.next() {
+        if let PrimitiveKind::Rectangle { .. } = primitive.kind {
+            backdrop = Some(primitive.rect);
+        }
+    }
+    assert_eq!(backdrop, Some(Rect::from_xy_dim([0.0, 0.0], [100.0, 50.0])));
+}
+
+#[test]
+fn nine_slice_images_should_keep_the_size_of_their_corners() {
+    let insets = Padding {
+        x: Range::new(10.0, 20.0),
+        y: Range::new(5.0, 5.0),
+    };
+    let source = Rect::from_corners([0.0, 0.0], [64.0, 32.0]);
+    let rect = Rect::from_corners([0.0, 0.0], [200.0, 100.0]);
+    let slices: Vec<_> = widget::image::nine_slice(rect, source, insets).collect();
+    assert_eq!(slices.len(), 9);
+    // The bottom left corner is drawn at its source size.
+    let corner = Rect::from_corners([0.0, 0.0], [10.0, 5.0]);
+    assert_eq!(slices[0], (corner, corner));
+    // The centre is stretched to fill the remaining area.
+    let centre = Rect::from_corners([10.0, 5.0], [180.0, 95.0]);
+    let centre_src = Rect::from_corners([10.0, 5.0], [44.0, 27.0]);
+    assert_eq!(slices[4], (centre, centre_src));
+    // The top right corner.
+    let corner = Rect::from_corners([180.0, 95.0], [200.0, 100.0]);
+    let corner_src = Rect::from_corners([44.0, 27.0], [64.0, 32.0]);
+    assert_eq!(slices[8], (corner, corner_src));
+
+    // Corners are shrunk to fit rects that are too small to contain them.
+    let rect = Rect::from_corners([0.0, 0.0], [15.0, 100.0]);
+    let slices: Vec<_> = widget::image::nine_slice(rect, source, insets).collect();
+    assert_eq!(slices.len(), 6);
+    assert_eq!(slices[0].0, Rect::from_corners([0.0, 0.0], [5.0, 5.0]));
+
+    // Buttons pass their insets on to their image.
+    let mut ui = UiBuilder::new([800.0, 600.0]).build();
+    let button = ui.widget_id_generator().next();
+    let mut image_map = image::Map::new();
+    let image_id = image_map.insert(());
+    {
+        let ui = &mut ui.set_widgets();
+        widget::Button::image(image_id)
+            .w_h(200.0, 100.0)
+            .middle()
+            .nine_slice(insets)
+            .set(button, ui);
+    }
+    let mut primitives = ui.draw();
+    while let Some(primitive) = primitives.next() {
+        if let PrimitiveKind::Image { nine_slice, .. } = primitive.kind {
+            assert_eq!(nine_slice, Some(insets));
+            return;
+        }
+    }
+    panic!("no primitive was produced for the button's image");
+}
+
+#[test]
+fn image_fit_modes_should_preserve_aspect_ratio_or_tile() {
+    use widget::image::Fit;
+    let source = Rect::from_corners([0.0, 0.0], [40.0, 20.0]);
+    let rect = Rect::from_corners([0.0, 0.0], [100.0, 100.0]);
+    let fit = |fit| widget::image::fit(fit, rect, source).collect::<Vec<_>>();
+
+    assert_eq!(fit(Fit::Stretch), vec![(rect, source)]);
+
+    // Letterboxed along the *y* axis.
+    let dst = Rect::from_corners([0.0, 25.0], [100.0, 75.0]);
+    assert_eq!(fit(Fit::Contain), vec![(dst, source)]);
+
+    // Cropped along the *x* axis.
+    let src = Rect::from_corners([10.0, 0.0], [30.0, 20.0]);
+    assert_eq!(fit(Fit::Cover), vec![(rect, src)]);
+
+    let dst = Rect::from_corners([30.0, 40.0], [70.0, 60.0]);
+    assert_eq!(fit(Fit::Centre), vec![(dst, source)]);
+
+    // Tiles begin at the top left, cropping those along the right and bottom edges.
+    let tiles = fit(Fit::Tile);
+    assert_eq!(tiles.len(), 3 * 5);
+    let top_left = Rect::from_corners([0.0, 80.0], [40.0, 100.0]);
+    assert_eq!(tiles[0], (top_left, source));
+    let top_right = Rect::from_corners([80.0, 80.0], [100.0, 100.0]);
+    let src = Rect::from_corners([0.0, 0.0], [20.0, 20.0]);
+    assert_eq!(tiles[2], (top_right, src));
+    let covered: f64 = tiles.iter().map(|&(dst, _)| dst.w() * dst.h()).sum();
+    assert_eq!(covered, 100.0 * 100.0);
+
+    // Tiny sources are scaled up so that the number of tiles is limited.
+    let max = widget::image::MAX_TILES_PER_AXIS;
+    let pixel = Rect::from_corners([0.0, 0.0], [1.0, 1.0]);
+    let screen = Rect::from_corners([0.0, 0.0], [1920.0, 1080.0]);
+    let tiles: Vec<_> = widget::image::fit(Fit::Tile, screen, pixel).collect();
+    assert!(tiles.len() <= max * max);
+    let covered: f64 = tiles.iter().map(|&(dst, _)| dst.w() * dst.h()).sum();
+    assert!((covered - 1920.0 * 1080.0).abs() < 1e-6);
+    let within_one_pixel = |src: Rect| src.w() <= 1.0 && src.h() <= 1.0;
+    assert!(tiles.iter().all(|&(_, src)| within_one_pixel(src)));
+
+    // Nine-slice insets take precedence over the fit.
+    let insets = Padding {
+        x: Range::new(5.0, 5.0),
+        y: Range::new(5.0, 5.0),
+    };
+    let quads = widget::image::quads(rect, source, Fit::Contain, Some(insets));
+    assert_eq!(quads.count(), 9);
+}
+
+#[test]
+fn custom_draw_widgets_should_be_preserved_by_owned_primitives() {
+    let mut ui = UiBuilder::new([800.0, 600.0]).build();
+    let ids = [
+        ui.widget_id_generator().next(),
+        ui.widget_id_generator().next(),
+    ];
+    let set = |ui: &mut Ui, data: u32| {
+        let ui = &mut ui.set_widgets();
+        widget::Canvas::new().opacity(0.5).set(ids[0], ui);
+        widget::CustomDraw::new(data)
+            .w_h(20.0, 10.0)
+            .middle_of(ids[0])
+            .set(ids[1], ui);
+    };
+
+    // Collect the id, rect, data and opacity of each custom primitive.
+    fn customs<P: PrimitiveWalker>(mut primitives: P) -> Vec<(widget::Id, Rect, u32, f32)> {
+        let mut customs = vec![];
+        while let Some(primitive) = primitives.next_primitive() {
+            if let PrimitiveKind::Custom { data } = primitive.kind {
+                let data = *data.downcast_ref::<u32>().unwrap();
+                customs.push((primitive.id, primitive.rect, data, primitive.opacity));
+            }
+        }
+        customs
+    }
+
+    set(&mut ui, 7);
+    let expected = vec![(ids[1], Rect::from_xy_dim([0.0, 0.0], [20.0, 10.0]), 7, 0.5)];
+    assert_eq!(customs(ui.draw()), expected);
+    let owned = ui.draw().owned();
+    assert_eq!(customs(owned.walk()), expected);
+
+    // The widget only changes when given different data.
+    set(&mut ui, 7);
+    assert!(!ui.changed_widgets().contains(&ids[1]));
+    set(&mut ui, 8);
+    assert!(ui.changed_widgets().contains(&ids[1]));
+    assert_eq!(customs(ui.draw())[0].2, 8);
+}
+
+#[test]
+fn crop_shapes_should_produce_clips_for_the_primitives_of_cropped_widgets() {
+    let mut ui = UiBuilder::new([800.0, 600.0]).build();
+    let ids = [
+        ui.widget_id_generator().next(),
+        ui.widget_id_generator().next(),
+        ui.widget_id_generator().next(),
+        ui.widget_id_generator().next(),
+    ];
+    let shape = widget::CropShape::RoundedRectangle { radius: 10.0 };
+    {
+        let ui = &mut ui.set_widgets();
+        widget::Rectangle::fill([100.0, 50.0])
+            .x_y(0.0, 0.0)
+            .crop_kids_to_shape(shape)
+            .set(ids[0], ui);
+        widget::Rectangle::fill([200.0, 200.0])
+            .middle_of(ids[0])
+            .parent(ids[0])
+            .set(ids[1], ui);
+        // Rectangular crops that are rotated cannot be described by the scizzor alone.
+        widget::Rectangle::fill([100.0, 50.0])
+            .x_y(200.0, 0.0)
+            .parent(ui.window)
+            .rotate(1.0)
+            .crop_kids()
+            .set(ids[2], ui);
+        widget::Rectangle::fill([200.0, 200.0])
+            .middle_of(ids[2])
+            .parent(ids[2])
+            .set(ids[3], ui);
+    }
+
+    // Collect the id and clips of each primitive of the given widgets.
+    fn clips<P>(mut primitives: P, ids: &[widget::Id]) -> Vec<(widget::Id, Vec<render::Clip>)>
+    where
+        P: PrimitiveWalker,
+    {
+        let mut clips = vec![];
+        while let Some(primitive) = primitives.next_primitive() {
+            if ids.contains(&primitive.id) {
+                clips.push((primitive.id, primitive.clips.to_vec()));
+            }
+        }
+        clips
+    }
+
+    let clips_of_primitives = clips(ui.draw(), &ids);
+    assert_eq!(clips_of_primitives.len(), 4);
+    assert!(clips_of_primitives[0].1.is_empty());
+    assert!(clips_of_primitives[2].1.is_empty());
+    let rounded = clips_of_primitives[1].1[0];
+    assert_eq!(clips_of_primitives[1].1.len(), 1);
+    assert_eq!(rounded.id, ids[0]);
+    assert_eq!(rounded.shape, shape);
+    assert_eq!(rounded.rect, Rect::from_xy_dim([0.0, 0.0], [100.0, 50.0]));
+    let rotated = clips_of_primitives[3].1[0];
+    assert_eq!(rotated.shape, widget::CropShape::Rectangle);
+    assert!(rotated.transform.is_some());
+    assert_eq!(clips(ui.draw().owned().walk(), &ids), clips_of_primitives);
+
+    // Points within the rounded corners lie outside of the clip.
+    assert!(rounded.contains([0.0, 0.0]));
+    assert!(rounded.contains([45.0, 20.0]));
+    assert!(!rounded.contains([49.0, 24.0]));
+    assert!(!rounded.contains([60.0, 0.0]));
+    let area = area(&rounded.triangles());
+    let expected = 100.0 * 50.0 - (4.0 - std::f64::consts::PI) * 10.0 * 10.0;
+    assert!((area - expected).abs() < 1.0);
+}
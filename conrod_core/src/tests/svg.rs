@@ -0,0 +1,111 @@
+use svg::{Svg, TextMode};
+use text;
+use widget;
+use {color, Colorable, Positionable, Sizeable, Ui, UiBuilder, Widget};
+
+///// Test assist code.
+
+// A `Ui` containing a red square cropped by a canvas, along with some text.
+fn ui_with_cropped_square_and_text() -> (Ui, text::font::Id) {
+    let mut ui = UiBuilder::new([200.0, 100.0]).build();
+    let path = concat!(
+        env!("CARGO_MANIFEST_DIR"),
+        "/../assets/fonts/NotoSans/NotoSans-Regular.ttf"
+    );
+    let font_id = ui.fonts.insert_from_file(path).unwrap();
+    let ids = [
+        ui.widget_id_generator().next(),
+        ui.widget_id_generator().next(),
+        ui.widget_id_generator().next(),
+    ];
+    {
+        let ui = &mut ui.set_widgets();
+        widget::Canvas::new()
+            .w_h(50.0, 50.0)
+            .top_left()
+            .crop_kids()
+            .set(ids[0], ui);
+        widget::Rectangle::fill([100.0, 100.0])
+            .color(color::RED)
+            .top_left_of(ids[0])
+            .parent(ids[0])
+            .set(ids[1], ui);
+        widget::Text::new("a < b")
+            .font_id(font_id)
+            .bottom_right()
+            .set(ids[2], ui);
+    }
+    (ui, font_id)
+}
+
+///// Actual tests.
+
+#[test]
+fn svg_should_describe_primitives_with_clip_paths() {
+    let (ui, font_id) = ui_with_cropped_square_and_text();
+    let svg = Svg::new([200.0, 100.0])
+        .font_family(font_id, "Noto Sans")
+        .to_string(ui.draw());
+
+    assert!(svg.starts_with("<svg xmlns=\"http://www.w3.org/2000/svg\""));
+    assert!(svg.trim_end().ends_with("</svg>"));
+    assert!(
+        svg.contains("<clipPath id=\"clip1\">\n<rect x=\"0\" y=\"0\" width=\"50\" height=\"50\"/>")
+    );
+    assert!(
+        svg.contains("<rect x=\"0\" y=\"0\" width=\"100\" height=\"100\" fill=\"rgb(204,0,0)\"/>")
+    );
+    assert!(svg.contains("font-family=\"Noto Sans\""));
+    assert!(svg.contains(">a &lt; b</text>"));
+    assert_eq!(svg.matches("<g ").count(), svg.matches("</g>").count());
+
+    // The same primitives should produce the same document once owned.
+    let owned = ui.draw().owned();
+    let owned_svg = Svg::new([200.0, 100.0])
+        .font_family(font_id, "Noto Sans")
+        .to_string(owned.walk());
+    assert_eq!(svg, owned_svg);
+}
+
+#[test]
+fn svg_glyph_outlines_should_replace_text_elements() {
+    let (ui, _) = ui_with_cropped_square_and_text();
+    let svg = Svg::new([200.0, 100.0])
+        .text_mode(TextMode::Outlines)
+        .to_string(ui.draw());
+    assert!(!svg.contains("<text"));
+    let glyph_paths: Vec<_> = svg.lines().filter(|l| l.contains('Q')).collect();
+    assert_eq!(glyph_paths.len(), 1);
+    assert!(glyph_paths[0].starts_with("<path d=\"M"));
+}
+
+#[test]
+fn svg_should_clip_to_crop_shapes() {
+    let mut ui = UiBuilder::new([200.0, 100.0]).build();
+    let ids = [
+        ui.widget_id_generator().next(),
+        ui.widget_id_generator().next(),
+    ];
+    {
+        let ui = &mut ui.set_widgets();
+        widget::Canvas::new()
+            .w_h(50.0, 50.0)
+            .top_left()
+            .pad(0.0)
+            .crop_kids_to_shape(widget::CropShape::RoundedRectangle { radius: 10.0 })
+            .set(ids[0], ui);
+        widget::Rectangle::fill([100.0, 100.0])
+            .top_left_of(ids[0])
+            .parent(ids[0])
+            .set(ids[1], ui);
+    }
+    let svg = Svg::new([200.0, 100.0]).to_string(ui.draw());
+
+    // The rounded clip path is itself clipped by the scizzor.
+    assert!(svg.contains(
+        "<clipPath id=\"clip2\" clip-path=\"url(#clip1)\">\n\
+         <rect x=\"0\" y=\"0\" width=\"50\" height=\"50\" rx=\"10\" ry=\"10\"/>"
+    ));
+    assert!(svg.contains("<g clip-path=\"url(#clip2)\">"));
+    assert_eq!(svg.matches("<g ").count(), svg.matches("</g>").count());
+}
@@ -0,0 +1,117 @@
+use event::Input;
+use input::{Button, Key};
+use render::{self, PrimitiveKind};
+use text;
+use widget;
+use widget::text::Highlight;
+use {color, Colorable, Positionable, Sizeable, Ui, UiBuilder, Widget};
+
+///// Test assist code.
+
+fn ui_with_font() -> (Ui, text::font::Id) {
+    let mut ui = UiBuilder::new([800.0, 600.0]).build();
+    let path = concat!(
+        env!("CARGO_MANIFEST_DIR"),
+        "/../assets/fonts/NotoSans/NotoSans-Regular.ttf"
+    );
+    let font_id = ui.fonts.insert_from_file(path).unwrap();
+    (ui, font_id)
+}
+
+// Collect the highlighted glyphs of the first `Text` primitive.
+fn highlighted_glyphs(primitives: &mut render::Primitives) -> Vec<render::HighlightedGlyph> {
+    while let Some(primitive) = primitives.next() {
+        if let PrimitiveKind::Text { text, .. } = primitive.kind {
+            return text.highlighted_glyphs(1.0).collect();
+        }
+    }
+    vec![]
+}
+
+// Give a `TextEdit` keyboard capture, then return its event for the given input.
+fn text_edit_event(read_only: bool, input: Input) -> Option<String> {
+    let (mut ui, font_id) = ui_with_font();
+    let id = ui.widget_id_generator().next();
+    let set = |ui: &mut Ui| {
+        widget::TextEdit::new("foo")
+            .read_only(read_only)
+            .font_id(font_id)
+            .w_h(200.0, 50.0)
+            .middle()
+            .set(id, &mut ui.set_widgets())
+    };
+    set(&mut ui);
+    ui.keyboard_capture(id);
+    ui.handle_event(input);
+    set(&mut ui)
+}
+
+///// Actual tests.
+
+#[test]
+fn text_highlights_should_colour_glyphs_within_their_byte_range() {
+    let (mut ui, font_id) = ui_with_font();
+    let text_id = ui.widget_id_generator().next();
+    let highlights = [
+        Highlight {
+            byte_range: 0..3,
+            color: Some(color::BLUE),
+            font_id: Some(font_id),
+        },
+        Highlight {
+            byte_range: 4..7,
+            color: Some(color::RED),
+            font_id: None,
+        },
+    ];
+
+    {
+        let ui = &mut ui.set_widgets();
+        widget::Text::new("let foo = 1;")
+            .highlights(&highlights)
+            .color(color::BLACK)
+            .middle()
+            .set(text_id, ui);
+    }
+
+    let glyphs = highlighted_glyphs(&mut ui.draw());
+    let colors: Vec<_> = glyphs.iter().map(|g| g.color).collect();
+    assert_eq!(glyphs.len(), 12);
+    assert_eq!(&colors[..3], &[Some(color::BLUE); 3]);
+    assert_eq!(colors[3], None);
+    assert_eq!(&colors[4..7], &[Some(color::RED); 3]);
+    assert!(colors[7..].iter().all(Option::is_none));
+    assert_eq!(glyphs[0].font_id, Some(font_id));
+    assert_eq!(glyphs[4].font_id, None);
+
+    // Highlights should survive conversion to `OwnedPrimitives`, with their fonts resolved only
+    // when walked along with the font map.
+    let owned = ui.draw().owned();
+    let owned_glyphs = |mut walk: render::WalkOwnedPrimitives| {
+        let mut glyphs = vec![];
+        while let Some(primitive) = walk.next() {
+            if let PrimitiveKind::Text { text, .. } = primitive.kind {
+                glyphs.extend(text.highlighted_glyphs(1.0).map(|g| (g.color, g.font_id)));
+            }
+        }
+        glyphs
+    };
+    let expected: Vec<_> = glyphs.iter().map(|g| (g.color, g.font_id)).collect();
+    assert_eq!(owned_glyphs(owned.walk_with_fonts(&ui.fonts)), expected);
+    let without_fonts = owned_glyphs(owned.walk());
+    assert!(without_fonts.iter().all(|&(_, font_id)| font_id.is_none()));
+    assert_eq!(
+        without_fonts.iter().map(|g| g.0).collect::<Vec<_>>(),
+        colors
+    );
+}
+
+#[test]
+fn read_only_text_edit_should_ignore_edits() {
+    let typed = || Input::Text("bar".to_string());
+    let backspace = || Input::Press(Button::Keyboard(Key::Backspace));
+    assert!(text_edit_event(false, typed()).is_some());
+    assert!(text_edit_event(false, backspace()).is_some());
+    assert_eq!(text_edit_event(true, typed()), None);
+    assert_eq!(text_edit_event(true, backspace()), None);
+}
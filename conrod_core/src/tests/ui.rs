@@ -1,7 +1,9 @@
+use color;
 use event::{self, Input};
 use input::keyboard::ModifierKey;
 use input::{self, Button, Key, Motion, MouseButton};
-use position::Point;
+use position::{Point, Rect, Scalar};
+use std;
 use widget;
 use {Color, Colorable, Labelable, Positionable, Sizeable, Ui, UiBuilder, Widget};
 
@@ -182,3 +184,315 @@ fn high_level_scroll_event_should_be_created_from_a_raw_mouse_scroll() {
         assert_eq!(expected_scroll, scroll);
     }
 }
+
+#[test]
+fn transformed_widgets_should_be_picked_and_receive_untransformed_events() {
+    let ui = &mut windowless_ui();
+    let (canvas, rectangle) = {
+        let mut id_generator = ui.widget_id_generator();
+        (id_generator.next(), id_generator.next())
+    };
+    let set_widgets = |ui: &mut Ui| {
+        let ui = &mut ui.set_widgets();
+        widget::Canvas::new()
+            .w_h(400.0, 400.0)
+            .middle()
+            .rotate(std::f64::consts::FRAC_PI_2)
+            .set(canvas, ui);
+        widget::Rectangle::fill([100.0, 20.0])
+            .x_y_relative_to(canvas, 100.0, 0.0)
+            .parent(canvas)
+            .set(rectangle, ui);
+    };
+    set_widgets(ui);
+
+    // Laid out to the right of the canvas' centre, the rectangle is rotated to lie above it.
+    move_mouse_to_abs_coordinates(100.0, 0.0, ui);
+    assert_eq!(ui.global_input().current.widget_under_mouse, Some(canvas));
+    move_mouse_to_abs_coordinates(5.0, 120.0, ui);
+    assert_eq!(
+        ui.global_input().current.widget_under_mouse,
+        Some(rectangle)
+    );
+
+    // Events are given relative to the rectangle's centre within its untransformed space.
+    set_widgets(ui);
+    left_click_mouse(ui);
+    let click = ui.widget_input(rectangle).clicks().left().next().unwrap();
+    assert!((click.xy[0] - 20.0).abs() < 1e-9);
+    assert!((click.xy[1] - -5.0).abs() < 1e-9);
+    let mouse = ui.widget_input(rectangle).mouse().unwrap();
+    assert!(mouse.is_over());
+
+    // Scrolling is rotated back into the rectangle's untransformed space too.
+    set_widgets(ui);
+    ui.scroll_widget(rectangle, [0.0, 10.0]);
+    let scroll = ui.widget_input(rectangle).scrolls().next().unwrap();
+    assert!((scroll.x - 10.0).abs() < 1e-9);
+    assert!(scroll.y.abs() < 1e-9);
+}
+
+#[test]
+fn changed_widgets_should_only_contain_widgets_whose_rect_or_style_changed() {
+    let mut ui = windowless_ui();
+    let ids = [
+        ui.widget_id_generator().next(),
+        ui.widget_id_generator().next(),
+        ui.widget_id_generator().next(),
+    ];
+    let set = |ui: &mut Ui, color: Color, x: Scalar| {
+        let ui = &mut ui.set_widgets();
+        widget::Rectangle::fill([10.0, 10.0])
+            .color(color)
+            .x_y(0.0, 0.0)
+            .set(ids[0], ui);
+        widget::Rectangle::fill([10.0, 10.0])
+            .x_y(x, 20.0)
+            .set(ids[1], ui);
+        widget::Rectangle::fill([10.0, 10.0])
+            .x_y(0.0, 40.0)
+            .set(ids[2], ui);
+    };
+
+    // Widgets instantiated for the first time have always changed.
+    set(&mut ui, color::RED, 0.0);
+    assert!(ids.iter().all(|id| ui.changed_widgets().contains(id)));
+
+    set(&mut ui, color::RED, 0.0);
+    assert!(ids.iter().all(|id| !ui.changed_widgets().contains(id)));
+
+    set(&mut ui, color::BLUE, 10.0);
+    assert!(ui.changed_widgets().contains(&ids[0]));
+    assert!(ui.changed_widgets().contains(&ids[1]));
+    assert!(!ui.changed_widgets().contains(&ids[2]));
+}
+
+#[test]
+fn changing_only_the_opacity_transform_or_crop_shape_should_require_a_redraw() {
+    let mut ui = windowless_ui();
+    let id = ui.widget_id_generator().next();
+    let set = |ui: &mut Ui, opacity: f32, radians: Scalar, shape: widget::CropShape| {
+        let ui = &mut ui.set_widgets();
+        widget::Canvas::new()
+            .w_h(100.0, 100.0)
+            .opacity(opacity)
+            .rotate(radians)
+            .crop_kids_to_shape(shape)
+            .set(id, ui);
+    };
+    let assert_redrawn = |ui: &mut Ui, opacity, radians, shape| {
+        while ui.draw_if_changed().is_some() {}
+        set(ui, opacity, radians, shape);
+        assert!(ui.changed_widgets().contains(&id));
+        assert!(ui.draw_if_changed().is_some());
+    };
+
+    let rounded = widget::CropShape::RoundedRectangle { radius: 10.0 };
+    assert_redrawn(&mut ui, 1.0, 0.0, widget::CropShape::Rectangle);
+    assert_redrawn(&mut ui, 0.5, 0.0, widget::CropShape::Rectangle);
+    assert_redrawn(&mut ui, 0.5, 1.0, widget::CropShape::Rectangle);
+    assert_redrawn(&mut ui, 0.5, 1.0, rounded);
+
+    // Setting the same values again requires no redraw.
+    while ui.draw_if_changed().is_some() {}
+    set(&mut ui, 0.5, 1.0, rounded);
+    assert!(!ui.changed_widgets().contains(&id));
+    assert!(ui.draw_if_changed().is_none());
+}
+
+#[test]
+fn damage_region_should_bound_widgets_that_changed_since_the_last_draw() {
+    let mut ui = windowless_ui();
+    ui.set_damage_tracking_enabled(true);
+    let ids = [
+        ui.widget_id_generator().next(),
+        ui.widget_id_generator().next(),
+        ui.widget_id_generator().next(),
+        ui.widget_id_generator().next(),
+    ];
+    let set = |ui: &mut Ui, color: Color, x: Scalar, with_last: bool, opacity: f32| {
+        let ui = &mut ui.set_widgets();
+        widget::Rectangle::fill([10.0, 10.0])
+            .color(color)
+            .x_y(0.0, 0.0)
+            .opacity(opacity)
+            .set(ids[0], ui);
+        widget::Rectangle::fill([10.0, 10.0])
+            .x_y(x, 20.0)
+            .set(ids[1], ui);
+        if with_last {
+            widget::Rectangle::fill([10.0, 10.0])
+                .x_y(0.0, 40.0)
+                .set(ids[2], ui);
+        }
+        widget::Rectangle::fill([10.0, 10.0])
+            .x_y(60.0, 0.0)
+            .parent(ids[0])
+            .set(ids[3], ui);
+    };
+    let rect = |x: Scalar, y: Scalar| Rect::from_xy_dim([x, y], [10.0, 10.0]);
+
+    // The first stage damages the whole window.
+    set(&mut ui, color::RED, 0.0, true, 1.0);
+    let window = Rect::from_xy_dim([0.0, 0.0], [800.0, 600.0]);
+    assert_eq!(ui.damage_region(), Some(window));
+    ui.draw();
+
+    set(&mut ui, color::RED, 0.0, true, 1.0);
+    assert_eq!(ui.damage_region(), None);
+
+    // Both the previous and the new area of a moved widget are damaged.
+    set(&mut ui, color::RED, 30.0, true, 1.0);
+    assert_eq!(
+        ui.damage_region(),
+        Some(rect(0.0, 20.0).max(rect(30.0, 20.0)))
+    );
+
+    // Damage accumulates until the `Ui` is drawn.
+    set(&mut ui, color::BLUE, 30.0, true, 1.0);
+    let expected = rect(0.0, 0.0).max(rect(0.0, 20.0)).max(rect(30.0, 20.0));
+    assert_eq!(ui.damage_region(), Some(expected));
+    assert!(ui.draw_if_changed().is_some());
+
+    // Removed widgets damage the area they occupied.
+    set(&mut ui, color::BLUE, 30.0, false, 1.0);
+    assert_eq!(ui.damage_region(), Some(rect(0.0, 40.0)));
+    ui.draw();
+
+    // Changing only the opacity of a widget also damages the areas of its children.
+    set(&mut ui, color::BLUE, 30.0, false, 0.5);
+    assert_eq!(
+        ui.damage_region(),
+        Some(rect(0.0, 0.0).max(rect(60.0, 0.0)))
+    );
+
+    // Without damage tracking, the whole window is damaged whenever the `Ui` has changed.
+    ui.set_damage_tracking_enabled(false);
+    while ui.draw_if_changed().is_some() {}
+    assert_eq!(ui.damage_region(), None);
+    set(&mut ui, color::RED, 30.0, false, 0.5);
+    assert_eq!(ui.damage_region(), Some(window));
+}
+
+#[test]
+fn sub_ui_should_remap_input_into_the_embedded_ui() {
+    let mut ui = windowless_ui();
+    let mut sub_ui = UiBuilder::new([400.0, 200.0]).build();
+    let sub_ui_id = ui.widget_id_generator().next();
+    let button = sub_ui.widget_id_generator().next();
+    let image_id = ::image::Map::<()>::new().insert(());
+    let set = |ui: &mut Ui| {
+        let ui = &mut ui.set_widgets();
+        widget::SubUi::new(image_id, [400.0, 200.0])
+            .x_y(100.0, 50.0)
+            .w_h(200.0, 100.0)
+            .set(sub_ui_id, ui)
+    };
+    let set_sub = |sub_ui: &mut Ui| {
+        let ui = &mut sub_ui.set_widgets();
+        widget::Button::new()
+            .x_y(-100.0, 0.0)
+            .w_h(100.0, 50.0)
+            .set(button, ui)
+            .was_clicked()
+    };
+    set(&mut ui);
+    set_sub(&mut sub_ui);
+
+    // The embedded `Ui` is twice the size of the widget displaying it.
+    move_mouse_to_abs_coordinates(50.0, 60.0, &mut ui);
+    left_click_mouse(&mut ui);
+    let inputs = set(&mut ui);
+    let cursor = Input::Motion(Motion::MouseCursor { x: -100.0, y: 20.0 });
+    let expected = vec![
+        cursor.clone(),
+        cursor.clone(),
+        Input::Press(Button::Mouse(MouseButton::Left)),
+        cursor,
+        Input::Release(Button::Mouse(MouseButton::Left)),
+    ];
+    assert_eq!(inputs, expected);
+
+    for input in inputs {
+        sub_ui.handle_event(input);
+    }
+    assert!(set_sub(&mut sub_ui));
+}
+
+#[test]
+fn stats_should_count_the_work_done_during_each_frame() {
+    use image;
+    use mesh::{self, Mesh};
+
+    let mut ui = UiBuilder::new([400.0, 200.0]).build();
+    let ids = [
+        ui.widget_id_generator().next(),
+        ui.widget_id_generator().next(),
+    ];
+    let set_widgets = |ui: &mut Ui| {
+        let ui = &mut ui.set_widgets();
+        for &id in &ids {
+            widget::Rectangle::fill([10.0, 10.0])
+                .x_y(0.0, 0.0)
+                .parent(ui.window)
+                .set(id, ui);
+        }
+    };
+    assert_eq!(ui.stats(), None);
+    ui.set_stats_enabled(true);
+
+    // The first frame instantiates the window along with each widget.
+    set_widgets(&mut ui);
+    let stats = ui.stats().unwrap();
+    let widgets_updated = ui.updated_widgets().len();
+    assert!(widgets_updated > ids.len());
+    assert_eq!(stats.widgets_instantiated, widgets_updated);
+    assert_eq!(stats.widgets_updated, widgets_updated);
+    assert_eq!(stats.widgets_changed, widgets_updated);
+    assert_eq!(stats.primitives, 0);
+
+    // Each primitive yielded is counted.
+    let mut primitives = ui.draw();
+    let mut num_primitives = 0;
+    while primitives.next().is_some() {
+        num_primitives += 1;
+    }
+    assert!(num_primitives >= ids.len());
+    assert_eq!(ui.stats().unwrap().primitives, num_primitives);
+
+    // Filling a mesh records the primitives drawn to it along with the mesh's contents. The stats
+    // are reset first so that the durations only cover the fill.
+    ui.set_stats_enabled(false);
+    ui.set_stats_enabled(true);
+    let viewport = Rect::from_xy_dim([0.0, 0.0], [ui.win_w, ui.win_h]);
+    let image_map = image::Map::<[u32; 2]>::new();
+    let mut mesh = Mesh::new();
+    let fill = mesh.fill(viewport, 1.0, &image_map, ui.draw()).unwrap();
+    let stats = ui.stats().unwrap();
+    assert_eq!(stats.primitives, num_primitives);
+    assert_eq!(stats.vertices, mesh.vertices().len());
+    assert_eq!(stats.triangles, mesh.vertices().len() / 3);
+    let draw_commands = mesh
+        .commands()
+        .filter(|command| match *command {
+            mesh::Command::Draw(_) => true,
+            _ => false,
+        })
+        .count();
+    assert_eq!(stats.draw_commands, draw_commands);
+    assert_eq!(stats.vertices, fill.stats.vertices);
+    assert!(stats.fill_duration >= stats.draw_duration);
+
+    // Unchanged widgets are updated without being instantiated or changed.
+    set_widgets(&mut ui);
+    let stats = ui.stats().unwrap();
+    assert_eq!(stats.widgets_instantiated, 0);
+    assert_eq!(stats.widgets_updated, widgets_updated);
+    assert_eq!(stats.widgets_changed, 0);
+    assert_eq!(stats.primitives, 0);
+    assert_eq!(stats.vertices, 0);
+
+    ui.set_stats_enabled(false);
+    set_widgets(&mut ui);
+    assert_eq!(ui.stats(), None);
+}
@@ -9,7 +9,9 @@ pub use rusttype::{Glyph, GlyphId, GlyphIter, LayoutIter, Scale};
 
 /// Re-exported RustType geometrical types.
 pub mod rt {
-    pub use rusttype::{gpu_cache, point, vector, Point, Rect, Vector};
+    pub use rusttype::{
+        gpu_cache, point, vector, Contour, Curve, Line, Point, Rect, Segment, Vector,
+    };
 }
 
 /// The RustType `Font` type used by conrod.
@@ -1017,6 +1019,8 @@ pub mod line {
         start_char: usize,
         /// The break type of the previously yielded line
         last_break: Option<Break>,
+        /// Whether or not line widths are measured assuming a monospaced font.
+        monospace: bool,
     }
 
     /// An iterator yielding a `Rect` for each line in
@@ -1077,6 +1081,7 @@ pub mod line {
                 start_byte: self.start_byte,
                 start_char: self.start_char,
                 last_break: None,
+                monospace: self.monospace,
             }
         }
     }
@@ -1107,7 +1112,11 @@ pub mod line {
         /// Converts `Self` into an `Infos` whose lines are wrapped at the character that first
         /// causes the line width to exceed the given `max_width`.
         pub fn wrap_by_character(mut self, max_width: Scalar) -> Self {
-            self.next_break_fn = next_break_by_character;
+            self.next_break_fn = if self.monospace {
+                next_break_by_character_monospace
+            } else {
+                next_break_by_character
+            };
             self.max_width = max_width;
             self
         }
@@ -1115,7 +1124,11 @@ pub mod line {
         /// Converts `Self` into an `Infos` whose lines are wrapped at the whitespace prior to the
         /// character that causes the line width to exceed the given `max_width`.
         pub fn wrap_by_whitespace(mut self, max_width: Scalar) -> Self {
-            self.next_break_fn = next_break_by_whitespace;
+            self.next_break_fn = if self.monospace {
+                next_break_by_whitespace_monospace
+            } else {
+                next_break_by_whitespace
+            };
             self.max_width = max_width;
             self
         }
@@ -1148,10 +1161,18 @@ pub mod line {
     /// along with the width of the line.
     fn next_break(text: &str, font: &super::Font, font_size: FontSize) -> (Break, Scalar) {
         let scale = super::pt_to_scale(font_size);
+        let mut last_glyph = None;
+        next_break_with(text, |ch| advance_width(ch, font, scale, &mut last_glyph))
+    }
+
+    /// The same as `next_break`, but measures each character via the given `advance` function.
+    fn next_break_with<A>(text: &str, mut advance: A) -> (Break, Scalar)
+    where
+        A: FnMut(char) -> Scalar,
+    {
         let mut width = 0.0;
         let mut char_i = 0;
         let mut char_indices = text.char_indices().peekable();
-        let mut last_glyph = None;
         while let Some((byte_i, ch)) = char_indices.next() {
             // Check for a newline.
             if ch == '\r' {
@@ -1173,7 +1194,7 @@ pub mod line {
             }
 
             // Update the width.
-            width += advance_width(ch, font, scale, &mut last_glyph);
+            width += advance(ch);
             char_i += 1;
         }
         let break_ = Break::End {
@@ -1195,10 +1216,25 @@ pub mod line {
         max_width: Scalar,
     ) -> (Break, Scalar) {
         let scale = super::pt_to_scale(font_size);
+        let mut last_glyph = None;
+        next_break_by_character_with(text, max_width, |ch| {
+            advance_width(ch, font, scale, &mut last_glyph)
+        })
+    }
+
+    /// The same as `next_break_by_character`, but measures each character via the given `advance`
+    /// function.
+    fn next_break_by_character_with<A>(
+        text: &str,
+        max_width: Scalar,
+        mut advance: A,
+    ) -> (Break, Scalar)
+    where
+        A: FnMut(char) -> Scalar,
+    {
         let mut width = 0.0;
         let mut char_i = 0;
         let mut char_indices = text.char_indices().peekable();
-        let mut last_glyph = None;
         while let Some((byte_i, ch)) = char_indices.next() {
             // Check for a newline.
             if ch == '\r' {
@@ -1220,7 +1256,7 @@ pub mod line {
             }
 
             // Add the character's width to the width so far.
-            let new_width = width + advance_width(ch, font, scale, &mut last_glyph);
+            let new_width = width + advance(ch);
 
             // Check for a line wrap.
             if new_width > max_width {
@@ -1257,17 +1293,32 @@ pub mod line {
         font_size: FontSize,
         max_width: Scalar,
     ) -> (Break, Scalar) {
+        let scale = super::pt_to_scale(font_size);
+        let mut last_glyph = None;
+        next_break_by_whitespace_with(text, max_width, |ch| {
+            advance_width(ch, font, scale, &mut last_glyph)
+        })
+    }
+
+    /// The same as `next_break_by_whitespace`, but measures each character via the given
+    /// `advance` function.
+    fn next_break_by_whitespace_with<A>(
+        text: &str,
+        max_width: Scalar,
+        mut advance: A,
+    ) -> (Break, Scalar)
+    where
+        A: FnMut(char) -> Scalar,
+    {
         struct Last {
             byte: usize,
             char: usize,
             width_before: Scalar,
         }
-        let scale = super::pt_to_scale(font_size);
         let mut last_whitespace_start = None;
         let mut width = 0.0;
         let mut char_i = 0;
         let mut char_indices = text.char_indices().peekable();
-        let mut last_glyph = None;
         while let Some((byte_i, ch)) = char_indices.next() {
             // Check for a newline.
             if ch == '\r' {
@@ -1289,7 +1340,7 @@ pub mod line {
             }
 
             // Add the character's width to the width so far.
-            let new_width = width + advance_width(ch, font, scale, &mut last_glyph);
+            let new_width = width + advance(ch);
 
             // Check for a line wrap.
             if width > max_width {
@@ -1343,6 +1394,68 @@ pub mod line {
         (break_, width)
     }
 
+    /// The advance width of a single glyph within a monospaced font.
+    ///
+    /// This is used by the monospace `next_break` functions below in place of per-glyph metrics.
+    fn monospace_glyph_width(font: &super::Font, font_size: FontSize) -> Scalar {
+        let scale = super::pt_to_scale(font_size);
+        font.glyph(' ').scaled(scale).h_metrics().advance_width as Scalar
+    }
+
+    /// The same as `next_break`, but assumes every character shares the same advance width.
+    fn next_break_monospace(
+        text: &str,
+        font: &super::Font,
+        font_size: FontSize,
+        _max_width: Scalar,
+    ) -> (Break, Scalar) {
+        let glyph_width = monospace_glyph_width(font, font_size);
+        next_break_with(text, |_| glyph_width)
+    }
+
+    /// The same as `next_break_by_character`, but assumes every character shares the same advance
+    /// width.
+    fn next_break_by_character_monospace(
+        text: &str,
+        font: &super::Font,
+        font_size: FontSize,
+        max_width: Scalar,
+    ) -> (Break, Scalar) {
+        let glyph_width = monospace_glyph_width(font, font_size);
+        next_break_by_character_with(text, max_width, |_| glyph_width)
+    }
+
+    /// The same as `next_break_by_whitespace`, but assumes every character shares the same advance
+    /// width.
+    fn next_break_by_whitespace_monospace(
+        text: &str,
+        font: &super::Font,
+        font_size: FontSize,
+        max_width: Scalar,
+    ) -> (Break, Scalar) {
+        let glyph_width = monospace_glyph_width(font, font_size);
+        next_break_by_whitespace_with(text, max_width, |_| glyph_width)
+    }
+
+    /// Returns the advance width shared by every glyph within the given `font` if it is
+    /// monospaced.
+    ///
+    /// Returns `None` if the font is proportional or if any glyphs are kerned. Fonts for which this
+    /// returns `Some` may be laid out via the cheaper [infos_monospace](./fn.infos_monospace.html)
+    /// path, producing exactly the same line widths as [infos](./fn.infos.html).
+    pub fn monospace_advance_width(font: &super::Font, font_size: FontSize) -> Option<Scalar> {
+        const SAMPLE: &str = " iIlmMW0._";
+        let scale = super::pt_to_scale(font_size);
+        let glyph_width = monospace_glyph_width(font, font_size);
+        let mut last_glyph = None;
+        for ch in SAMPLE.chars() {
+            if advance_width(ch, font, scale, &mut last_glyph) != glyph_width {
+                return None;
+            }
+        }
+        Some(glyph_width)
+    }
+
     /// Produce the width of the given line of text including spaces (i.e. ' ').
     pub fn width(text: &str, font: &super::Font, font_size: FontSize) -> Scalar {
         let scale = super::Scale::uniform(super::pt_to_px(font_size));
@@ -1379,6 +1492,7 @@ pub mod line {
             start_byte: 0,
             start_char: 0,
             last_break: None,
+            monospace: false,
         }
     }
 
@@ -1403,6 +1517,31 @@ pub mod line {
         infos_wrapped_by(text, font, font_size, std::f64::MAX, no_wrap)
     }
 
+    /// Produce an `Infos` iterator that yields an `Info` for every line in the given text, assuming
+    /// that the given `font` is monospaced.
+    ///
+    /// Rather than looking up the metrics and kerning for every glyph, the width of each line is
+    /// measured by its number of characters. Wrapping via the `wrap_by_character` and
+    /// `wrap_by_whitespace` builder methods retains this behaviour.
+    ///
+    /// This is only accurate for fonts for which
+    /// [monospace_advance_width](./fn.monospace_advance_width.html) returns `Some`.
+    pub fn infos_monospace<'a>(
+        text: &'a str,
+        font: &'a super::Font,
+        font_size: FontSize,
+    ) -> Infos<'a, NextBreakFnPtr> {
+        let mut infos = infos_wrapped_by(
+            text,
+            font,
+            font_size,
+            std::f64::MAX,
+            next_break_monospace as NextBreakFnPtr,
+        );
+        infos.monospace = true;
+        infos
+    }
+
     /// Produce an iterator yielding the bounding `Rect` for each line in the text.
     ///
     /// This function assumes that `font_size` is the same `FontSize` used to produce the `Info`s
@@ -1492,6 +1631,7 @@ pub mod line {
                 ref mut start_byte,
                 ref mut start_char,
                 ref mut last_break,
+                ..
             } = *self;
 
             match next_break_fn(&text[*start_byte..], font, font_size, max_width) {
@@ -2,7 +2,7 @@
 //! Types a functionality for handling Canvas and Widget theming.
 //!
 
-use color::{Color, BLACK, WHITE};
+use color::{self, Color, BLACK, WHITE};
 use fnv;
 use position::{Align, Direction, Padding, Position, Relative};
 use std;
@@ -16,6 +16,9 @@ use Scalar;
 pub type StyleMap = fnv::FnvHashMap<std::any::TypeId, WidgetDefault>;
 
 /// A serializable collection of canvas and widget styling defaults.
+///
+/// Fields may be added to the **Theme** over time, so prefer constructing it from the defaults,
+/// e.g. `Theme { anti_aliasing: Some(1.0), ..Theme::default() }`.
 #[derive(Debug)]
 pub struct Theme {
     /// A name for the theme used for identification.
@@ -53,6 +56,12 @@ pub struct Theme {
     /// Once the `Duration` that separates two consecutive `Click`s is greater than this value, a
     /// `DoubleClick` event will no longer be generated.
     pub double_click_threshold: std::time::Duration,
+    /// When `Some`, the edges of ovals, polygons, lines and point paths are anti-aliased by
+    /// surrounding them with a fringe of the given width within which their alpha falls off to
+    /// zero. This allows for smooth edges on backends that do not use multisampling.
+    ///
+    /// A width of around `1.0` works well for most displays.
+    pub anti_aliasing: Option<Scalar>,
 }
 
 /// The defaults for a specific widget.
@@ -103,6 +112,29 @@ impl Theme {
             widget_styling: fnv::FnvHashMap::default(),
             mouse_drag_threshold: 0.0,
             double_click_threshold: std::time::Duration::from_millis(500),
+            anti_aliasing: None,
+        }
+    }
+
+    /// A theme generated from the given `accent` color, which is used for the shapes of widgets.
+    ///
+    /// The label color is whichever of black or white contrasts the `accent` the most, while the
+    /// background is a very light or very dark tint of the accent's hue, opposite to the label.
+    /// Labels therefore meet the WCAG contrast ratio of 4.5 recommended for normal text against
+    /// both widget shapes and the background, provided the `accent` is opaque.
+    pub fn from_accent(accent: Color) -> Theme {
+        let accent = accent.with_alpha(1.0);
+        let label_color = accent.plain_contrast();
+        let color::Oklcha(_, chroma, hue, _) = accent.to_oklch();
+        let background_lightness = if label_color == WHITE { 0.2 } else { 0.96 };
+        let background_color = color::oklcha(background_lightness, chroma.min(0.04), hue, 1.0);
+        Theme {
+            name: "Accent Theme".to_string(),
+            background_color: background_color,
+            shape_color: accent,
+            border_color: label_color,
+            label_color: label_color,
+            ..Theme::default()
         }
     }
 
@@ -7,7 +7,7 @@ use input;
 use position::{self, Align, Dimensions, Direction, Padding, Point, Position, Range, Rect, Scalar};
 use render;
 use std;
-use std::sync::atomic::{self, AtomicUsize};
+use std::sync::atomic::{self, AtomicBool, AtomicUsize};
 use text;
 use theme::Theme;
 use utils;
@@ -77,6 +77,20 @@ pub struct Ui {
     /// We use this to compare against the newly generated `updated_widgets` to see whether or not
     /// we require re-drawing.
     prev_updated_widgets: fnv::FnvHashSet<widget::Id>,
+    /// The set of updated widgets whose rect, style or state has changed since the beginning of
+    /// the `set_widgets` stage.
+    changed_widgets: fnv::FnvHashSet<widget::Id>,
+    /// The visible area and inherited appearance of each updated widget as of the end of the most
+    /// recent `set_widgets` stage.
+    ///
+    /// This is `None` unless damage tracking is enabled via `Ui::set_damage_tracking_enabled`.
+    visible_widgets: Option<fnv::FnvHashMap<widget::Id, VisibleWidget>>,
+    /// The union of the areas of the window that have changed since the last call to `Ui::draw`.
+    damage_region: Option<Rect>,
+    /// Whether or not `Ui::draw` has been called since the `damage_region` was last accumulated.
+    ///
+    /// This is atomic as `draw` only borrows the `Ui` immutably.
+    damage_drawn: AtomicBool,
     /// Scroll events that have been emitted during a call to `Ui::set_widgets`. These are usually
     /// emitted by some widget like the `Scrollbar`.
     ///
@@ -86,6 +100,12 @@ pub struct Ui {
     pending_scroll_events: Vec<event::Ui>,
     /// Mouse cursor
     mouse_cursor: cursor::MouseCursor,
+    /// The stats recorded for the current frame, if enabled.
+    ///
+    /// This is a `Mutex` as the `Primitives` produced by `draw` record into it.
+    stats: Option<std::sync::Mutex<Stats>>,
+    /// The moment at which the current `set_widgets` stage began, if stats are enabled.
+    set_widgets_start: Option<std::time::Instant>,
 
     // TODO: Remove the following fields as they should now be handled by `input::Global`.
     /// Window width.
@@ -94,6 +114,52 @@ pub struct Ui {
     pub win_h: f64,
 }
 
+/// Counts and timings describing the work done by the `Ui` during a single frame.
+///
+/// A frame begins with a call to `Ui::set_widgets` and includes all drawing that occurs before
+/// the next call. Stats are only recorded once enabled via `Ui::set_stats_enabled`.
+///
+/// The counts and timings of `mesh::Mesh`es are recorded when they are filled from the
+/// `Primitives` produced by `Ui::draw`, as is the case for most backends.
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+pub struct Stats {
+    /// The number of widgets instantiated for the first time, producing their initial state.
+    pub widgets_instantiated: usize,
+    /// The number of widgets updated during the `set_widgets` stage, including the window.
+    pub widgets_updated: usize,
+    /// The number of updated widgets whose `Rect`, style or state changed.
+    pub widgets_changed: usize,
+    /// The number of primitives yielded by `Primitives` produced by `Ui::draw`.
+    pub primitives: usize,
+    /// The number of vertices within the filled meshes.
+    pub vertices: usize,
+    /// The number of triangles within the filled meshes.
+    pub triangles: usize,
+    /// The number of draw commands produced by the filled meshes.
+    pub draw_commands: usize,
+    /// The number of scizzor commands produced by the filled meshes.
+    pub scizzor_changes: usize,
+    /// The number of times the glyph cache required uploading to the GPU.
+    pub glyph_cache_uploads: usize,
+    /// The time spent within the `set_widgets` stage, up until the `UiCell` is dropped.
+    pub set_widgets_duration: std::time::Duration,
+    /// The time spent producing primitives.
+    pub draw_duration: std::time::Duration,
+    /// The time spent filling meshes, measured from the first primitive requested by each mesh.
+    ///
+    /// This includes the time spent producing the primitives that were drawn to the meshes.
+    pub fill_duration: std::time::Duration,
+}
+
+/// The visible area of a widget in window space along with the opacity and transform inherited
+/// from its depth parents, any change to which damages the area.
+#[derive(Copy, Clone, Debug, PartialEq)]
+struct VisibleWidget {
+    rect: Rect,
+    opacity: f32,
+    transform: Option<position::Transform>,
+}
+
 /// A wrapper around the `Ui` that restricts the user from mutating the `Ui` in certain ways while
 /// in the scope of the `Ui::set_widgets` function and within `Widget`s' `update` methods. Using
 /// the `UiCell`, users may access the `Ui` immutably (via `Deref`) however they wish, however they
@@ -191,6 +257,7 @@ impl Ui {
 
         let window = widget_graph.add_placeholder();
         let prev_updated_widgets = updated_widgets.clone();
+        let changed_widgets = updated_widgets.clone();
         Ui {
             widget_graph: widget_graph,
             theme: maybe_theme.unwrap_or_else(|| Theme::default()),
@@ -206,9 +273,15 @@ impl Ui {
             depth_order: depth_order,
             updated_widgets: updated_widgets,
             prev_updated_widgets: prev_updated_widgets,
+            changed_widgets: changed_widgets,
+            visible_widgets: None,
+            damage_region: None,
+            damage_drawn: AtomicBool::new(false),
             global_input: input::Global::new(),
             pending_scroll_events: Vec::new(),
             mouse_cursor: cursor::MouseCursor::Arrow,
+            stats: None,
+            set_widgets_start: None,
         }
     }
 
@@ -222,7 +295,11 @@ impl Ui {
             let bottom_edge = self.win_h / 2.0;
             Rect::from_xy_dim([right_edge, bottom_edge], [0.0, 0.0])
         });
-        input::Widget::for_widget(widget, rect, &self.global_input)
+        let widget_input = input::Widget::for_widget(widget, rect, &self.global_input);
+        match graph::algo::transform_of_widget(&self.widget_graph, widget) {
+            Some(transform) => widget_input.transform(transform),
+            None => widget_input,
+        }
     }
 
     /// The **Rect** for the widget at the given index.
@@ -295,6 +372,41 @@ impl Ui {
         &self.prev_updated_widgets
     }
 
+    /// Borrow the **Ui**'s set of changed widgets.
+    ///
+    /// This set indicates which of the `updated_widgets` have changed in some visible way (i.e.
+    /// their `Rect`, depth, `Style`, state or scroll offset) since the beginning of the most recent
+    /// `Ui::set_widgets` call. Widgets instantiated for the first time are always considered to
+    /// have changed.
+    pub fn changed_widgets(&self) -> &fnv::FnvHashSet<widget::Id> {
+        &self.changed_widgets
+    }
+
+    /// The area of the window that has changed since the last call to `Ui::draw` (or to
+    /// `Ui::draw_if_changed` when it draws), or `None` if nothing visible has changed.
+    ///
+    /// This is the union of the visible areas of every widget that has been added, removed or
+    /// changed in some visible way during the `set_widgets` stages since the last draw, both
+    /// before and after the change. It is given in the same coordinates as the widgets' `Rect`s
+    /// and is bounded by the window's `Rect`.
+    ///
+    /// Backends and software renderers may use this to redraw and present only the damaged area
+    /// of the window. Note that when buffers are being swapped, each buffer must also be updated
+    /// with the damage of the frames drawn to the other buffers since it was last presented.
+    ///
+    /// Redraws requested manually via `Ui::needs_redraw` are not reflected in the damage region.
+    ///
+    /// Damage is only tracked once enabled via `Ui::set_damage_tracking_enabled`. Until then, this
+    /// is the whole window whenever `Ui::has_changed` returns `true`.
+    pub fn damage_region(&self) -> Option<Rect> {
+        let window = Rect::from_xy_dim([0.0, 0.0], [self.win_w, self.win_h]);
+        match self.visible_widgets {
+            Some(_) => self.damage_region.and_then(|rect| rect.overlap(window)),
+            None if self.has_changed() => Some(window),
+            None => None,
+        }
+    }
+
     /// Produces a type that may be used to generate new unique `widget::Id`s.
     ///
     /// See the [**widget::id::Generator**](../widget/id/struct.Generator.html) docs for details on
@@ -1139,16 +1251,26 @@ impl Ui {
         self.maybe_prev_widget_id = None;
         self.maybe_current_parent_id = None;
 
+        // Begin recording the stats of a new frame.
+        if let Some(stats) = self.stats_mut() {
+            *stats = Stats::default();
+        }
+        if self.stats.is_some() {
+            self.set_widgets_start = Some(std::time::Instant::now());
+        }
+
         // Move the previous `updated_widgets` to `prev_updated_widgets` and clear
         // `updated_widgets` so that we're ready to store the newly updated widgets.
         {
             let Ui {
                 ref mut updated_widgets,
                 ref mut prev_updated_widgets,
+                ref mut changed_widgets,
                 ..
             } = *self;
             std::mem::swap(updated_widgets, prev_updated_widgets);
             updated_widgets.clear();
+            changed_widgets.clear();
         }
 
         let mut ui_cell = UiCell { ui: self };
@@ -1186,6 +1308,46 @@ impl Ui {
         self.num_redraw_frames = num_frames;
     }
 
+    /// Enable or disable the tracking of the `damage_region`.
+    ///
+    /// Damage tracking is disabled by default, as it requires finding the visible area of every
+    /// updated widget at the end of each `set_widgets` stage. While disabled, the `damage_region`
+    /// is the whole window whenever the `Ui` has changed.
+    pub fn set_damage_tracking_enabled(&mut self, enabled: bool) {
+        match (enabled, self.visible_widgets.is_some()) {
+            (true, false) => self.visible_widgets = Some(fnv::FnvHashMap::default()),
+            (false, _) => self.visible_widgets = None,
+            _ => (),
+        }
+        self.damage_region = None;
+    }
+
+    /// Enable or disable the recording of `Stats` for each frame.
+    ///
+    /// Stats are disabled by default, in which case no counting or timing occurs.
+    pub fn set_stats_enabled(&mut self, enabled: bool) {
+        match (enabled, self.stats.is_some()) {
+            (true, false) => self.stats = Some(std::sync::Mutex::new(Stats::default())),
+            (false, _) => {
+                self.stats = None;
+                self.set_widgets_start = None;
+            }
+            _ => (),
+        }
+    }
+
+    /// The `Stats` recorded since the beginning of the most recent `set_widgets` stage.
+    ///
+    /// Returns `None` if stats are not enabled.
+    pub fn stats(&self) -> Option<Stats> {
+        self.stats.as_ref().map(|stats| *stats.lock().unwrap())
+    }
+
+    // The stats of the current frame, if enabled.
+    fn stats_mut(&mut self) -> Option<&mut Stats> {
+        self.stats.as_mut().map(|stats| stats.get_mut().unwrap())
+    }
+
     /// Tells the `Ui` that it needs to re-draw everything. It does this by setting the redraw
     /// count to `num_redraw_frames`. See the docs for `set_num_redraw_frames`, SAFE_REDRAW_COUNT
     /// or `draw_if_changed` for more info on how/why the redraw count is used.
@@ -1228,7 +1390,15 @@ impl Ui {
             redraw_count.store(remaining_redraws - 1, atomic::Ordering::Relaxed);
         }
 
-        render::Primitives::new(widget_graph, indices, theme, fonts, [win_w, win_h])
+        // The damage accumulated so far will have been drawn.
+        self.damage_drawn.store(true, atomic::Ordering::Relaxed);
+
+        let primitives =
+            render::Primitives::new(widget_graph, indices, theme, fonts, [win_w, win_h]);
+        match self.stats {
+            Some(ref stats) => primitives.record_stats(stats),
+            None => primitives,
+        }
     }
 
     /// Same as the `Ui::draw` method, but *only* draws if the `redraw_count` is greater than 0.
@@ -1276,6 +1446,78 @@ impl Ui {
     pub fn mouse_cursor(&self) -> cursor::MouseCursor {
         self.mouse_cursor
     }
+
+    /// Add the visible areas of all widgets that were added, removed or changed during the most
+    /// recent `set_widgets` stage to the `damage_region`, if damage tracking is enabled.
+    ///
+    /// The `damage_region` is first reset if it has been drawn since it was last updated.
+    ///
+    /// Returns whether or not any damage was added.
+    fn update_damage_region(&mut self) -> bool {
+        let Ui {
+            ref widget_graph,
+            ref updated_widgets,
+            ref prev_updated_widgets,
+            ref changed_widgets,
+            ref mut visible_widgets,
+            ref mut damage_region,
+            ref damage_drawn,
+            ..
+        } = *self;
+
+        let visible_widgets = match *visible_widgets {
+            Some(ref mut visible_widgets) => visible_widgets,
+            None => return false,
+        };
+
+        if damage_drawn.swap(false, atomic::Ordering::Relaxed) {
+            *damage_region = None;
+        }
+
+        let mut damaged = false;
+        let mut damage = |rect: Rect| {
+            if rect.w() > 0.0 && rect.h() > 0.0 {
+                *damage_region = Some(damage_region.map_or(rect, |region| region.max(rect)));
+                damaged = true;
+            }
+        };
+
+        // Widgets that are no longer instantiated leave behind the area that they occupied.
+        for id in prev_updated_widgets.difference(updated_widgets) {
+            if let Some(prev) = visible_widgets.remove(id) {
+                damage(prev.rect);
+            }
+        }
+
+        // A widget's appearance may also change without the widget itself changing, e.g. when the
+        // opacity or transform of one of its parents changes or when it is scrolled out of view.
+        for &id in updated_widgets {
+            let visible = graph::algo::cropped_area_of_widget(widget_graph, id).map(|rect| {
+                let transform = graph::algo::transform_of_widget(widget_graph, id);
+                let rect = match transform {
+                    Some(ref transform) => transform.bounding_rect(rect),
+                    None => rect,
+                };
+                let opacity = graph::algo::opacity_of_widget(widget_graph, id);
+                VisibleWidget {
+                    rect,
+                    opacity,
+                    transform,
+                }
+            });
+            let prev = match visible {
+                Some(visible) => visible_widgets.insert(id, visible),
+                None => visible_widgets.remove(&id),
+            };
+            if visible != prev || changed_widgets.contains(&id) {
+                for widget in prev.iter().chain(visible.iter()) {
+                    damage(widget.rect);
+                }
+            }
+        }
+
+        damaged
+    }
 }
 
 impl<'a> UiCell<'a> {
@@ -1370,6 +1612,11 @@ impl<'a> Drop for UiCell<'a> {
             depth_order.update(widget_graph, window, updated_widgets);
         }
 
+        // Accumulate the area of the window that will need to be redrawn.
+        if self.ui.update_damage_region() {
+            self.ui.needs_redraw();
+        }
+
         // Reset the global input state. Note that this is the **only** time this should be called.
         self.ui.global_input.clear_events_and_update_start_state();
 
@@ -1383,6 +1630,17 @@ impl<'a> Drop for UiCell<'a> {
         for scroll_event in self.ui.pending_scroll_events.drain(0..) {
             self.ui.global_input.push_event(scroll_event.into());
         }
+
+        // Complete the stats of the `set_widgets` stage.
+        if let Some(start) = self.ui.set_widgets_start.take() {
+            let widgets_updated = self.ui.updated_widgets.len();
+            let widgets_changed = self.ui.changed_widgets.len();
+            if let Some(stats) = self.ui.stats_mut() {
+                stats.widgets_updated = widgets_updated;
+                stats.widgets_changed = widgets_changed;
+                stats.set_widgets_duration = start.elapsed();
+            }
+        }
     }
 }
 
@@ -1463,6 +1721,19 @@ pub fn pre_update_cache(ui: &mut Ui, widget: widget::PreUpdateCache) {
     ui.updated_widgets.insert(widget_id);
 }
 
+/// Record the instantiation of a widget whose state has been initialised for the first time.
+pub fn widget_instantiated(ui: &mut Ui) {
+    if let Some(stats) = ui.stats_mut() {
+        stats.widgets_instantiated += 1;
+    }
+}
+
+/// Mark the widget with the given `id` as having changed in some visible way during the current
+/// `set_widgets` stage.
+pub fn widget_changed(ui: &mut Ui, id: widget::Id) {
+    ui.changed_widgets.insert(id);
+}
+
 /// Cache some `PostUpdateCache` widget data into the widget graph.
 /// Set the widget that is being cached as the new `prev_widget`.
 /// Set the widget's parent as the new `current_parent`.
@@ -1,7 +1,7 @@
 //! The `Button` widget and related items.
 
 use image;
-use position::{self, Align, Rect, Scalar};
+use position::{self, Align, Padding, Rect, Scalar};
 use text;
 use widget;
 use {Borderable, Color, Colorable, FontSize, Labelable, Positionable, Sizeable, UiCell, Widget};
@@ -32,6 +32,9 @@ pub struct Style {
     /// The color of the border.
     #[conrod(default = "theme.border_color")]
     pub border_color: Option<Color>,
+    /// The shadow cast by the Button, if any.
+    #[conrod(default = "None")]
+    pub shadow: Option<Option<widget::drop_shadow::Shadow>>,
     /// The color of the Button's label.
     #[conrod(default = "theme.label_color")]
     pub label_color: Option<Color>,
@@ -56,6 +59,7 @@ widget_ids! {
     /// Identifiers for a "flat" button.
     #[allow(missing_docs, missing_copy_implementations)]
     pub struct FlatIds {
+        shadow,
         rectangle,
         label,
     }
@@ -65,6 +69,7 @@ widget_ids! {
     /// Identifiers for an image button.
     #[allow(missing_docs, missing_copy_implementations)]
     pub struct ImageIds {
+        shadow,
         image,
         label,
     }
@@ -96,6 +101,8 @@ pub struct Image {
     pub color: ImageColor,
     /// The rectangular area of the original source image that should be displayed.
     pub src_rect: Option<Rect>,
+    /// If `Some`, the source image is divided into nine slices by the given insets.
+    pub nine_slice: Option<Padding>,
 }
 
 /// The coloring of the `Image`.
@@ -153,6 +160,7 @@ impl<'a> Button<'a, Image> {
             hover_image_id: None,
             press_image_id: None,
             src_rect: None,
+            nine_slice: None,
             color: ImageColor::None,
         };
         Self::new_internal(image)
@@ -166,6 +174,15 @@ impl<'a> Button<'a, Image> {
         self
     }
 
+    /// Divide the image into nine slices using the given insets, given in pixels.
+    ///
+    /// This allows for a skinned `Button` to be scaled without distorting its border. See
+    /// `widget::Image::nine_slice` for details.
+    pub fn nine_slice(mut self, insets: Padding) -> Self {
+        self.show.nine_slice = Some(insets);
+        self
+    }
+
     /// Map the `Image`'s luminance to the given color.
     pub fn image_color(mut self, color: Color) -> Self {
         self.show.color = ImageColor::Normal(color);
@@ -273,6 +290,12 @@ impl<'a, S> Button<'a, S> {
         self
     }
 
+    /// Cast the given shadow beneath the `Button`.
+    pub fn shadow(mut self, shadow: widget::drop_shadow::Shadow) -> Self {
+        self.style.shadow = Some(Some(shadow));
+        self
+    }
+
     builder_methods! {
         pub enabled { enabled = bool }
     }
@@ -316,6 +339,7 @@ impl<'a> Widget for Button<'a, Flat> {
                 .unwrap_or_else(|| style.color(&ui.theme).clicked()),
         };
 
+        drop_shadow(id, state.shadow, rect, style, ui);
         bordered_rectangle(id, state.rectangle, rect, color, style, ui);
 
         // Label widget.
@@ -356,12 +380,15 @@ impl<'a> Widget for Button<'a, Image> {
 
         let (interaction, times_triggered) = interaction_and_times_triggered(id, ui);
 
+        drop_shadow(id, state.shadow, rect, style, ui);
+
         // Instantiate the image.
         let Image {
             image_id,
             press_image_id,
             hover_image_id,
             src_rect,
+            nine_slice,
             color,
         } = show;
 
@@ -379,6 +406,7 @@ impl<'a> Widget for Button<'a, Image> {
             .parent(id)
             .graphics_for(id);
         image.src_rect = src_rect;
+        image.nine_slice = nine_slice;
         image.style.maybe_color = match color {
             ImageColor::Normal(color) => Some(Some(color)),
             ImageColor::WithFeedback(color) => ui
@@ -437,6 +465,22 @@ fn interaction_and_times_triggered(button_id: widget::Id, ui: &UiCell) -> (Inter
     (interaction, times_triggered)
 }
 
+fn drop_shadow(
+    button_id: widget::Id,
+    shadow_id: widget::Id,
+    rect: Rect,
+    style: &Style,
+    ui: &mut UiCell,
+) {
+    // DropShadow widget, if the button casts a shadow.
+    if let Some(shadow) = style.shadow(&ui.theme) {
+        widget::DropShadow::cast_by(rect, shadow)
+            .parent(button_id)
+            .graphics_for(button_id)
+            .set(shadow_id, ui);
+    }
+}
+
 fn bordered_rectangle(
     button_id: widget::Id,
     rectangle_id: widget::Id,
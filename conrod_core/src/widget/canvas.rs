@@ -43,6 +43,7 @@ pub struct State {
 
 widget_ids! {
     struct Ids {
+        shadow,
         rectangle,
         title_bar,
     }
@@ -60,6 +61,9 @@ pub struct Style {
     /// The color of the Canvas' border.
     #[conrod(default = "theme.border_color")]
     pub border_color: Option<Color>,
+    /// The shadow cast by the Canvas, if any.
+    #[conrod(default = "None")]
+    pub shadow: Option<Option<widget::drop_shadow::Shadow>>,
     /// If this Canvas is a split of some parent Canvas, this is the length of the split.
     #[conrod(default = "Length::Weight(1.0)")]
     pub length: Option<Length>,
@@ -210,6 +214,16 @@ impl<'a> Canvas<'a> {
         self.style.title_bar_color = Some(Some(color));
         self
     }
+
+    /// Cast the given shadow beneath the `Canvas`.
+    ///
+    /// This is useful for visually separating floating panels and popups from the background.
+    /// Note that the shadow is cropped along with the rest of the `Canvas`' children if the
+    /// `Canvas` crops its kids, e.g. when it is scrollable.
+    pub fn shadow(mut self, shadow: widget::drop_shadow::Shadow) -> Self {
+        self.style.shadow = Some(Some(shadow));
+        self
+    }
 }
 
 impl<'a> Widget for Canvas<'a> {
@@ -284,6 +298,15 @@ impl<'a> Widget for Canvas<'a> {
             ..
         } = self;
 
+        // DropShadow widget beneath the rectangle backdrop if the Canvas casts a shadow.
+        if let Some(shadow) = style.shadow(ui.theme()) {
+            widget::DropShadow::cast_by(rect, shadow)
+                .parent(id)
+                .graphics_for(id)
+                .place_on_kid_area(false)
+                .set(state.ids.shadow, &mut ui);
+        }
+
         // BorderedRectangle widget as the rectangle backdrop.
         let dim = rect.dim();
         let color = style.color(ui.theme());
@@ -376,6 +376,7 @@ impl Style {
                 .map(|c| if is_selected { c.highlighted() } else { c }),
             border: self.border,
             border_color: self.border_color,
+            shadow: None,
             label_color: self.label_color,
             label_font_size: self.label_font_size,
             label_justify: self.label_justify,
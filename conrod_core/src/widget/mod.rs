@@ -6,7 +6,7 @@
 use graph::{Container, UniqueWidgetState};
 use position::{
     Align, Depth, Dimension, Dimensions, Padding, Point, Position, Positionable, Rect, Relative,
-    Sizeable,
+    Scalar, Sizeable, Transform,
 };
 use std;
 use text::font;
@@ -15,8 +15,11 @@ use ui::{self, Ui, UiCell};
 
 pub use self::id::Id;
 
+pub use self::primitive::custom_draw::{self, CustomDraw};
+pub use self::primitive::drop_shadow::{self, DropShadow};
 pub use self::primitive::image::{self, Image};
 pub use self::primitive::line::{self, Line};
+pub use self::primitive::path::{self, Path};
 pub use self::primitive::point_path::{self, PointPath};
 pub use self::primitive::shape::circle::{self, Circle};
 pub use self::primitive::shape::oval::{self, Oval};
@@ -43,6 +46,7 @@ pub use self::range_slider::RangeSlider;
 pub use self::rounded_rectangle::RoundedRectangle;
 pub use self::scrollbar::Scrollbar;
 pub use self::slider::Slider;
+pub use self::sub_ui::SubUi;
 pub use self::tabs::Tabs;
 pub use self::text_box::TextBox;
 pub use self::text_edit::TextEdit;
@@ -82,6 +86,7 @@ pub mod range_slider;
 pub mod rounded_rectangle;
 pub mod scrollbar;
 pub mod slider;
+pub mod sub_ui;
 pub mod tabs;
 pub mod text_box;
 pub mod text_edit;
@@ -148,6 +153,24 @@ pub struct KidArea {
     pub pad: Padding,
 }
 
+/// The shape within a **Widget**'s `kid_area` to which its children are cropped.
+///
+/// Children are always cropped to the bounding rectangle of the shape. Shapes other than a
+/// `Rectangle` additionally produce a `render::Clip` for each `render::Primitive` that they crop,
+/// which backends may use to crop to the exact shape (e.g. via a stencil buffer).
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum CropShape {
+    /// Crop to the `kid_area` itself. This is the default.
+    Rectangle,
+    /// Crop to the `kid_area` with its corners rounded, e.g. for the contents of a card.
+    RoundedRectangle {
+        /// The radius of each corner, limited to half of the smaller side of the `kid_area`.
+        radius: Scalar,
+    },
+    /// Crop to the ellipse bounded by the `kid_area`, e.g. for a circular avatar.
+    Ellipse,
+}
+
 /// The builder argument for the **Widget**'s parent.
 #[derive(Copy, Clone, Debug, PartialEq)]
 pub enum MaybeParent {
@@ -188,6 +211,64 @@ impl MaybeParent {
     }
 }
 
+impl CropShape {
+    /// Whether or not the given point lies within the shape bounded by the given `Rect`.
+    pub fn is_over(&self, rect: Rect, point: Point) -> bool {
+        match *self {
+            CropShape::Rectangle => rect.is_over(point),
+            CropShape::RoundedRectangle { radius } => {
+                if !rect.is_over(point) {
+                    return false;
+                }
+                // Within the corners, the point must lie within the corner's circle.
+                let radius = rounded_rectangle_radius(rect, radius);
+                let inner = rect.pad(radius);
+                let nearest = [
+                    point[0].max(inner.left()).min(inner.right()),
+                    point[1].max(inner.bottom()).min(inner.top()),
+                ];
+                let (dx, dy) = (point[0] - nearest[0], point[1] - nearest[1]);
+                dx * dx + dy * dy <= radius * radius
+            }
+            CropShape::Ellipse => oval::is_over(rect, point),
+        }
+    }
+
+    /// The triangles describing the shape bounded by the given `Rect`.
+    ///
+    /// The triangles never overlap, so that each point within the shape is covered exactly once.
+    pub fn triangles(&self, rect: Rect) -> Vec<triangles::Triangle<Point>> {
+        match *self {
+            CropShape::Rectangle => {
+                let (l, r, b, t) = rect.l_r_b_t();
+                let (first, second) = triangles::from_quad([[l, t], [r, t], [r, b], [l, b]]);
+                vec![first, second]
+            }
+            CropShape::RoundedRectangle { radius } => {
+                let radius = rounded_rectangle_radius(rect, radius);
+                let resolution = rounded_rectangle::DEFAULT_CORNER_RESOLUTION;
+                let points = rounded_rectangle::points(rect, radius, resolution);
+                polygon::triangles(points).into_iter().flatten().collect()
+            }
+            CropShape::Ellipse => {
+                let resolution = oval::DEFAULT_RESOLUTION;
+                oval::triangles(rect, resolution).collect()
+            }
+        }
+    }
+}
+
+impl Default for CropShape {
+    fn default() -> Self {
+        CropShape::Rectangle
+    }
+}
+
+// The radius of a rounded rectangle, limited so that its corners do not overlap.
+fn rounded_rectangle_radius(rect: Rect, radius: Scalar) -> Scalar {
+    radius.max(0.0).min(rect.w().min(rect.h()) / 2.0)
+}
+
 /// State necessary for "floating" (pop-up style) widgets.
 #[derive(Copy, Clone, Debug, PartialEq)]
 pub struct Floating {
@@ -214,6 +295,13 @@ pub struct CommonBuilder {
     /// By default, the kid_area is the size of the entire widget, though it may be specified
     /// otherwise via the `Widget::kid_area` method.
     pub crop_kids: bool,
+    /// The shape within the `kid_area` to which the children are cropped.
+    pub crop_shape: CropShape,
+    /// The opacity of the **Widget** and all of its descendants, multiplying the alpha of every
+    /// primitive that they produce.
+    pub opacity: f32,
+    /// The transform applied to the **Widget** and all of its descendants about its centre.
+    pub maybe_transform: Option<Transform>,
     /// Arguments to the scrolling of the widget's *x* axis.
     pub maybe_x_scroll: Option<scroll::Scroll>,
     /// Arguments to the scrolling of the widget's *y* axis.
@@ -290,6 +378,14 @@ pub struct CommonState {
     pub maybe_x_scroll_state: Option<scroll::StateX>,
     /// If the widget is scrollable across the *y* axis.
     pub maybe_y_scroll_state: Option<scroll::StateY>,
+    /// Whether or not the widget's children are cropped to its `kid_area`.
+    pub crop_kids: bool,
+    /// The shape within the `kid_area` to which the children are cropped.
+    pub crop_shape: CropShape,
+    /// The opacity of the widget and its descendants.
+    pub opacity: f32,
+    /// The transform applied to the widget and its descendants about its centre.
+    pub maybe_transform: Option<Transform>,
 }
 
 // **Widget** data to be cached prior to the **Widget::update** call in the **widget::set_widget**
@@ -325,6 +421,12 @@ pub struct PreUpdateCache {
     pub maybe_floating: Option<Floating>,
     /// Whether or not the children of the **Widget** should be cropped to its `kid_area`.
     pub crop_kids: bool,
+    /// The shape within the `kid_area` to which the children are cropped.
+    pub crop_shape: CropShape,
+    /// The opacity of the **Widget** and its descendants.
+    pub opacity: f32,
+    /// The transform applied to the **Widget** and its descendants about its centre.
+    pub maybe_transform: Option<Transform>,
     /// Scrolling data for the **Widget**'s *x* axis if there is some.
     pub maybe_x_scroll_state: Option<scroll::StateX>,
     /// Scrolling data for the **Widget**'s *y* axis if there is some.
@@ -781,6 +883,63 @@ pub trait Widget: Common + Sized {
         self
     }
 
+    /// Indicates that all widgets who are children of this widget should be cropped to the given
+    /// shape within the `kid_area` of this widget, e.g. the rounded corners of a card.
+    ///
+    /// Backends that cannot crop to the exact shape fall back to cropping to its bounding
+    /// rectangle.
+    ///
+    /// This method calls `Widget::crop_kids` internally.
+    fn crop_kids_to_shape(mut self, shape: CropShape) -> Self {
+        self.common_mut().crop_shape = shape;
+        self.crop_kids()
+    }
+
+    /// Set the opacity of the widget and all of its descendants (the default is `1.0`).
+    ///
+    /// The alpha of every primitive produced by the widget and its descendants (including text
+    /// and images) is multiplied by this value when rendering. Nested opacities are multiplied
+    /// together, making this useful for fading an entire subtree such as a disabled panel or a
+    /// popup. Widgets with an opacity of `0.0` produce no primitives at all.
+    fn opacity(mut self, opacity: f32) -> Self {
+        self.common_mut().opacity = opacity;
+        self
+    }
+
+    /// Set the transform applied to the widget and all of its descendants.
+    ///
+    /// The transform is applied about the centre of the widget's `Rect` after layout, affecting
+    /// every primitive produced by the widget and its descendants as well as hit-testing. Events
+    /// delivered to the widget have their coordinates transformed back into the widget's
+    /// untransformed space.
+    ///
+    /// The transforms of nested widgets are combined, with a child's transform applied before that
+    /// of its parent.
+    fn transform(mut self, transform: Transform) -> Self {
+        self.common_mut().maybe_transform = Some(transform);
+        self
+    }
+
+    /// Rotate the widget and all of its descendants counter-clockwise about its centre.
+    ///
+    /// The rotation is applied after any transform already given to the widget.
+    fn rotate(mut self, radians: Scalar) -> Self {
+        let rotation = Transform::rotation(radians);
+        let transform = self.common().maybe_transform.unwrap_or_default();
+        self.common_mut().maybe_transform = Some(transform.then(rotation));
+        self
+    }
+
+    /// Scale the widget and all of its descendants about its centre.
+    ///
+    /// The scaling is applied after any transform already given to the widget.
+    fn scale(mut self, scale: Scalar) -> Self {
+        let scaling = Transform::scale(scale);
+        let transform = self.common().maybe_transform.unwrap_or_default();
+        self.common_mut().maybe_transform = Some(transform.then(scaling));
+        self
+    }
+
     /// Makes the widget's `KidArea` scrollable.
     ///
     /// If a widget is scrollable and it has children widgets that fall outside of its `KidArea`,
@@ -936,6 +1095,10 @@ where
                     maybe_floating,
                     maybe_x_scroll_state,
                     maybe_y_scroll_state,
+                    crop_kids,
+                    crop_shape,
+                    opacity,
+                    maybe_transform,
                     ..
                 } = *container;
 
@@ -957,6 +1120,10 @@ where
                     kid_area: kid_area,
                     maybe_x_scroll_state: maybe_x_scroll_state,
                     maybe_y_scroll_state: maybe_y_scroll_state,
+                    crop_kids: crop_kids,
+                    crop_shape: crop_shape,
+                    opacity: opacity,
+                    maybe_transform: maybe_transform,
                 };
 
                 Some((Some(state), Some(prev_common), Some(style)))
@@ -1133,6 +1300,13 @@ where
     // We'll use this to determine whether or not we need to draw for the first time.
     let is_first_set = maybe_prev_common.is_none();
 
+    // Retrieve whether or not the widget's children should be cropped to it, along with the
+    // opacity and transform applied to the widget and its children.
+    let crop_kids = widget.common().crop_kids;
+    let crop_shape = widget.common().crop_shape;
+    let opacity = widget.common().opacity;
+    let maybe_transform = widget.common().maybe_transform;
+
     // Update all positioning and dimension related data prior to calling `Widget::update`.
     // We do this so that if this widget were to internally `set` some other `Widget`s, this
     // `Widget`s positioning and dimension data already exists within the `Graph`.
@@ -1146,9 +1320,6 @@ where
         let maybe_x_positioned_relatively_id = maybe_positioned_relatively_id(x_pos);
         let maybe_y_positioned_relatively_id = maybe_positioned_relatively_id(y_pos);
 
-        // Retrieve whether or not the widget's children should be cropped to it.
-        let crop_kids = widget.common().crop_kids;
-
         // This will cache the given data into the `ui`'s `widget_graph`.
         let ui: &mut Ui = ui::ref_mut_from_ui_cell(ui);
         ui::pre_update_cache(
@@ -1165,6 +1336,9 @@ where
                 maybe_dragged_from: maybe_dragged_from,
                 maybe_floating: maybe_floating,
                 crop_kids: crop_kids,
+                crop_shape: crop_shape,
+                opacity: opacity,
+                maybe_transform: maybe_transform,
                 maybe_y_scroll_state: maybe_y_scroll_state,
                 maybe_x_scroll_state: maybe_x_scroll_state,
                 maybe_graphics_for: widget.common().maybe_graphics_for,
@@ -1183,6 +1357,10 @@ where
         kid_area: kid_area,
         maybe_x_scroll_state: maybe_x_scroll_state,
         maybe_y_scroll_state: maybe_y_scroll_state,
+        crop_kids: crop_kids,
+        crop_shape: crop_shape,
+        opacity: opacity,
+        maybe_transform: maybe_transform,
     });
 
     // Retrieve the widget's unique state and update it via `Widget::update`.
@@ -1191,6 +1369,9 @@ where
         // `init_state` method to construct some initial state.
         let mut unique_state =
             maybe_prev_unique_state.unwrap_or_else(|| widget.init_state(ui.widget_id_generator()));
+        if is_first_set {
+            ui::widget_instantiated(ui::ref_mut_from_ui_cell(ui));
+        }
         let (has_updated, event) = {
             // A wrapper around the widget's unique state in order to keep track of whether or not it
             // has been updated during the `Widget::update` method.
@@ -1234,14 +1415,22 @@ where
                 .as_ref()
                 .and_then(|p| p.maybe_y_scroll_state);
 
+    // The cropping, opacity and transform of the widget affect both it and its descendants.
+    let appearance_has_changed = crop_kids != prev_common.crop_kids
+        || crop_shape != prev_common.crop_shape
+        || opacity != prev_common.opacity
+        || maybe_transform != prev_common.maybe_transform;
+
     // We only need to redraw if some visible part of our widget has changed.
-    let requires_redraw = style_has_changed || state_has_changed || scroll_has_changed;
+    let requires_redraw =
+        style_has_changed || state_has_changed || scroll_has_changed || appearance_has_changed;
 
     let ui: &mut Ui = ui::ref_mut_from_ui_cell(ui);
 
     // If we require a redraw, we should notify the `Ui`.
     if requires_redraw {
         ui.needs_redraw();
+        ui::widget_changed(ui, id);
     }
 
     // Finally, cache the `Widget`'s newly updated `State` and `Style` within the `ui`'s
@@ -1295,6 +1484,9 @@ impl Default for CommonBuilder {
             maybe_x_scroll: None,
             maybe_y_scroll: None,
             crop_kids: false,
+            crop_shape: CropShape::Rectangle,
+            opacity: 1.0,
+            maybe_transform: None,
         }
     }
 }
@@ -32,6 +32,12 @@ pub struct Style {
     /// The color of the line.
     #[conrod(default = "theme.shape_color")]
     pub color: Option<Color>,
+    /// The style with which the ends of the line are drawn.
+    #[conrod(default = "widget::line::Cap::Flat")]
+    pub cap: Option<widget::line::Cap>,
+    /// The style with which the corners between the plotted segments are drawn.
+    #[conrod(default = "widget::line::Join::Miter")]
+    pub join: Option<widget::line::Join>,
 }
 
 widget_ids! {
@@ -64,6 +70,18 @@ impl<X, Y, F> PlotPath<X, Y, F> {
         self.style.thickness = Some(thickness);
         self
     }
+
+    /// The style with which the ends of the plotted line are drawn.
+    pub fn cap(mut self, cap: widget::line::Cap) -> Self {
+        self.style.cap = Some(cap);
+        self
+    }
+
+    /// The style with which the corners between the plotted segments are drawn.
+    pub fn join(mut self, join: widget::line::Join) -> Self {
+        self.style.join = Some(join);
+        self
+    }
 }
 
 impl<X, Y, F> Widget for PlotPath<X, Y, F>
@@ -128,11 +146,15 @@ where
 
         let thickness = style.thickness(ui.theme());
         let color = style.color(ui.theme());
+        let cap = style.cap(ui.theme());
+        let join = style.join(ui.theme());
         widget::PointPath::new(point_iter)
             .wh(rect.dim())
             .xy(rect.xy())
             .color(color)
             .thickness(thickness)
+            .cap(cap)
+            .join(join)
             .parent(id)
             .graphics_for(id)
             .set(state.ids.point_path, ui);
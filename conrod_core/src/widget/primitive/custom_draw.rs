@@ -0,0 +1,83 @@
+//! A primitive widget whose contents are drawn by the user's own rendering code.
+
+use std::any::Any;
+use std::sync::Arc;
+use widget;
+use Widget;
+
+/// A primitive widget that reserves its `Rect` within the depth order for drawing by the user.
+///
+/// Rather than being tessellated by conrod, a **CustomDraw** produces a `PrimitiveKind::Custom`
+/// carrying the given user data. Backends built upon `mesh::Mesh` surface this as a
+/// `mesh::Command::Custom` along with the scizzor that should be applied, so that a user callback
+/// may draw things like 3D viewports, video or shader effects in the correct place among the
+/// other widgets.
+///
+/// The glium and gfx backends draw these via `Renderer::draw_with_custom`, the piston backend via
+/// `draw::primitives_with_custom` and the wgpu backend via `RenderPassCommand::Custom`. The
+/// vulkano backend leaves them to users of `Renderer::commands`. The rendy backend does not
+/// support them.
+///
+/// The widget is only considered to have changed when the given data is not equal to the data
+/// given during the previous update.
+#[derive(Clone, Debug, WidgetCommon_)]
+pub struct CustomDraw<T> {
+    /// Data necessary and common for all widget builder types.
+    #[conrod(common_builder)]
+    pub common: widget::CommonBuilder,
+    /// The user data passed on to the rendering code.
+    pub data: T,
+}
+
+/// User data carried by a **CustomDraw** to the rendering code.
+///
+/// The data may be accessed via `downcast_ref` with the type with which the widget was built.
+pub type Data = Arc<dyn Any + Send + Sync>;
+
+/// Unique state for the **CustomDraw**.
+#[derive(Clone, Debug)]
+pub struct State {
+    /// The user data given during the most recent update.
+    pub data: Data,
+}
+
+/// Unique styling for the **CustomDraw**.
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+pub struct Style;
+
+impl<T> CustomDraw<T> {
+    /// Build a new **CustomDraw** carrying the given user data.
+    pub fn new(data: T) -> Self {
+        CustomDraw {
+            common: widget::CommonBuilder::default(),
+            data: data,
+        }
+    }
+}
+
+impl<T> Widget for CustomDraw<T>
+where
+    T: Any + Send + Sync + PartialEq,
+{
+    type State = State;
+    type Style = Style;
+    type Event = ();
+
+    fn init_state(&self, _: widget::id::Generator) -> Self::State {
+        // The data is always replaced during the first update.
+        State { data: Arc::new(()) }
+    }
+
+    fn style(&self) -> Self::Style {
+        Style
+    }
+
+    fn update(self, args: widget::UpdateArgs<Self>) -> Self::Event {
+        let widget::UpdateArgs { state, .. } = args;
+        let CustomDraw { data, .. } = self;
+        if state.data.downcast_ref::<T>() != Some(&data) {
+            let data: Data = Arc::new(data);
+            state.update(|state| state.data = data);
+        }
+    }
+}
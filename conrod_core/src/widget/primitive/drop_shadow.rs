@@ -0,0 +1,169 @@
+//! A simple, non-interactive widget for drawing the soft-edged shadow cast by some other widget.
+
+use color;
+use graph;
+use widget;
+use widget::primitive::shape::polygon;
+use widget::rounded_rectangle;
+use widget::triangles::{ColoredPoint, Triangle};
+use {Color, Colorable, Point, Positionable, Rect, Scalar, Sizeable, Theme, Widget};
+
+/// Describes the shadow cast by a widget such as a `Canvas`, `Button` or `RoundedRectangle`.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Shadow {
+    /// The offset of the shadow from the widget casting it.
+    pub offset: [Scalar; 2],
+    /// The width of the box blur applied to the shadow.
+    ///
+    /// The edges of the shadow fade out over this width, centred upon the edges of the widget
+    /// casting it.
+    pub blur: Scalar,
+    /// The color of the shadow.
+    pub color: Color,
+}
+
+/// A primitive widget that draws a shadow filling its rectangle, with edges that fade out as
+/// though the shadow were box blurred.
+///
+/// A **DropShadow** is never considered to be under the mouse, so that it does not obscure the
+/// widgets beneath it. It is typically instantiated via `DropShadow::cast_by` before the widget
+/// casting it so that it is drawn underneath.
+#[derive(Copy, Clone, Debug, WidgetCommon_)]
+pub struct DropShadow {
+    /// Data necessary and common for all widget builder types.
+    #[conrod(common_builder)]
+    pub common: widget::CommonBuilder,
+    /// Unique styling for the **DropShadow**.
+    pub style: Style,
+}
+
+/// Unique state for the **DropShadow**.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct State;
+
+/// Unique styling for the **DropShadow** widget.
+#[derive(Copy, Clone, Debug, Default, PartialEq, WidgetStyle_)]
+pub struct Style {
+    /// The color of the shadow.
+    #[conrod(default = "color::BLACK.alpha(0.5)")]
+    pub color: Option<Color>,
+    /// The width of the box blur applied to the shadow.
+    #[conrod(default = "0.0")]
+    pub blur: Option<Scalar>,
+    /// The radius of the corners of the rectangle casting the shadow.
+    #[conrod(default = "0.0")]
+    pub corner_radius: Option<Scalar>,
+}
+
+impl Shadow {
+    /// Describe a shadow with the given offset, blur width and color.
+    pub fn new(offset: [Scalar; 2], blur: Scalar, color: Color) -> Self {
+        Shadow {
+            offset: offset,
+            blur: blur,
+            color: color,
+        }
+    }
+}
+
+impl DropShadow {
+    /// Build a new, unpositioned **DropShadow**.
+    pub fn new() -> Self {
+        DropShadow {
+            common: widget::CommonBuilder::default(),
+            style: Style::default(),
+        }
+    }
+
+    /// Build the **DropShadow** cast by a widget occupying the given `rect`.
+    ///
+    /// The **DropShadow** is positioned at the given `rect` shifted by the shadow's offset and
+    /// sized so that it also bounds the blurred edges of the shadow.
+    pub fn cast_by(rect: Rect, shadow: Shadow) -> Self {
+        let blur = shadow.blur.max(0.0);
+        let (xy, dim) = rect.shift(shadow.offset).pad(-blur * 0.5).xy_dim();
+        DropShadow::new()
+            .color(shadow.color)
+            .blur(blur)
+            .xy(xy)
+            .wh(dim)
+    }
+
+    builder_methods! {
+        pub blur { style.blur = Some(Scalar) }
+        pub corner_radius { style.corner_radius = Some(Scalar) }
+        pub with_style { style = Style }
+    }
+}
+
+impl Widget for DropShadow {
+    type State = State;
+    type Style = Style;
+    type Event = ();
+
+    fn init_state(&self, _: widget::id::Generator) -> Self::State {
+        State
+    }
+
+    fn style(&self) -> Self::Style {
+        self.style.clone()
+    }
+
+    fn is_over(&self) -> widget::IsOverFn {
+        is_over_widget
+    }
+
+    /// Update the state of the DropShadow.
+    fn update(self, _args: widget::UpdateArgs<Self>) -> Self::Event {
+        // Nothing to be updated here.
+    }
+}
+
+impl Colorable for DropShadow {
+    builder_method!(color { style.color = Some(Color) });
+}
+
+/// The triangles describing a shadow that fills the given `rect`, with edges that fade out over
+/// the given `blur` width.
+///
+/// `rect` bounds the blurred edges of the shadow, so the rectangle casting the shadow is `rect`
+/// padded by half of the `blur` on each side. Where the `blur` is wider than the rectangle
+/// casting the shadow, the shadow never reaches the full strength of the given `color`.
+///
+/// Returns `None` if `rect` has no area.
+pub fn triangles(
+    rect: Rect,
+    corner_radius: Scalar,
+    blur: Scalar,
+    color: color::Rgba,
+) -> Option<impl Iterator<Item = Triangle<ColoredPoint>>> {
+    let blur = blur.max(0.0);
+    let (w, h) = rect.w_h();
+    let (caster_w, caster_h) = ((w - blur).max(0.0), (h - blur).max(0.0));
+
+    // Blurring a box by more than its length produces a ramp as long as the box whose peak is
+    // reduced in proportion.
+    let feather = blur.min(caster_w).min(caster_h);
+    let strength = |len: Scalar| if blur > len { len / blur } else { 1.0 };
+    let color::Rgba(r, g, b, a) = color;
+    let color = color::Rgba(
+        r,
+        g,
+        b,
+        a * (strength(caster_w) * strength(caster_h)) as f32,
+    );
+
+    let shape = rect.pad(feather * 0.5);
+    let radius = corner_radius
+        .max(0.0)
+        .min(shape.w() * 0.5)
+        .min(shape.h() * 0.5);
+    let resolution = rounded_rectangle::DEFAULT_CORNER_RESOLUTION;
+    let points = rounded_rectangle::points(shape, radius, resolution);
+    polygon::triangles_anti_aliased(points, feather, color)
+}
+
+/// A **DropShadow** is never considered to be under the mouse.
+pub fn is_over_widget(_: &graph::Container, _: Point, _: &Theme) -> widget::IsOver {
+    false.into()
+}
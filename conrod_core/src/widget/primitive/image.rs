@@ -1,7 +1,8 @@
 //! A simple, non-interactive widget for drawing an `Image`.
 
 use image;
-use position::{Dimension, Rect};
+use position::{Dimension, Padding, Range, Rect, Scalar};
+use std;
 use widget;
 use {Color, Ui, Widget};
 
@@ -15,6 +16,10 @@ pub struct Image {
     pub image_id: image::Id,
     /// The rectangle area of the original source image that should be used.
     pub src_rect: Option<Rect>,
+    /// If `Some`, the source rectangle is divided into nine slices by the given insets.
+    pub nine_slice: Option<Padding>,
+    /// The way in which the image is fitted to the widget's `Rect`.
+    pub fit: Fit,
     /// Unique styling.
     pub style: Style,
 }
@@ -28,6 +33,10 @@ pub struct State {
     pub src_rect: Option<Rect>,
     /// The unique identifier for the image's associated data that will be drawn.
     pub image_id: image::Id,
+    /// The insets dividing the source rectangle into nine slices, if any.
+    pub nine_slice: Option<Padding>,
+    /// The way in which the image is fitted to the widget's `Rect`.
+    pub fit: Fit,
 }
 
 /// Unique styling for the `Image` widget.
@@ -38,6 +47,52 @@ pub struct Style {
     pub maybe_color: Option<Option<Color>>,
 }
 
+/// The way in which an `Image` is fitted to the `Rect` of its widget.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum Fit {
+    /// Stretch the image to fill the `Rect`, ignoring its aspect ratio.
+    ///
+    /// This is the default.
+    Stretch,
+    /// Scale the image to fit within the `Rect` while preserving its aspect ratio.
+    ///
+    /// The image is centred, leaving the `Rect` uncovered along one of its axes.
+    Contain,
+    /// Scale the image to cover the `Rect` while preserving its aspect ratio.
+    ///
+    /// The image is centred and cropped along one of its axes.
+    Cover,
+    /// Draw the image at its original size, centred within the `Rect` and cropped to it.
+    Centre,
+    /// Repeat the image at its original size from the top left of the `Rect`.
+    ///
+    /// The tiles along the right and bottom edges are cropped to the `Rect`.
+    ///
+    /// At most `MAX_TILES_PER_AXIS` tiles are drawn along either axis. Images too small to cover
+    /// the `Rect` within that many tiles are scaled up until they do.
+    Tile,
+}
+
+/// The maximum number of tiles drawn along either axis by `Fit::Tile`, limiting the number of
+/// quads for a single image to the square of this value.
+pub const MAX_TILES_PER_AXIS: usize = 64;
+
+/// An iterator yielding the destination and source rectangles of each quad that draws an image.
+///
+/// Destination rectangles are given in the same space as the widget's `Rect`, while source
+/// rectangles are given in pixels from the bottom left of the image.
+///
+/// Produced by the `quads`, `fit` and `nine_slice` functions.
+#[derive(Clone, Debug)]
+pub struct Quads {
+    xs: Vec<Segment>,
+    ys: Vec<Segment>,
+    index: usize,
+}
+
+// A destination range along with the range of the source drawn to it along a single axis.
+type Segment = (Range, Range);
+
 impl Image {
     /// Construct a new `Image`.
     ///
@@ -68,6 +123,8 @@ impl Image {
             common: widget::CommonBuilder::default(),
             image_id: image_id,
             src_rect: None,
+            nine_slice: None,
+            fit: Fit::Stretch,
             style: Style::default(),
         }
     }
@@ -80,6 +137,27 @@ impl Image {
         self
     }
 
+    /// Divide the source rectangle into nine slices using the given insets, given in pixels.
+    ///
+    /// The four corner slices are drawn at their original size, the four edge slices are
+    /// stretched along their edge and the centre slice is stretched to fill the remaining area.
+    /// This allows for skinned buttons and panels to be scaled without distorting their borders.
+    ///
+    /// See the `nine_slice` function for details.
+    pub fn nine_slice(mut self, insets: Padding) -> Self {
+        self.nine_slice = Some(insets);
+        self
+    }
+
+    /// Specify the way in which the image is fitted to the widget's `Rect`.
+    ///
+    /// By default, the image is stretched to fill the `Rect`. This is ignored if the image is
+    /// divided into nine slices.
+    pub fn fit(mut self, fit: Fit) -> Self {
+        self.fit = fit;
+        self
+    }
+
     builder_methods! {
         pub color { style.maybe_color = Some(Option<Color>) }
     }
@@ -94,6 +172,8 @@ impl Widget for Image {
         State {
             src_rect: None,
             image_id: self.image_id,
+            nine_slice: None,
+            fit: Fit::Stretch,
         }
     }
 
@@ -118,7 +198,11 @@ impl Widget for Image {
     fn update(self, args: widget::UpdateArgs<Self>) -> Self::Event {
         let widget::UpdateArgs { state, .. } = args;
         let Image {
-            image_id, src_rect, ..
+            image_id,
+            src_rect,
+            nine_slice,
+            fit,
+            ..
         } = self;
 
         if state.image_id != image_id {
@@ -127,5 +211,164 @@ impl Widget for Image {
         if state.src_rect != src_rect {
             state.update(|state| state.src_rect = src_rect);
         }
+        if state.nine_slice != nine_slice {
+            state.update(|state| state.nine_slice = nine_slice);
+        }
+        if state.fit != fit {
+            state.update(|state| state.fit = fit);
+        }
+    }
+}
+
+impl Default for Fit {
+    fn default() -> Self {
+        Fit::Stretch
+    }
+}
+
+impl Quads {
+    // The quads formed by each pair of segments along the *x* and *y* axes, skipping those
+    // without area.
+    fn new(xs: Vec<Segment>, ys: Vec<Segment>) -> Self {
+        let has_area = |&(dst, src): &Segment| dst.magnitude() > 0.0 && src.magnitude() > 0.0;
+        Quads {
+            xs: xs.into_iter().filter(has_area).collect(),
+            ys: ys.into_iter().filter(has_area).collect(),
+            index: 0,
+        }
+    }
+}
+
+impl Iterator for Quads {
+    type Item = (Rect, Rect);
+    fn next(&mut self) -> Option<Self::Item> {
+        let columns = self.xs.len();
+        if columns == 0 {
+            return None;
+        }
+        let (column, row) = (self.index % columns, self.index / columns);
+        let (&(dst_x, src_x), &(dst_y, src_y)) = (&self.xs[column], self.ys.get(row)?);
+        self.index += 1;
+        let dst = Rect { x: dst_x, y: dst_y };
+        let src = Rect { x: src_x, y: src_y };
+        Some((dst, src))
+    }
+}
+
+/// The destination and source rectangles of each quad required to draw the given `source_rect`
+/// of an image to the given `rect`.
+///
+/// If `nine_slice` insets are given, the image is divided as described by the `nine_slice`
+/// function. Otherwise, the image is fitted as described by the `fit` function.
+pub fn quads(rect: Rect, source_rect: Rect, fit: Fit, nine_slice: Option<Padding>) -> Quads {
+    match nine_slice {
+        Some(insets) => self::nine_slice(rect, source_rect, insets),
+        None => self::fit(fit, rect, source_rect),
+    }
+}
+
+/// The destination and source rectangles of each quad required to draw the given `source_rect`
+/// of an image to the given `rect` in the manner described by `fit`.
+///
+/// A single quad is produced for all but the `Tile` mode, which produces a quad per tile.
+pub fn fit(fit: Fit, rect: Rect, source_rect: Rect) -> Quads {
+    // Draw the source at the given scale, centring both and cropping each to the other.
+    fn scaled(rect: Rect, source_rect: Rect, scale: Scalar) -> Quads {
+        if !(scale > 0.0 && scale.is_finite()) {
+            return Quads::new(vec![], vec![]);
+        }
+        let axis = |dst: Range, src: Range| {
+            let dst_len = dst.len().min(src.len() * scale);
+            let dst = Range::from_pos_and_len(dst.middle(), dst_len);
+            let src = Range::from_pos_and_len(src.middle(), dst_len / scale);
+            vec![(dst, src)]
+        };
+        Quads::new(axis(rect.x, source_rect.x), axis(rect.y, source_rect.y))
+    }
+
+    // Repeat the source along a single axis, from the end of `dst` rather than the start if
+    // `from_end` is `true`.
+    //
+    // Tiles are scaled up if necessary so that no more than `MAX_TILES_PER_AXIS` are produced.
+    fn tiles(dst: Range, src: Range, from_end: bool) -> Vec<Segment> {
+        let (len, src_tile) = (dst.len(), src.len());
+        if !(src_tile > 0.0) {
+            return vec![];
+        }
+        let tile = src_tile.max(len / MAX_TILES_PER_AXIS as Scalar);
+        let scale = src_tile / tile;
+        let count = std::cmp::min((len / tile).ceil().max(0.0) as usize, MAX_TILES_PER_AXIS);
+        (0..count)
+            .map(|i| {
+                let offset = i as Scalar * tile;
+                let tile_len = tile.min(len - offset);
+                let src_len = tile_len * scale;
+                if from_end {
+                    let end = dst.end - offset;
+                    let dst = Range::new(end - tile_len, end);
+                    (dst, Range::new(src.end - src_len, src.end))
+                } else {
+                    let start = dst.start + offset;
+                    let dst = Range::new(start, start + tile_len);
+                    (dst, Range::new(src.start, src.start + src_len))
+                }
+            })
+            .collect()
     }
+
+    let (w, h) = rect.w_h();
+    let (src_w, src_h) = source_rect.w_h();
+    match fit {
+        Fit::Stretch => Quads::new(vec![(rect.x, source_rect.x)], vec![(rect.y, source_rect.y)]),
+        Fit::Contain => scaled(rect, source_rect, (w / src_w).min(h / src_h)),
+        Fit::Cover => scaled(rect, source_rect, (w / src_w).max(h / src_h)),
+        Fit::Centre => scaled(rect, source_rect, 1.0),
+        Fit::Tile => Quads::new(
+            tiles(rect.x, source_rect.x, false),
+            tiles(rect.y, source_rect.y, true),
+        ),
+    }
+}
+
+/// The destination and source rectangles of each of the nine slices of an image, where the given
+/// `source_rect` is divided by the given `insets` and drawn to the given `rect`.
+///
+/// The corner slices are drawn at the size of their source, the edge slices are stretched along
+/// their edge and the centre slice is stretched along both axes. Insets that do not fit within
+/// `source_rect` are shrunk to fit, as are the corners when `rect` is too small to contain them.
+///
+/// Slices with no area are skipped.
+pub fn nine_slice(rect: Rect, source_rect: Rect, insets: Padding) -> Quads {
+    // Shrink the `start` and `end` lengths in proportion if they would not fit within `len`.
+    fn shrink_to_fit(len: Scalar, start: Scalar, end: Scalar) -> (Scalar, Scalar) {
+        let (start, end) = (start.max(0.0), end.max(0.0));
+        let total = start + end;
+        if total > len && total > 0.0 {
+            let scale = len.max(0.0) / total;
+            (start * scale, end * scale)
+        } else {
+            (start, end)
+        }
+    }
+
+    // The three slices of the given ranges along a single axis.
+    fn slices(dst: Range, src: Range, (start, end): (Scalar, Scalar)) -> Vec<Segment> {
+        let (dst_start, dst_end) = shrink_to_fit(dst.len(), start, end);
+        let dst = [dst.start, dst.start + dst_start, dst.end - dst_end, dst.end];
+        let src = [src.start, src.start + start, src.end - end, src.end];
+        (0..3)
+            .map(|i| {
+                let dst = Range::new(dst[i], dst[i + 1]);
+                let src = Range::new(src[i], src[i + 1]);
+                (dst, src)
+            })
+            .collect()
+    }
+
+    let x = shrink_to_fit(source_rect.w(), insets.x.start, insets.x.end);
+    let y = shrink_to_fit(source_rect.h(), insets.y.start, insets.y.end);
+    Quads::new(
+        slices(rect.x, source_rect.x, x),
+        slices(rect.y, source_rect.y, y),
+    )
 }
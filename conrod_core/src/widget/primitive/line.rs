@@ -42,6 +42,8 @@ pub struct Style {
     pub maybe_thickness: Option<Scalar>,
     /// The style with which the ends of the line are drawn.
     pub maybe_cap: Option<Cap>,
+    /// The style with which the corners between consecutive segments are drawn.
+    pub maybe_join: Option<Join>,
 }
 
 /// The pattern used to draw the line.
@@ -56,7 +58,6 @@ pub enum Pattern {
 }
 
 /// Whether the end of the **Line** should be flat or rounded.
-#[allow(dead_code)]
 #[derive(Copy, Clone, Debug, PartialEq)]
 pub enum Cap {
     /// The line is capped with a flat edge.
@@ -65,6 +66,20 @@ pub enum Cap {
     Round,
 }
 
+/// How the corner between two consecutive segments of a path should be drawn.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum Join {
+    /// The outer edges of the segments are extended until they meet at a point.
+    ///
+    /// Corners sharp enough that the point would extend beyond `point_path::MITER_LIMIT` are
+    /// drawn as a `Bevel` instead.
+    Miter,
+    /// The corner is rounded with a circular arc.
+    Round,
+    /// The corner is cut off by a straight edge between the outer edges of the segments.
+    Bevel,
+}
+
 const DEFAULT_THICKNESS: Scalar = 1.0;
 
 impl Line {
@@ -148,6 +163,12 @@ impl Line {
         self
     }
 
+    /// The style with which the ends of the line are drawn.
+    pub fn cap(mut self, cap: Cap) -> Self {
+        self.style.set_cap(cap);
+        self
+    }
+
     fn calc_rect(&self) -> Rect {
         let thickness = self.style.maybe_thickness.unwrap_or(DEFAULT_THICKNESS);
         let corners = rect_corners(self.start, self.end, thickness * 0.5);
@@ -163,6 +184,7 @@ impl Style {
             maybe_color: None,
             maybe_thickness: None,
             maybe_cap: None,
+            maybe_join: None,
         }
     }
 
@@ -205,6 +227,12 @@ impl Style {
         self
     }
 
+    /// The style for the corners between the segments of a path.
+    pub fn join(mut self, join: Join) -> Self {
+        self.set_join(join);
+        self
+    }
+
     /// Set the pattern for the line.
     pub fn set_pattern(&mut self, pattern: Pattern) {
         self.maybe_pattern = Some(pattern);
@@ -225,6 +253,11 @@ impl Style {
         self.maybe_cap = Some(cap);
     }
 
+    /// Set the **Join** for the corners of a path.
+    pub fn set_join(&mut self, join: Join) {
+        self.maybe_join = Some(join);
+    }
+
     /// The Pattern for the Line.
     pub fn get_pattern(&self, theme: &Theme) -> Pattern {
         const DEFAULT_PATTERN: Pattern = Pattern::Solid;
@@ -270,6 +303,18 @@ impl Style {
             })
             .unwrap_or(DEFAULT_CAP)
     }
+
+    /// The style for the corners between the segments of a path.
+    pub fn get_join(&self, theme: &Theme) -> Join {
+        const DEFAULT_JOIN: Join = Join::Miter;
+        self.maybe_join
+            .or_else(|| {
+                theme
+                    .widget_style::<Style>()
+                    .map(|default| default.style.maybe_join.unwrap_or(DEFAULT_JOIN))
+            })
+            .unwrap_or(DEFAULT_JOIN)
+    }
 }
 
 impl Widget for Line {
@@ -364,9 +409,15 @@ pub fn is_over_widget(widget: &graph::Container, point: Point, theme: &Theme) ->
     widget
         .unique_widget_state::<Line>()
         .map(|widget| {
+            let cap = widget.style.get_cap(theme);
             let thickness = widget.style.get_thickness(theme);
             let (a, b) = (widget.state.start, widget.state.end);
-            is_over(a, b, thickness, point)
+            // Round caps extend the line by a half circle at either end.
+            let over_cap = |end: Point| {
+                let (dx, dy) = (point[0] - end[0], point[1] - end[1]);
+                (dx * dx + dy * dy).sqrt() <= thickness * 0.5
+            };
+            is_over(a, b, thickness, point) || (cap == Cap::Round && (over_cap(a) || over_cap(b)))
         })
         .unwrap_or_else(|| widget.rect.is_over(point))
         .into()
@@ -4,8 +4,11 @@
 //! define their own methods for rendering. Instead, conrod graphics backends only need to define
 //! rendering methods for a small set of primitives.
 
+pub mod custom_draw;
+pub mod drop_shadow;
 pub mod image;
 pub mod line;
+pub mod path;
 pub mod point_path;
 pub mod shape;
 pub mod text;
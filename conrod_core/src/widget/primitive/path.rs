@@ -0,0 +1,705 @@
+//! A simple, non-interactive widget for drawing paths made of straight lines, bezier curves and
+//! arcs.
+
+use color;
+use graph;
+use std;
+use utils::{bounding_box_for_points, vec2_add, vec2_sub};
+use widget;
+use widget::primitive::shape::Style as ShapeStyle;
+use widget::triangles::{ColoredPoint, Triangle};
+use {Color, Colorable, Dimensions, Point, Positionable, Rect, Scalar, Sizeable, Theme, Widget};
+
+/// A basic, non-interactive widget for drawing a **Path** described by a series of **Command**s.
+///
+/// Curves and arcs are flattened into a series of straight lines that lie within the style's
+/// tolerance of the true curve, so that they remain smooth regardless of size.
+///
+/// A filled **Path** automatically closes all of its subpaths, while an outlined **Path** is only
+/// closed where a `Command::Close` is given.
+#[derive(Clone, Debug, WidgetCommon_)]
+pub struct Path<I> {
+    /// Data necessary and common for all widget builder types.
+    #[conrod(common_builder)]
+    pub common: widget::CommonBuilder,
+    /// The commands describing the **Path**.
+    pub commands: I,
+    /// Unique styling for the **Path**.
+    pub style: Style,
+    /// Whether or not the commands should be automatically centred to the widget position.
+    pub maybe_shift_to_centre_from: Option<Point>,
+}
+
+/// A single step in the description of a **Path**.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum Command {
+    /// Begin a new subpath at the given point.
+    MoveTo(Point),
+    /// A straight line to the given point.
+    LineTo(Point),
+    /// A quadratic bezier curve with the given control point, ending at the second point.
+    QuadraticTo(Point, Point),
+    /// A cubic bezier curve with the two given control points, ending at the third point.
+    CubicTo(Point, Point, Point),
+    /// An elliptical arc about `centre` with the given `radii`.
+    ///
+    /// The arc begins at `start_radians` and spans `sweep_radians`, where positive sweeps travel
+    /// anti-clockwise. If the subpath is already underway, a straight line joins the current point
+    /// to the start of the arc.
+    Arc {
+        /// The centre of the ellipse on which the arc lies.
+        centre: Point,
+        /// The radius of the ellipse along the *x* and *y* axes respectively.
+        radii: Dimensions,
+        /// The angle at which the arc begins, where `0.0` is the rightmost point of the ellipse.
+        start_radians: Scalar,
+        /// The angle spanned by the arc.
+        sweep_radians: Scalar,
+    },
+    /// Close the current subpath with a straight line back to its first point.
+    Close,
+}
+
+/// The rule used to decide which regions of a filled **Path** lie inside it.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum FillRule {
+    /// Regions that the path winds around a non-zero number of times are filled.
+    NonZero,
+    /// Regions that are enclosed by an odd number of path edges are filled.
+    EvenOdd,
+}
+
+/// Unique styling for a **Path**.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Style {
+    /// Whether the **Path** is filled or outlined.
+    pub shape: ShapeStyle,
+    /// The rule used to decide which regions of a filled **Path** lie inside it.
+    pub maybe_fill_rule: Option<FillRule>,
+    /// The maximum distance between the flattened lines and the true curves of the **Path**.
+    pub maybe_tolerance: Option<Scalar>,
+}
+
+/// Unique state for the **Path**.
+#[derive(Clone, Debug, PartialEq)]
+pub struct State {
+    /// An owned version of the commands yielded by the **Path**'s `commands` iterator.
+    pub commands: Vec<Command>,
+    /// The `commands` flattened into subpaths, re-used by rendering and hit-testing until either
+    /// the commands or the tolerance change.
+    pub subpaths: Vec<Vec<Point>>,
+    /// The tolerance with which the `subpaths` were flattened.
+    pub tolerance: Scalar,
+}
+
+/// The default tolerance used to flatten curves if none is specified.
+pub const DEFAULT_TOLERANCE: Scalar = 0.25;
+
+/// The smallest tolerance with which curves will be flattened.
+const MIN_TOLERANCE: Scalar = 0.001;
+
+/// The greatest number of lines into which a single curve or arc will be flattened.
+const MAX_SEGMENTS: usize = 1024;
+
+impl<I> Path<I> {
+    /// Build a **Path** with the given commands and style.
+    pub fn styled(commands: I, style: Style) -> Self {
+        Path {
+            common: widget::CommonBuilder::default(),
+            commands: commands,
+            style: style,
+            maybe_shift_to_centre_from: None,
+        }
+    }
+
+    /// Build a **Path** with the default **Fill** style.
+    pub fn fill(commands: I) -> Self {
+        Path::styled(commands, Style::fill())
+    }
+
+    /// Build a **Path** **Fill**ed with the given **Color**.
+    pub fn fill_with(commands: I, color: Color) -> Self {
+        Path::styled(commands, Style::fill_with(color))
+    }
+
+    /// Build a **Path** with the default **Outline** style.
+    pub fn outline(commands: I) -> Self {
+        Path::styled(commands, Style::outline())
+    }
+
+    /// Build a **Path** **Outline**d with the given line style.
+    pub fn outline_styled(commands: I, style: widget::line::Style) -> Self {
+        Path::styled(commands, Style::outline_styled(style))
+    }
+
+    /// Build a new **Path** whose bounding box is fit to the absolute co-ordinates of the path.
+    ///
+    /// This requires that the `commands` iterator is `Clone` so that we may iterate through and
+    /// determine the bounding box of the path.
+    ///
+    /// If you would rather centre the path to the middle of the bounding box, use
+    /// [**Path::centred_styled**](./struct.Path#method.centred_styled) instead.
+    pub fn abs_styled(commands: I, style: Style) -> Self
+    where
+        I: IntoIterator<Item = Command> + Clone,
+    {
+        let (xy, dim) = bounding_box(commands.clone()).xy_dim();
+        Path::styled(commands, style).wh(dim).xy(xy)
+    }
+
+    /// Build a new **Path** and shift its location so that the centre of its bounding rectangle
+    /// lies at the position determined for the **Path** widget.
+    ///
+    /// This is useful if your commands simply describe a shape and you want to position them using
+    /// conrod's auto-layout and/or **Positionable** methods.
+    pub fn centred_styled(commands: I, style: Style) -> Self
+    where
+        I: IntoIterator<Item = Command> + Clone,
+    {
+        let (xy, dim) = bounding_box(commands.clone()).xy_dim();
+        let mut path = Path::styled(commands, style).wh(dim);
+        path.maybe_shift_to_centre_from = Some(xy);
+        path
+    }
+
+    /// The rule used to decide which regions of a filled **Path** lie inside it.
+    pub fn fill_rule(mut self, rule: FillRule) -> Self {
+        self.style.set_fill_rule(rule);
+        self
+    }
+
+    /// The maximum distance between the flattened lines and the true curves of the **Path**.
+    pub fn tolerance(mut self, tolerance: Scalar) -> Self {
+        self.style.set_tolerance(tolerance);
+        self
+    }
+}
+
+impl Command {
+    /// The same command, with all of its points shifted by the given amount.
+    pub fn shift(self, amount: Point) -> Self {
+        let shift = |point| vec2_add(point, amount);
+        match self {
+            Command::MoveTo(a) => Command::MoveTo(shift(a)),
+            Command::LineTo(a) => Command::LineTo(shift(a)),
+            Command::QuadraticTo(a, b) => Command::QuadraticTo(shift(a), shift(b)),
+            Command::CubicTo(a, b, c) => Command::CubicTo(shift(a), shift(b), shift(c)),
+            Command::Arc {
+                centre,
+                radii,
+                start_radians,
+                sweep_radians,
+            } => Command::Arc {
+                centre: shift(centre),
+                radii,
+                start_radians,
+                sweep_radians,
+            },
+            Command::Close => Command::Close,
+        }
+    }
+}
+
+impl Style {
+    /// A **Style** with the given shape styling.
+    pub fn new(shape: ShapeStyle) -> Self {
+        Style {
+            shape: shape,
+            maybe_fill_rule: None,
+            maybe_tolerance: None,
+        }
+    }
+
+    /// A default `Fill` style.
+    pub fn fill() -> Self {
+        Style::new(ShapeStyle::fill())
+    }
+
+    /// A `Fill` style with some given `Color`.
+    pub fn fill_with(color: Color) -> Self {
+        Style::new(ShapeStyle::fill_with(color))
+    }
+
+    /// A default `Outline` style.
+    pub fn outline() -> Self {
+        Style::new(ShapeStyle::outline())
+    }
+
+    /// An `Outline` style with the given line style.
+    pub fn outline_styled(line_style: widget::line::Style) -> Self {
+        Style::new(ShapeStyle::outline_styled(line_style))
+    }
+
+    /// The style with the given fill rule.
+    pub fn fill_rule(mut self, rule: FillRule) -> Self {
+        self.set_fill_rule(rule);
+        self
+    }
+
+    /// The style with the given tolerance.
+    pub fn tolerance(mut self, tolerance: Scalar) -> Self {
+        self.set_tolerance(tolerance);
+        self
+    }
+
+    /// Set the color for the style.
+    pub fn set_color(&mut self, color: Color) {
+        self.shape.set_color(color);
+    }
+
+    /// Set the fill rule for the style.
+    pub fn set_fill_rule(&mut self, rule: FillRule) {
+        self.maybe_fill_rule = Some(rule);
+    }
+
+    /// Set the tolerance for the style.
+    pub fn set_tolerance(&mut self, tolerance: Scalar) {
+        self.maybe_tolerance = Some(tolerance);
+    }
+
+    /// The color of the **Path**.
+    pub fn get_color(&self, theme: &Theme) -> Color {
+        self.shape.get_color(theme)
+    }
+
+    /// The rule used to decide which regions of a filled **Path** lie inside it.
+    pub fn get_fill_rule(&self, theme: &Theme) -> FillRule {
+        const DEFAULT_FILL_RULE: FillRule = FillRule::NonZero;
+        self.maybe_fill_rule
+            .or_else(|| {
+                theme
+                    .widget_style::<Style>()
+                    .map(|default| default.style.maybe_fill_rule.unwrap_or(DEFAULT_FILL_RULE))
+            })
+            .unwrap_or(DEFAULT_FILL_RULE)
+    }
+
+    /// The maximum distance between the flattened lines and the true curves of the **Path**.
+    pub fn get_tolerance(&self, theme: &Theme) -> Scalar {
+        self.maybe_tolerance
+            .or_else(|| {
+                theme
+                    .widget_style::<Style>()
+                    .map(|default| default.style.maybe_tolerance.unwrap_or(DEFAULT_TOLERANCE))
+            })
+            .unwrap_or(DEFAULT_TOLERANCE)
+    }
+}
+
+impl<I> Widget for Path<I>
+where
+    I: IntoIterator<Item = Command>,
+{
+    type State = State;
+    type Style = Style;
+    type Event = ();
+
+    fn init_state(&self, _: widget::id::Generator) -> Self::State {
+        State {
+            commands: Vec::new(),
+            subpaths: Vec::new(),
+            tolerance: DEFAULT_TOLERANCE,
+        }
+    }
+
+    fn style(&self) -> Self::Style {
+        self.style.clone()
+    }
+
+    fn is_over(&self) -> widget::IsOverFn {
+        is_over_widget
+    }
+
+    /// Update the state of the Path.
+    fn update(self, args: widget::UpdateArgs<Self>) -> Self::Event {
+        use utils::{iter_diff, IterDiff};
+        let widget::UpdateArgs {
+            rect,
+            state,
+            style,
+            ui,
+            ..
+        } = args;
+        let Path {
+            commands,
+            maybe_shift_to_centre_from,
+            ..
+        } = self;
+
+        // A function that compares the given commands iterator to the commands currently owned by
+        // `State` and updates only if necessary, returning whether or not they changed.
+        fn update_commands<I>(state: &mut widget::State<State>, commands: I) -> bool
+        where
+            I: IntoIterator<Item = Command>,
+        {
+            match iter_diff(&state.commands, commands) {
+                Some(IterDiff::FirstMismatch(i, mismatch)) => state.update(|state| {
+                    state.commands.truncate(i);
+                    state.commands.extend(mismatch);
+                }),
+                Some(IterDiff::Longer(remaining)) => {
+                    state.update(|state| state.commands.extend(remaining))
+                }
+                Some(IterDiff::Shorter(total)) => {
+                    state.update(|state| state.commands.truncate(total))
+                }
+                None => return false,
+            }
+            true
+        }
+
+        let commands_changed = match maybe_shift_to_centre_from {
+            Some(original) => {
+                let difference = vec2_sub(rect.xy(), original);
+                let commands = commands.into_iter().map(|c| c.shift(difference));
+                update_commands(state, commands)
+            }
+            None => update_commands(state, commands),
+        };
+
+        // Only re-flatten the curves when the commands or the tolerance have changed.
+        let tolerance = style.get_tolerance(&ui.theme);
+        if commands_changed || state.tolerance != tolerance {
+            let subpaths = flatten(state.commands.iter().cloned(), tolerance);
+            state.update(|state| {
+                state.subpaths = subpaths;
+                state.tolerance = tolerance;
+            });
+        }
+    }
+}
+
+impl<I> Colorable for Path<I> {
+    fn color(mut self, color: Color) -> Self {
+        self.style.set_color(color);
+        self
+    }
+}
+
+/// Flatten the given commands into a list of subpaths, each described by a series of points.
+///
+/// Curves and arcs are divided into enough straight lines that no point on the lines lies
+/// further than `tolerance` from the true curve. Subpaths closed by a `Command::Close` end with
+/// their first point.
+pub fn flatten<I>(commands: I, tolerance: Scalar) -> Vec<Vec<Point>>
+where
+    I: IntoIterator<Item = Command>,
+{
+    let tolerance = tolerance.max(MIN_TOLERANCE);
+    let mut subpaths = Vec::new();
+    let mut current: Vec<Point> = Vec::new();
+
+    fn finish(subpaths: &mut Vec<Vec<Point>>, current: &mut Vec<Point>) {
+        let subpath = std::mem::replace(current, Vec::new());
+        if subpath.len() > 1 {
+            subpaths.push(subpath);
+        }
+    }
+
+    for command in commands {
+        match command {
+            Command::MoveTo(point) => {
+                finish(&mut subpaths, &mut current);
+                current.push(point);
+            }
+            Command::LineTo(point) => current.push(point),
+            Command::QuadraticTo(ctrl, to) => {
+                let from = *current.last().unwrap_or(&ctrl);
+                if current.is_empty() {
+                    current.push(from);
+                }
+                let n = quadratic_segments(from, ctrl, to, tolerance);
+                current.extend((1..n + 1).map(|i| {
+                    let t = i as Scalar / n as Scalar;
+                    let mt = 1.0 - t;
+                    let (a, b, c) = (mt * mt, 2.0 * mt * t, t * t);
+                    [
+                        a * from[0] + b * ctrl[0] + c * to[0],
+                        a * from[1] + b * ctrl[1] + c * to[1],
+                    ]
+                }));
+            }
+            Command::CubicTo(ctrl_a, ctrl_b, to) => {
+                let from = *current.last().unwrap_or(&ctrl_a);
+                if current.is_empty() {
+                    current.push(from);
+                }
+                let n = cubic_segments(from, ctrl_a, ctrl_b, to, tolerance);
+                current.extend((1..n + 1).map(|i| {
+                    let t = i as Scalar / n as Scalar;
+                    let mt = 1.0 - t;
+                    let (a, b, c, d) =
+                        (mt * mt * mt, 3.0 * mt * mt * t, 3.0 * mt * t * t, t * t * t);
+                    [
+                        a * from[0] + b * ctrl_a[0] + c * ctrl_b[0] + d * to[0],
+                        a * from[1] + b * ctrl_a[1] + c * ctrl_b[1] + d * to[1],
+                    ]
+                }));
+            }
+            Command::Arc {
+                centre,
+                radii,
+                start_radians,
+                sweep_radians,
+            } => {
+                let n = arc_segments(radii, sweep_radians, tolerance);
+                current.extend((0..n + 1).map(|i| {
+                    let angle = start_radians + sweep_radians * i as Scalar / n as Scalar;
+                    [
+                        centre[0] + radii[0] * angle.cos(),
+                        centre[1] + radii[1] * angle.sin(),
+                    ]
+                }));
+            }
+            Command::Close => {
+                if let Some(&first) = current.first() {
+                    current.push(first);
+                    finish(&mut subpaths, &mut current);
+                    current.push(first);
+                }
+            }
+        }
+    }
+    finish(&mut subpaths, &mut current);
+    subpaths
+}
+
+// The number of lines required to flatten a quadratic curve within the given tolerance.
+//
+// The distance between the curve and a line spanning `1 / n` of it is bounded by an eighth of the
+// curve's (constant) second derivative divided by `n` squared.
+fn quadratic_segments(from: Point, ctrl: Point, to: Point, tolerance: Scalar) -> usize {
+    let dd = magnitude(second_difference(from, ctrl, to));
+    segments((dd / (4.0 * tolerance)).sqrt())
+}
+
+// The number of lines required to flatten a cubic curve within the given tolerance.
+//
+// As for `quadratic_segments`, using the greatest second derivative along the curve.
+fn cubic_segments(from: Point, ctrl_a: Point, ctrl_b: Point, to: Point, tol: Scalar) -> usize {
+    let dd_a = magnitude(second_difference(from, ctrl_a, ctrl_b));
+    let dd_b = magnitude(second_difference(ctrl_a, ctrl_b, to));
+    segments((3.0 * dd_a.max(dd_b) / (4.0 * tol)).sqrt())
+}
+
+// The number of lines required to flatten an arc within the given tolerance.
+//
+// Each line may span an angle no greater than that at which its midpoint falls `tolerance` short
+// of the arc.
+fn arc_segments(radii: Dimensions, sweep_radians: Scalar, tolerance: Scalar) -> usize {
+    let radius = radii[0].abs().max(radii[1].abs());
+    if radius <= tolerance {
+        return 1;
+    }
+    let max_angle = 2.0 * (1.0 - tolerance / radius).acos();
+    segments(sweep_radians.abs() / max_angle)
+}
+
+fn segments(n: Scalar) -> usize {
+    if n.is_finite() {
+        std::cmp::min(std::cmp::max(n.ceil() as usize, 1), MAX_SEGMENTS)
+    } else {
+        1
+    }
+}
+
+fn second_difference(a: Point, b: Point, c: Point) -> Point {
+    [a[0] - 2.0 * b[0] + c[0], a[1] - 2.0 * b[1] + c[1]]
+}
+
+fn magnitude(v: Point) -> Scalar {
+    (v[0] * v[0] + v[1] * v[1]).sqrt()
+}
+
+/// The bounding rectangle of the path described by the given commands.
+pub fn bounding_box<I>(commands: I) -> Rect
+where
+    I: IntoIterator<Item = Command>,
+{
+    let subpaths = flatten(commands, DEFAULT_TOLERANCE);
+    bounding_box_for_points(subpaths.iter().flat_map(|s| s.iter().cloned()))
+}
+
+// An edge of a filled path that is not horizontal, ordered from bottom to top.
+#[derive(Copy, Clone)]
+struct Edge {
+    bottom: Point,
+    top: Point,
+    // `1` if the path travels upwards along the edge, `-1` if downwards.
+    winding: i32,
+}
+
+impl Edge {
+    fn x_at(&self, y: Scalar) -> Scalar {
+        let t = (y - self.bottom[1]) / (self.top[1] - self.bottom[1]);
+        self.bottom[0] + (self.top[0] - self.bottom[0]) * t
+    }
+}
+
+// All non-horizontal edges of the given subpaths, each of which is implicitly closed.
+fn fill_edges(subpaths: &[Vec<Point>]) -> Vec<Edge> {
+    let mut edges = Vec::new();
+    for subpath in subpaths {
+        let n = subpath.len();
+        for i in 0..n {
+            let (a, b) = (subpath[i], subpath[(i + 1) % n]);
+            if a[1] < b[1] {
+                edges.push(Edge {
+                    bottom: a,
+                    top: b,
+                    winding: 1,
+                });
+            } else if a[1] > b[1] {
+                edges.push(Edge {
+                    bottom: b,
+                    top: a,
+                    winding: -1,
+                });
+            }
+        }
+    }
+    edges
+}
+
+impl FillRule {
+    /// Whether or not a region with the given winding number lies inside the path.
+    pub fn is_inside(self, winding: i32) -> bool {
+        match self {
+            FillRule::NonZero => winding != 0,
+            FillRule::EvenOdd => winding % 2 != 0,
+        }
+    }
+}
+
+/// Triangulate the area enclosed by the given subpaths according to the given fill rule.
+///
+/// Each subpath is implicitly closed. Subpaths may overlap and intersect themselves or each other.
+///
+/// The area is divided into horizontal slabs at every vertex and edge intersection, such that the
+/// edges crossing each slab do not cross each other within it. Each span between a pair of edges
+/// that lies inside the path is then filled with a trapezoid.
+pub fn fill_triangles(subpaths: &[Vec<Point>], rule: FillRule) -> Vec<Triangle<Point>> {
+    let edges = fill_edges(subpaths);
+    let mut ys: Vec<Scalar> = edges
+        .iter()
+        .flat_map(|e| vec![e.bottom[1], e.top[1]])
+        .collect();
+    for (i, a) in edges.iter().enumerate() {
+        for b in &edges[i + 1..] {
+            if let Some(y) = intersection_y(a, b) {
+                ys.push(y);
+            }
+        }
+    }
+    ys.sort_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
+    ys.dedup();
+
+    let mut triangles = Vec::new();
+    let mut crossing = Vec::new();
+    for slab in ys.windows(2) {
+        let (y0, y1) = (slab[0], slab[1]);
+        let mid = (y0 + y1) / 2.0;
+        crossing.clear();
+        crossing.extend(edges.iter().filter(|e| e.bottom[1] <= y0 && e.top[1] >= y1));
+        crossing.sort_by(|a: &&Edge, b: &&Edge| {
+            a.x_at(mid)
+                .partial_cmp(&b.x_at(mid))
+                .unwrap_or(std::cmp::Ordering::Equal)
+        });
+        let mut winding = 0;
+        let mut span_start: Option<&Edge> = None;
+        for edge in crossing.iter() {
+            winding += edge.winding;
+            match span_start {
+                None if rule.is_inside(winding) => span_start = Some(edge),
+                Some(start) if !rule.is_inside(winding) => {
+                    let a = [start.x_at(y0), y0];
+                    let b = [edge.x_at(y0), y0];
+                    let c = [edge.x_at(y1), y1];
+                    let d = [start.x_at(y1), y1];
+                    triangles.push(Triangle([a, b, c]));
+                    triangles.push(Triangle([a, c, d]));
+                    span_start = None;
+                }
+                _ => (),
+            }
+        }
+    }
+    triangles
+}
+
+// The height at which the two edges cross, if they cross at some point within both.
+fn intersection_y(a: &Edge, b: &Edge) -> Option<Scalar> {
+    let (lo, hi) = (a.bottom[1].max(b.bottom[1]), a.top[1].min(b.top[1]));
+    if lo >= hi {
+        return None;
+    }
+    let d_lo = a.x_at(lo) - b.x_at(lo);
+    let d_hi = a.x_at(hi) - b.x_at(hi);
+    if (d_lo < 0.0) == (d_hi < 0.0) || d_lo == 0.0 || d_hi == 0.0 {
+        return None;
+    }
+    Some(lo + (hi - lo) * d_lo / (d_lo - d_hi))
+}
+
+/// Triangulate the area enclosed by the given subpaths as for `fill_triangles`, surrounding it
+/// with a fringe of the given `feather` width within which the alpha falls off to zero.
+///
+/// The fringe is centred upon the edges of the path.
+pub fn fill_triangles_anti_aliased(
+    subpaths: &[Vec<Point>],
+    rule: FillRule,
+    feather: Scalar,
+    color: color::Rgba,
+) -> Vec<Triangle<ColoredPoint>> {
+    use widget::line::{Cap, Join};
+    let fill = fill_triangles(subpaths, rule).into_iter();
+    let mut triangles: Vec<_> = fill
+        .map(|Triangle([a, b, c])| Triangle([(a, color), (b, color), (c, color)]))
+        .collect();
+    for subpath in subpaths {
+        let closed = subpath.iter().chain(subpath.first()).cloned();
+        let (cap, join) = (Cap::Flat, Join::Round);
+        let fringe =
+            widget::point_path::triangles_anti_aliased(closed, cap, join, feather, feather, color);
+        triangles.extend(fringe.into_iter().flat_map(|t| t));
+    }
+    triangles
+}
+
+/// Whether or not the given point lies within the area enclosed by the given subpaths according
+/// to the given fill rule.
+pub fn is_over_fill(subpaths: &[Vec<Point>], rule: FillRule, p: Point) -> bool {
+    let winding: i32 = fill_edges(subpaths)
+        .iter()
+        .filter(|e| e.bottom[1] <= p[1] && p[1] < e.top[1] && e.x_at(p[1]) > p[0])
+        .map(|e| e.winding)
+        .sum();
+    rule.is_inside(winding)
+}
+
+/// The function to use for picking whether a given point is over the path.
+pub fn is_over_widget(widget: &graph::Container, point: Point, theme: &Theme) -> widget::IsOver {
+    widget
+        .state_and_style::<State, Style>()
+        .map(|widget| {
+            let subpaths = &widget.state.subpaths;
+            match widget.style.shape {
+                ShapeStyle::Fill(_) => {
+                    let rule = widget.style.get_fill_rule(theme);
+                    is_over_fill(subpaths, rule, point)
+                }
+                ShapeStyle::Outline(ref line_style) => {
+                    let cap = line_style.get_cap(theme);
+                    let join = line_style.get_join(theme);
+                    let thickness = line_style.get_thickness(theme);
+                    subpaths.iter().any(|subpath| {
+                        let points = subpath.iter().cloned();
+                        widget::point_path::is_over_with_join(points, cap, join, thickness, point)
+                    })
+                }
+            }
+        })
+        .unwrap_or_else(|| widget.rect.is_over(point))
+        .into()
+}
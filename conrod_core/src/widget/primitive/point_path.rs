@@ -1,13 +1,15 @@
 //! A simple, non-interactive widget for drawing a series of conjoined lines.
 
+use color;
 use graph;
+use std;
 use utils::{vec2_add, vec2_sub};
 use widget;
-use widget::triangles::Triangle;
+use widget::triangles::{ColoredPoint, Triangle};
 use {Color, Colorable, Point, Positionable, Scalar, Sizeable, Theme, Widget};
 
-pub use super::line::Pattern;
 pub use super::line::Style;
+pub use super::line::{Cap, Join, Pattern};
 
 /// A simple, non-interactive widget for drawing a series of lines and/or points.
 #[derive(Clone, Debug, WidgetCommon_)]
@@ -30,15 +32,11 @@ pub struct State {
     pub points: Vec<Point>,
 }
 
-/// An iterator that triangulates a point path.
-#[derive(Clone)]
-pub struct Triangles<I> {
-    next: Option<Triangle<Point>>,
-    prev: Point,
-    points: I,
-    half_thickness: Scalar,
-    cap: widget::line::Cap,
-}
+/// An iterator yielding the triangles of a stroked point path.
+///
+/// Joins and closed paths depend upon the points either side of each corner, so the path is
+/// stroked up front and its triangles are yielded from a buffer.
+pub type Triangles = std::vec::IntoIter<Triangle<Point>>;
 
 impl<I> PointPath<I> {
     /// The same as [**PointPath::new**](./struct.PointPath#method.new) but with th given style.
@@ -141,6 +139,18 @@ impl<I> PointPath<I> {
         self.style.set_pattern(Pattern::Dotted);
         self
     }
+
+    /// The style with which the ends of the path are drawn.
+    pub fn cap(mut self, cap: Cap) -> Self {
+        self.style.set_cap(cap);
+        self
+    }
+
+    /// The style with which the corners between consecutive segments are drawn.
+    pub fn join(mut self, join: Join) -> Self {
+        self.style.set_join(join);
+        self
+    }
 }
 
 impl<I> Widget for PointPath<I>
@@ -215,59 +225,350 @@ impl<I> Colorable for PointPath<I> {
     }
 }
 
-/// Triangulate a point path.
+/// The length of a miter join, as a multiple of half the line thickness, beyond which the corner
+/// is drawn as a `Join::Bevel` instead.
+pub const MITER_LIMIT: Scalar = 4.0;
+
+/// The maximum angle in radians spanned by each triangle of a round join or cap.
+const ROUND_STEP: Scalar = std::f64::consts::PI / 16.0;
+
+/// Points closer together than this are considered to be the same point.
+const EPSILON: Scalar = 1e-6;
+
+/// Triangulate a point path with the given line cap and thickness.
+///
+/// Corners are drawn with a `Join::Miter`. See `triangles_with_join`.
 ///
 /// Returns `None` if the given iterator yields less than one point.
 pub fn triangles<I>(
     points: I,
     cap: widget::line::Cap,
     thickness: Scalar,
-) -> Option<Triangles<I::IntoIter>>
+) -> Option<Triangles>
 where
     I: IntoIterator<Item = Point>,
 {
-    let mut points = points.into_iter();
-    let first = match points.next() {
-        Some(point) => point,
-        None => return None,
-    };
-    Some(Triangles {
-        next: None,
-        prev: first,
-        points: points,
-        half_thickness: thickness / 2.0,
-        cap: cap,
-    })
+    triangles_with_join(points, cap, Join::Miter, thickness)
 }
 
-impl<I> Iterator for Triangles<I>
+/// Triangulate a point path with the given line cap, join and thickness.
+///
+/// If the first and last points coincide, the path is considered closed and is joined at that
+/// point rather than capped.
+///
+/// Returns `None` if the given iterator yields less than one point.
+pub fn triangles_with_join<I>(
+    points: I,
+    cap: widget::line::Cap,
+    join: widget::line::Join,
+    thickness: Scalar,
+) -> Option<Triangles>
 where
-    I: Iterator<Item = Point>,
+    I: IntoIterator<Item = Point>,
 {
-    type Item = Triangle<Point>;
-    fn next(&mut self) -> Option<Self::Item> {
-        if let Some(triangle) = self.next.take() {
-            return Some(triangle);
+    let path = Path::new(points)?;
+    let mut triangles = Vec::new();
+    path.stroke(cap, join, thickness / 2.0, |part| {
+        if let Part::Triangle(triangle) = part {
+            triangles.push(triangle);
         }
-        self.points.next().map(|point| {
-            let (a, b) = (self.prev, point);
-            self.prev = point;
-            let tris = widget::line::triangles(a, b, self.half_thickness);
-            self.next = Some(tris[1]);
-            tris[0]
-        })
-    }
+    });
+    Some(triangles.into_iter())
+}
+
+/// Triangulate a point path of the given color, feathering its edges by the given width.
+///
+/// The solid core of the path is surrounded by a fringe `feather` wide within which the alpha
+/// falls off to zero. The fringe is centred upon the edge of the path so that the path keeps its
+/// apparent weight. Paths thinner than the `feather` are drawn as a fringe with reduced alpha.
+///
+/// Returns `None` if the given iterator yields less than one point.
+pub fn triangles_anti_aliased<I>(
+    points: I,
+    cap: widget::line::Cap,
+    join: widget::line::Join,
+    thickness: Scalar,
+    feather: Scalar,
+    color: color::Rgba,
+) -> Option<impl Iterator<Item = Triangle<ColoredPoint>>>
+where
+    I: IntoIterator<Item = Point>,
+{
+    let path = Path::new(points)?;
+    let half_thickness = thickness / 2.0;
+    let half_feather = feather / 2.0;
+
+    // Paths thinner than the feather have no solid core and are faded in proportion.
+    let (inner, fringe, color) = if half_thickness > half_feather {
+        (half_thickness - half_feather, feather, color)
+    } else {
+        let color::Rgba(r, g, b, alpha) = color;
+        let fade = (half_thickness / half_feather) as f32;
+        (0.0, half_feather, color::Rgba(r, g, b, alpha * fade))
+    };
+    let transparent = color::Rgba(color.0, color.1, color.2, 0.0);
+
+    let mut triangles = Vec::new();
+    path.stroke(cap, join, inner, |part| match part {
+        Part::Triangle(Triangle([a, b, c])) => {
+            if inner > 0.0 {
+                triangles.push(Triangle([(a, color), (b, color), (c, color)]));
+            }
+        }
+        Part::Edge([a, b], [na, nb]) => {
+            let oa = vec2_add(a, vec2_scale(na, fringe));
+            let ob = vec2_add(b, vec2_scale(nb, fringe));
+            triangles.push(Triangle([(a, color), (b, color), (ob, transparent)]));
+            triangles.push(Triangle([(a, color), (ob, transparent), (oa, transparent)]));
+        }
+    });
+    Some(triangles.into_iter())
 }
 
 /// Returns whether or not the given point `p` lies over the `PointPath` described by the given
 /// points, line cap and thickness.
+///
+/// Corners are drawn with a `Join::Miter`. See `is_over_with_join`.
 pub fn is_over<I>(points: I, cap: widget::line::Cap, thickness: Scalar, p: Point) -> bool
 where
     I: IntoIterator<Item = Point>,
 {
-    triangles(points, cap, thickness)
-        .map(|ts| widget::triangles::is_over(ts, p))
-        .unwrap_or(false)
+    is_over_with_join(points, cap, Join::Miter, thickness, p)
+}
+
+/// Returns whether or not the given point `p` lies over the `PointPath` described by the given
+/// points, line cap, join and thickness.
+///
+/// Each triangle is tested as the path is stroked, rather than collecting them first.
+pub fn is_over_with_join<I>(
+    points: I,
+    cap: widget::line::Cap,
+    join: widget::line::Join,
+    thickness: Scalar,
+    p: Point,
+) -> bool
+where
+    I: IntoIterator<Item = Point>,
+{
+    let path = match Path::new(points) {
+        Some(path) => path,
+        None => return false,
+    };
+    let mut is_over = false;
+    path.stroke(cap, join, thickness / 2.0, |part| {
+        if let Part::Triangle(ref triangle) = part {
+            is_over = is_over || widget::triangles::is_over_triangle(triangle, p);
+        }
+    });
+    is_over
+}
+
+// A path with consecutive duplicate points removed, ready to be stroked.
+struct Path {
+    points: Vec<Point>,
+    closed: bool,
+}
+
+// A piece of a stroked path.
+enum Part {
+    // A triangle within the solid body of the stroke.
+    Triangle(Triangle<Point>),
+    // An edge along the outside of the stroke, along with the outward offset at either end.
+    //
+    // The offsets are scaled so that moving each end by its offset multiplied by some width
+    // yields an edge that lies the given width beyond the original.
+    Edge([Point; 2], [Point; 2]),
+}
+
+impl Path {
+    fn new<I>(points: I) -> Option<Self>
+    where
+        I: IntoIterator<Item = Point>,
+    {
+        let mut path: Vec<Point> = Vec::new();
+        for point in points {
+            if path.last().map_or(true, |&last| !coincident(last, point)) {
+                path.push(point);
+            }
+        }
+        if path.is_empty() {
+            return None;
+        }
+        let closed = path.len() > 2 && coincident(path[0], path[path.len() - 1]);
+        if closed {
+            path.pop();
+        }
+        Some(Path {
+            points: path,
+            closed: closed,
+        })
+    }
+
+    // Produce the parts describing the path stroked with the given cap, join and half thickness.
+    fn stroke<F>(&self, cap: Cap, join: Join, half_thickness: Scalar, mut part: F)
+    where
+        F: FnMut(Part),
+    {
+        let Path { ref points, closed } = *self;
+        let n = points.len();
+
+        // A lone point is only visible as a dot formed by its round caps.
+        if n == 1 {
+            if let Cap::Round = cap {
+                let full = 2.0 * std::f64::consts::PI;
+                arc(points[0], 0.0, full, half_thickness, &mut part);
+            }
+            return;
+        }
+
+        let next = |i: usize| points[(i + 1) % n];
+        let num_segments = if closed { n } else { n - 1 };
+        for i in 0..num_segments {
+            let (a, b) = (points[i], next(i));
+            let normal = perpendicular(direction(a, b));
+            let offset = vec2_scale(normal, half_thickness);
+            let (al, ar) = (vec2_add(a, offset), vec2_sub(a, offset));
+            let (bl, br) = (vec2_add(b, offset), vec2_sub(b, offset));
+            part(Part::Triangle(Triangle([al, bl, br])));
+            part(Part::Triangle(Triangle([al, br, ar])));
+            let opposite = vec2_scale(normal, -1.0);
+            part(Part::Edge([al, bl], [normal, normal]));
+            part(Part::Edge([br, ar], [opposite, opposite]));
+        }
+
+        let corners = if closed { 0..n } else { 1..n - 1 };
+        for i in corners {
+            let (prev, point) = (points[(i + n - 1) % n], points[i]);
+            let (d0, d1) = (direction(prev, point), direction(point, next(i)));
+            corner(point, d0, d1, join, half_thickness, &mut part);
+        }
+
+        if !closed {
+            let start = direction(points[1], points[0]);
+            let end = direction(points[n - 2], points[n - 1]);
+            end_cap(points[0], start, cap, half_thickness, &mut part);
+            end_cap(points[n - 1], end, cap, half_thickness, &mut part);
+        }
+    }
+}
+
+// Produce the parts for the join at `point` between segments travelling in directions `d0` and
+// `d1`.
+fn corner<F>(point: Point, d0: Point, d1: Point, join: Join, half_thickness: Scalar, part: &mut F)
+where
+    F: FnMut(Part),
+{
+    let cross = d0[0] * d1[1] - d0[1] * d1[0];
+    let dot = d0[0] * d1[0] + d0[1] * d1[1];
+    let reversed = cross.abs() < EPSILON;
+    if reversed && dot > 0.0 {
+        return;
+    }
+
+    // The gap between the segments lies on the outside of the turn.
+    let side = if cross > 0.0 { -1.0 } else { 1.0 };
+    let o0 = vec2_scale(perpendicular(d0), side);
+    let o1 = vec2_scale(perpendicular(d1), side);
+    let a = vec2_add(point, vec2_scale(o0, half_thickness));
+    let b = vec2_add(point, vec2_scale(o1, half_thickness));
+
+    match join {
+        Join::Round => {
+            let start = o0[1].atan2(o0[0]);
+            let sweep = if reversed {
+                -side * std::f64::consts::PI
+            } else {
+                let o_cross = o0[0] * o1[1] - o0[1] * o1[0];
+                o_cross.atan2(o0[0] * o1[0] + o0[1] * o1[1])
+            };
+            arc(point, start, sweep, half_thickness, part);
+        }
+        Join::Miter | Join::Bevel => {
+            // The miter offset lies along the bisector, one unit from either outer edge.
+            let bisector = vec2_add(o0, o1);
+            let len_squared = bisector[0] * bisector[0] + bisector[1] * bisector[1];
+            let miter = if join == Join::Miter && len_squared > 4.0 / (MITER_LIMIT * MITER_LIMIT) {
+                Some(vec2_scale(bisector, 2.0 / len_squared))
+            } else {
+                None
+            };
+            match miter {
+                Some(miter) => {
+                    let m = vec2_add(point, vec2_scale(miter, half_thickness));
+                    part(Part::Triangle(Triangle([point, a, m])));
+                    part(Part::Triangle(Triangle([point, m, b])));
+                    part(Part::Edge([a, m], [o0, miter]));
+                    part(Part::Edge([m, b], [miter, o1]));
+                }
+                None => {
+                    part(Part::Triangle(Triangle([point, a, b])));
+                    part(Part::Edge([a, b], [o0, o1]));
+                }
+            }
+        }
+    }
+}
+
+// Produce the parts for the cap at the end `point` of a path leaving in the given `outward`
+// direction.
+fn end_cap<F>(point: Point, outward: Point, cap: Cap, half_thickness: Scalar, part: &mut F)
+where
+    F: FnMut(Part),
+{
+    let normal = perpendicular(outward);
+    match cap {
+        Cap::Flat => {
+            let offset = vec2_scale(normal, half_thickness);
+            let ends = [vec2_add(point, offset), vec2_sub(point, offset)];
+            part(Part::Edge(ends, [outward, outward]));
+        }
+        Cap::Round => {
+            let start = normal[1].atan2(normal[0]);
+            arc(point, start, -std::f64::consts::PI, half_thickness, part);
+        }
+    }
+}
+
+// Produce the parts for a circular arc about `centre` beginning at the `start` angle and spanning
+// the given `sweep`, both in radians.
+fn arc<F>(centre: Point, start: Scalar, sweep: Scalar, radius: Scalar, part: &mut F)
+where
+    F: FnMut(Part),
+{
+    let steps = std::cmp::max((sweep.abs() / ROUND_STEP).ceil() as usize, 1);
+    let unit = |step: usize| {
+        let angle = start + sweep * step as Scalar / steps as Scalar;
+        [angle.cos(), angle.sin()]
+    };
+    let mut prev = unit(0);
+    for step in 1..steps + 1 {
+        let next = unit(step);
+        let a = vec2_add(centre, vec2_scale(prev, radius));
+        let b = vec2_add(centre, vec2_scale(next, radius));
+        part(Part::Triangle(Triangle([centre, a, b])));
+        part(Part::Edge([a, b], [prev, next]));
+        prev = next;
+    }
+}
+
+fn coincident(a: Point, b: Point) -> bool {
+    (a[0] - b[0]).abs() < EPSILON && (a[1] - b[1]).abs() < EPSILON
+}
+
+// The unit vector pointing from `a` to `b`.
+fn direction(a: Point, b: Point) -> Point {
+    let [x, y] = vec2_sub(b, a);
+    let mag = (x * x + y * y).sqrt();
+    [x / mag, y / mag]
+}
+
+fn vec2_scale([x, y]: Point, scale: Scalar) -> Point {
+    [x * scale, y * scale]
+}
+
+// The given vector rotated a quarter turn anti-clockwise.
+fn perpendicular([x, y]: Point) -> Point {
+    [-y, x]
 }
 
 /// The function to use for picking whether a given point is over the point path.
@@ -276,8 +577,15 @@ pub fn is_over_widget(widget: &graph::Container, point: Point, theme: &Theme) ->
         .state_and_style::<State, Style>()
         .map(|widget| {
             let cap = widget.style.get_cap(theme);
+            let join = widget.style.get_join(theme);
             let thickness = widget.style.get_thickness(theme);
-            is_over(widget.state.points.iter().cloned(), cap, thickness, point)
+            is_over_with_join(
+                widget.state.points.iter().cloned(),
+                cap,
+                join,
+                thickness,
+                point,
+            )
         })
         .unwrap_or_else(|| widget.rect.is_over(point))
         .into()
@@ -1,10 +1,11 @@
 //! A simple, non-interactive widget for drawing a single **Oval**.
 
 use super::Style;
+use color;
 use graph;
 use std;
 use widget;
-use widget::triangles::Triangle;
+use widget::triangles::{ColoredPoint, Triangle};
 use {Color, Colorable, Dimensions, Point, Rect, Scalar, Sizeable, Theme, Widget};
 
 /// A simple, non-interactive widget for drawing a single **Oval**.
@@ -195,6 +196,17 @@ pub fn triangles(rect: Rect, resolution: usize) -> Triangles {
     circumference(rect, resolution).triangles()
 }
 
+/// The triangles that describe the given oval with edges feathered by the given width, as
+/// described by `polygon::triangles_anti_aliased`.
+pub fn triangles_anti_aliased(
+    rect: Rect,
+    resolution: usize,
+    feather: Scalar,
+    color: color::Rgba,
+) -> impl Iterator<Item = Triangle<ColoredPoint>> {
+    circumference(rect, resolution).triangles_anti_aliased(feather, color)
+}
+
 /// An iterator yielding the edges of an `Oval` (or some section of an `Oval`) as a circumference
 /// represented as a series of edges.
 #[derive(Clone)]
@@ -279,6 +291,27 @@ impl Circumference {
         let last = self.next().unwrap_or(self.point);
         Triangles { last, points: self }
     }
+
+    /// Produces the `Triangle`s describing the `Oval` (or section) with edges feathered by the
+    /// given width, as described by `polygon::triangles_anti_aliased`.
+    pub fn triangles_anti_aliased(
+        self,
+        feather: Scalar,
+        color: color::Rgba,
+    ) -> impl Iterator<Item = Triangle<ColoredPoint>> {
+        use std::f64::consts::PI;
+        // Sections are closed via the middle of the `Oval`.
+        let radians = self.rad_step * (self.num_points - 1) as Scalar;
+        let middle = if radians.abs() < 2.0 * PI - 1e-6 {
+            Some(self.point)
+        } else {
+            None
+        };
+        let points = middle.into_iter().chain(self);
+        widget::polygon::triangles_anti_aliased(points, feather, color)
+            .into_iter()
+            .flat_map(|triangles| triangles)
+    }
 }
 
 impl Iterator for Circumference {
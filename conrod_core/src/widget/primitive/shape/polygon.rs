@@ -1,11 +1,12 @@
 //! A simple, non-interactive **Polygon** widget for drawing arbitrary convex shapes.
 
 use super::Style;
+use color;
 use graph;
 use utils::{bounding_box_for_points, vec2_add, vec2_sub};
 use widget;
-use widget::triangles::Triangle;
-use {Color, Colorable, Point, Positionable, Sizeable, Theme, Widget};
+use widget::triangles::{ColoredPoint, Triangle};
+use {Color, Colorable, Point, Positionable, Scalar, Sizeable, Theme, Widget};
 
 /// A basic, non-interactive, arbitrary **Polygon** widget.
 ///
@@ -317,6 +318,76 @@ where
     }
 }
 
+/// Triangulate the polygon given as a list of `Point`s describing its sides, surrounding it with a
+/// fringe of the given `feather` width within which the alpha falls off to zero.
+///
+/// The fringe is centred upon the edges of the polygon so that the shape keeps its apparent size.
+///
+/// Returns `None` if the given iterator yields less than three distinct points.
+pub fn triangles_anti_aliased<I>(
+    points: I,
+    feather: Scalar,
+    color: color::Rgba,
+) -> Option<impl Iterator<Item = Triangle<ColoredPoint>>>
+where
+    I: IntoIterator<Item = Point>,
+{
+    let mut points: Vec<Point> = points.into_iter().collect();
+    points.dedup();
+    if points.len() > 1 && points.first() == points.last() {
+        points.pop();
+    }
+    let n = points.len();
+    if n < 3 {
+        return None;
+    }
+
+    // Determine the winding so that the edge normals point away from the polygon.
+    let area: Scalar = (0..n)
+        .map(|i| {
+            let (a, b) = (points[i], points[(i + 1) % n]);
+            a[0] * b[1] - b[0] * a[1]
+        })
+        .sum();
+    let winding = if area < 0.0 { -1.0 } else { 1.0 };
+    let edge_normal = |a: Point, b: Point| {
+        let (dx, dy) = (b[0] - a[0], b[1] - a[1]);
+        let mag = (dx * dx + dy * dy).sqrt();
+        [winding * dy / mag, -winding * dx / mag]
+    };
+
+    // Offset each vertex along the average of its adjacent edge normals, lengthened so that the
+    // fringe keeps a constant width along each edge.
+    let half_feather = feather * 0.5;
+    let mut inner = Vec::with_capacity(n);
+    let mut outer = Vec::with_capacity(n);
+    for i in 0..n {
+        let (prev, p, next) = (points[(i + n - 1) % n], points[i], points[(i + 1) % n]);
+        let (n1, n2) = (edge_normal(prev, p), edge_normal(p, next));
+        let mut dm = [(n1[0] + n2[0]) * 0.5, (n1[1] + n2[1]) * 0.5];
+        let dm_len_sq = dm[0] * dm[0] + dm[1] * dm[1];
+        if dm_len_sq > 1e-6 {
+            let scale = (1.0 / dm_len_sq).min(100.0);
+            dm = [dm[0] * scale, dm[1] * scale];
+        }
+        let offset = [dm[0] * half_feather, dm[1] * half_feather];
+        inner.push(([p[0] - offset[0], p[1] - offset[1]], color));
+        outer.push((
+            [p[0] + offset[0], p[1] + offset[1]],
+            color::Rgba(color.0, color.1, color.2, 0.0),
+        ));
+    }
+
+    let mut triangles = Vec::with_capacity(n - 2 + n * 2);
+    triangles.extend((1..n - 1).map(|i| Triangle([inner[0], inner[i], inner[i + 1]])));
+    for i in 0..n {
+        let j = (i + 1) % n;
+        triangles.push(Triangle([inner[i], outer[i], outer[j]]));
+        triangles.push(Triangle([inner[i], outer[j], inner[j]]));
+    }
+    Some(triangles.into_iter())
+}
+
 /// Returns `true` if the given `Point` is over the polygon described by the given series of
 /// points.
 pub fn is_over<I>(points: I, point: Point) -> bool
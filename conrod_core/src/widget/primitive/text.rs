@@ -20,6 +20,8 @@ pub struct Text<'a> {
     pub common: widget::CommonBuilder,
     /// The text to be drawn by the **Text**.
     pub text: &'a str,
+    /// Ranges of the text that should be drawn with a colour or font other than the default.
+    pub highlights: &'a [Highlight],
     /// Unique styling for the **Text**.
     pub style: Style,
 }
@@ -59,6 +61,21 @@ pub enum Wrap {
     Whitespace,
 }
 
+/// Styling for a range of bytes within the **Text** that overrides its default colour and font.
+///
+/// Highlights only affect how glyphs are drawn. The text is always laid out (wrapped, measured,
+/// etc) using the **Text**'s own font, so a highlight's font should share the metrics of the
+/// **Text**'s font (e.g. a bold or italic variant of a monospaced font) to avoid overlapping glyphs.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Highlight {
+    /// The range of bytes within the text to which the highlight applies.
+    pub byte_range: std::ops::Range<usize>,
+    /// The colour used for the highlighted glyphs. When `None`, the **Text**'s colour is used.
+    pub color: Option<Color>,
+    /// The font used for the highlighted glyphs. When `None`, the **Text**'s font is used.
+    pub font_id: Option<text::font::Id>,
+}
+
 // /// Line styling for the **Text**.
 // pub enum Line {
 //     /// Underline the text.
@@ -76,6 +93,8 @@ pub struct State {
     pub string: String,
     /// The indices and width for each line of text within the `string`.
     pub line_infos: Vec<text::line::Info>,
+    /// The highlighted ranges of the `string`, sorted by the start of their byte range.
+    pub highlights: Vec<Highlight>,
 }
 
 impl<'a> Text<'a> {
@@ -84,10 +103,20 @@ impl<'a> Text<'a> {
         Text {
             common: widget::CommonBuilder::default(),
             text: text,
+            highlights: &[],
             style: Style::default(),
         }
     }
 
+    /// Draw the given ranges of the text with a colour and/or font other than the default.
+    ///
+    /// The highlights must be sorted by the start of their byte range. Ranges should not overlap.
+    /// Where they do, which highlight applies is unspecified.
+    pub fn highlights(mut self, highlights: &'a [Highlight]) -> Self {
+        self.highlights = highlights;
+        self
+    }
+
     /// Specify that the **Text** should not wrap lines around the width.
     pub fn no_line_wrap(mut self) -> Self {
         self.style.maybe_wrap = Some(None);
@@ -149,6 +178,7 @@ impl<'a> Widget for Text<'a> {
         State {
             string: String::new(),
             line_infos: Vec::new(),
+            highlights: Vec::new(),
         }
     }
 
@@ -227,7 +257,9 @@ impl<'a> Widget for Text<'a> {
             ui,
             ..
         } = args;
-        let Text { text, .. } = self;
+        let Text {
+            text, highlights, ..
+        } = self;
 
         let maybe_wrap = style.maybe_wrap(ui.theme());
         let font_size = style.font_size(ui.theme());
@@ -277,6 +309,17 @@ impl<'a> Widget for Text<'a> {
                 state.update(|state| state.line_infos = new_line_infos);
             }
         }
+
+        // Update the highlights if they have changed.
+        debug_assert!(
+            highlights
+                .windows(2)
+                .all(|w| w[0].byte_range.start <= w[1].byte_range.start),
+            "`Text` highlights must be sorted by the start of their byte range"
+        );
+        if &state.highlights[..] != highlights {
+            state.update(|state| state.highlights = highlights.to_vec());
+        }
     }
 }
 
@@ -24,10 +24,12 @@ pub struct RoundedRectangle {
     pub radius: Scalar,
     /// The number of points in each corner of the circle used to draw the rounded corners.
     pub corner_resolution: usize,
+    /// The shadow cast by the rectangle, if any.
+    pub maybe_shadow: Option<widget::drop_shadow::Shadow>,
 }
 
 widget_ids! {
-    struct Ids { polygon }
+    struct Ids { shadow, polygon }
 }
 
 /// The default resolution of the circle used to draw the rounded corners.
@@ -46,6 +48,7 @@ impl RoundedRectangle {
             style: style,
             radius: radius,
             corner_resolution: DEFAULT_CORNER_RESOLUTION,
+            maybe_shadow: None,
         }
         .wh(dim)
     }
@@ -79,6 +82,12 @@ impl RoundedRectangle {
         self.corner_resolution = res;
         self
     }
+
+    /// Cast the given shadow beneath the rounded rectangle.
+    pub fn shadow(mut self, shadow: widget::drop_shadow::Shadow) -> Self {
+        self.maybe_shadow = Some(shadow);
+        self
+    }
 }
 
 impl Widget for RoundedRectangle {
@@ -113,8 +122,16 @@ impl Widget for RoundedRectangle {
         let RoundedRectangle {
             radius,
             corner_resolution,
+            maybe_shadow,
             ..
         } = self;
+        if let Some(shadow) = maybe_shadow {
+            widget::DropShadow::cast_by(rect, shadow)
+                .corner_radius(radius)
+                .parent(id)
+                .graphics_for(id)
+                .set(state.ids.shadow, ui);
+        }
         let points = points(rect, radius, corner_resolution);
         let (x, y, w, h) = rect.x_y_w_h();
         widget::Polygon::styled(points, *style)
@@ -0,0 +1,203 @@
+//! The `SubUi` widget and related items.
+
+use event::{self, Input};
+use graph;
+use image;
+use input::{self, Motion};
+use position::{Dimensions, Point, Rect};
+use widget;
+use {Positionable, Sizeable, Widget};
+
+/// Displays a `Ui` that has been rendered to a texture and remaps the input received by the
+/// widget into the co-ordinate space of that `Ui`.
+///
+/// The embedded `Ui` is owned by the user and is otherwise independent of the `Ui` within which
+/// the **SubUi** is instantiated. It has its own window dimensions and DPI factor, while the
+/// texture is inserted into the parent's `image::Map` under the given `image::Id`. A typical
+/// frame looks like:
+///
+/// 1. Feed each of the `Input`s yielded by the **SubUi** to the embedded `Ui` via `handle_event`.
+/// 2. Update the embedded `Ui`'s widgets as usual.
+/// 3. Render the embedded `Ui` into the texture using a renderer dedicated to it, passing the
+///    texture's own dimensions and DPI factor rather than those of the window.
+///
+/// The same remapping is useful for screens placed within a 3D scene, where the texture
+/// co-ordinates under a ray may be converted with the `point_from_tex_coords` function.
+///
+/// Only a whole `Ui` may be rendered to a texture. A widget subtree that should be rendered
+/// offscreen must be instantiated within an embedded `Ui` of its own.
+///
+/// See the glium backend's `sub_ui` example for a demonstration.
+#[derive(Copy, Clone, Debug, WidgetCommon_)]
+pub struct SubUi {
+    /// Data necessary and common for all widget builder types.
+    #[conrod(common_builder)]
+    pub common: widget::CommonBuilder,
+    /// The texture to which the embedded `Ui` is rendered.
+    pub image_id: image::Id,
+    /// The window dimensions of the embedded `Ui` in points.
+    pub dimensions: Dimensions,
+}
+
+widget_ids! {
+    struct Ids {
+        image,
+    }
+}
+
+/// Unique state for the **SubUi**.
+pub struct State {
+    ids: Ids,
+}
+
+/// Unique styling for the **SubUi**.
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+pub struct Style;
+
+impl SubUi {
+    /// Build a new **SubUi** showing the given texture of an embedded `Ui` whose window has the
+    /// given dimensions in points.
+    pub fn new(image_id: image::Id, dimensions: Dimensions) -> Self {
+        SubUi {
+            common: widget::CommonBuilder::default(),
+            image_id: image_id,
+            dimensions: dimensions,
+        }
+    }
+}
+
+impl Widget for SubUi {
+    type State = State;
+    type Style = Style;
+    /// The input for the embedded `Ui`, given within its own co-ordinate space.
+    type Event = Vec<Input>;
+
+    fn init_state(&self, id_gen: widget::id::Generator) -> Self::State {
+        State {
+            ids: Ids::new(id_gen),
+        }
+    }
+
+    fn style(&self) -> Self::Style {
+        Style
+    }
+
+    fn update(self, args: widget::UpdateArgs<Self>) -> Self::Event {
+        let widget::UpdateArgs {
+            id,
+            state,
+            rect,
+            ui,
+            ..
+        } = args;
+        let SubUi {
+            image_id,
+            dimensions,
+            ..
+        } = self;
+
+        widget::Image::new(image_id)
+            .wh_of(id)
+            .middle_of(id)
+            .graphics_for(id)
+            .set(state.ids.image, ui);
+
+        // Maps a point relative to the centre of the widget into the embedded `Ui`.
+        let scale = |len, sub_len| if len > 0.0 { sub_len / len } else { 0.0 };
+        let [sx, sy] = [
+            scale(rect.w(), dimensions[0]),
+            scale(rect.h(), dimensions[1]),
+        ];
+        let to_sub = |xy: Point| [xy[0] * sx, xy[1] * sy];
+
+        // Maps an absolute point within the parent `Ui` into the embedded `Ui`.
+        let absolute_to_sub = |xy: Point| {
+            graph::algo::untransformed_point(ui.widget_graph(), id, xy)
+                .map(|xy| to_sub([xy[0] - rect.x(), xy[1] - rect.y()]))
+        };
+        let cursor = |xy: Point| Input::Motion(Motion::MouseCursor { x: xy[0], y: xy[1] });
+
+        let mut inputs = vec![];
+        for event in ui.widget_input(id).events() {
+            match event {
+                event::Widget::Motion(event::Motion { motion, .. }) => match motion {
+                    Motion::MouseCursor { x, y } => {
+                        if let Some(xy) = absolute_to_sub([x, y]) {
+                            inputs.push(cursor(xy));
+                        }
+                    }
+                    Motion::MouseRelative { x, y } => {
+                        let [x, y] = to_sub([x, y]);
+                        inputs.push(Input::Motion(Motion::MouseRelative { x: x, y: y }));
+                    }
+                    Motion::Scroll { x, y } => {
+                        let [x, y] = to_sub([x, y]);
+                        inputs.push(Input::Motion(Motion::Scroll { x: x, y: y }));
+                    }
+                    Motion::ControllerAxis(_) => inputs.push(Input::Motion(motion)),
+                },
+
+                // Mouse buttons are preceded by the cursor position so that the embedded `Ui`
+                // knows which of its widgets were pressed.
+                event::Widget::Press(press) => match press.button {
+                    event::Button::Mouse(button, xy) => {
+                        inputs.push(cursor(to_sub(xy)));
+                        inputs.push(Input::Press(input::Button::Mouse(button)));
+                    }
+                    event::Button::Keyboard(key) => {
+                        inputs.push(Input::Press(input::Button::Keyboard(key)))
+                    }
+                    event::Button::Controller(button) => {
+                        inputs.push(Input::Press(input::Button::Controller(button)))
+                    }
+                },
+                event::Widget::Release(release) => match release.button {
+                    event::Button::Mouse(button, xy) => {
+                        inputs.push(cursor(to_sub(xy)));
+                        inputs.push(Input::Release(input::Button::Mouse(button)));
+                    }
+                    event::Button::Keyboard(key) => {
+                        inputs.push(Input::Release(input::Button::Keyboard(key)))
+                    }
+                    event::Button::Controller(button) => {
+                        inputs.push(Input::Release(input::Button::Controller(button)))
+                    }
+                },
+
+                event::Widget::Text(text) => inputs.push(Input::Text(text.string)),
+
+                event::Widget::Touch(touch) => inputs.push(Input::Touch(input::Touch {
+                    xy: to_sub(touch.xy),
+                    ..touch
+                })),
+
+                // Let the embedded `Ui` know that the mouse has left its window.
+                event::Widget::UncapturesInputSource(input::Source::Mouse) => {
+                    let xy = ui.global_input().current.mouse.xy;
+                    if let Some(xy) = absolute_to_sub(xy) {
+                        inputs.push(cursor(xy));
+                    }
+                }
+
+                // Clicks, drags, etc are produced by the embedded `Ui` itself.
+                _ => (),
+            }
+        }
+
+        inputs
+    }
+}
+
+/// Convert texture co-ordinates on the embedded `Ui`'s texture into a point within the
+/// embedded `Ui`'s window with the given dimensions.
+///
+/// The texture co-ordinates range from `0.0` to `1.0` with the origin at the bottom left, while
+/// the resulting point has its origin at the centre of the window, as with all conrod input.
+pub fn point_from_tex_coords(tex_coords: [f32; 2], dimensions: Dimensions) -> Point {
+    let rect = Rect::from_xy_dim([0.0, 0.0], dimensions);
+    let [u, v] = tex_coords;
+    [
+        rect.left() + u as f64 * rect.w(),
+        rect.bottom() + v as f64 * rect.h(),
+    ]
+}
@@ -9,21 +9,55 @@ use std;
 use text;
 use utils;
 use widget;
-use widget::primitive::text::Wrap;
-use {Color, Colorable, FontSize, Positionable, Sizeable, Ui, Widget};
+use widget::primitive::text::{Highlight, Wrap};
+use {Color, Colorable, FontSize, Positionable, Sizeable, Theme, Ui, Widget};
 
 /// A widget for displaying and mutating multi-line text, given as a `String`.
 ///
 /// By default the text is wrapped via the first whitespace before the line exceeds the
 /// `TextEdit`'s width, however a user may change this using the `.wrap_by_character` method.
+///
+/// The `TextEdit` may also be switched into "code mode" via the `.code_mode` method, in which case
+/// a gutter of line numbers is displayed, `Tab` and `Shift+Tab` indent and outdent the selected
+/// lines and `Return` automatically indents the new line to match the line above.
+///
+/// A `Highlighter` may be given via the `.highlighter` method in order to draw ranges of the text
+/// (e.g. keywords) with their own colour or font.
+///
+/// A `TextEdit` may also be made `read_only`, in which case its text may be selected and copied
+/// but not edited. This is useful for displaying text that users may want to copy, such as error
+/// messages or IDs.
+///
+/// The text may be searched via the `.find` method, in which case every match is highlighted and
+/// may be stepped through and replaced via `.find_next`, `.find_previous`, `.replace` and
+/// `.replace_all`. While capturing the keyboard, `Ctrl+F` searches for the selected text, `F3` (or
+/// `Ctrl+G`) selects the next match, `Shift+F3` (or `Ctrl+Shift+G`) selects the previous match and
+/// `Escape` clears the search.
 #[derive(WidgetCommon_)]
-pub struct TextEdit<'a> {
+pub struct TextEdit<'a, H = HighlighterFnPtr> {
     #[conrod(common_builder)]
     common: widget::CommonBuilder,
     text: &'a str,
+    maybe_highlighter: Option<H>,
+    maybe_search: Option<&'a str>,
+    maybe_search_action: Option<SearchAction<'a>>,
     style: Style,
 }
 
+/// Types that can determine the `Highlight`s for the text of a `TextEdit`.
+///
+/// This is implemented for all functions of the form `FnMut(&str) -> Vec<Highlight>`.
+pub trait Highlighter {
+    /// Produce the highlighted byte ranges of the given text.
+    ///
+    /// This is called during each update of the `TextEdit` with the latest text, after all edits
+    /// for that update have been applied.
+    fn highlight(&mut self, text: &str) -> Vec<Highlight>;
+}
+
+/// An alias for the function pointer used as the default `TextEdit` highlighter type.
+pub type HighlighterFnPtr = fn(&str) -> Vec<Highlight>;
+
 /// Unique graphical styling for the TextEdit.
 #[derive(Copy, Clone, Debug, Default, PartialEq, WidgetStyle_)]
 pub struct Style {
@@ -51,6 +85,24 @@ pub struct Style {
     /// The font used for the `Text`.
     #[conrod(default = "theme.font_id")]
     pub font_id: Option<Option<text::font::Id>>,
+    /// Whether or not the `TextEdit` behaves as a code editor.
+    #[conrod(default = "false")]
+    pub code_mode: Option<bool>,
+    /// Whether or not a gutter displaying line numbers is drawn while in code mode.
+    #[conrod(default = "true")]
+    pub line_numbers: Option<bool>,
+    /// The number of columns between each tab stop while in code mode.
+    #[conrod(default = "4")]
+    pub tab_width: Option<usize>,
+    /// Whether or not `Return` indents the new line to match the previous line in code mode.
+    #[conrod(default = "true")]
+    pub auto_indent: Option<bool>,
+    /// Whether or not the text may only be selected and copied, rather than edited.
+    #[conrod(default = "false")]
+    pub read_only: Option<bool>,
+    /// Whether or not searching distinguishes between upper and lower case characters.
+    #[conrod(default = "false")]
+    pub match_case: Option<bool>,
 }
 
 widget_ids! {
@@ -58,6 +110,8 @@ widget_ids! {
         selected_rectangles[],
         text,
         cursor,
+        line_numbers,
+        matches,
     }
 }
 
@@ -68,9 +122,20 @@ pub struct State {
     drag: Option<Drag>,
     /// Information about each line of text.
     line_infos: Vec<text::line::Info>,
+    /// The search query entered via `Ctrl+F`, used when no query is given via `TextEdit::find`.
+    search_query: Option<String>,
     ids: Ids,
 }
 
+/// An action to perform upon the matches of the search query during the next update.
+#[derive(Copy, Clone, Debug, PartialEq)]
+enum SearchAction<'a> {
+    FindNext,
+    FindPrevious,
+    Replace(&'a str),
+    ReplaceAll(&'a str),
+}
+
 /// Track whether some sort of dragging is currently occurring.
 #[derive(Copy, Clone, Debug, PartialEq)]
 pub enum Drag {
@@ -105,9 +170,80 @@ impl<'a> TextEdit<'a> {
             common: widget::CommonBuilder::default(),
             style: Style::default(),
             text: text,
+            maybe_highlighter: None,
+            maybe_search: None,
+            maybe_search_action: None,
+        }
+    }
+}
+
+impl<'a, H> TextEdit<'a, H> {
+    /// Specify a `Highlighter` used to colour ranges of the text, e.g. for syntax highlighting.
+    ///
+    /// Highlights only affect how the text is drawn. Editing, wrapping and cursor positioning are
+    /// always performed using the `TextEdit`'s own font.
+    pub fn highlighter<F>(self, highlighter: F) -> TextEdit<'a, F>
+    where
+        F: Highlighter,
+    {
+        let TextEdit {
+            common,
+            text,
+            maybe_search,
+            maybe_search_action,
+            style,
+            ..
+        } = self;
+        TextEdit {
+            common,
+            text,
+            maybe_search,
+            maybe_search_action,
+            style,
+            maybe_highlighter: Some(highlighter),
         }
     }
 
+    /// Search the text for all occurrences of the given query, highlighting each match.
+    ///
+    /// Matches are found by character and never overlap. Whether or not the search is case
+    /// sensitive is determined by `match_case`. An empty query matches nothing.
+    ///
+    /// When no query is given, the text selected when `Ctrl+F` was last pressed is used instead.
+    pub fn find(mut self, query: &'a str) -> Self {
+        self.maybe_search = Some(query);
+        self
+    }
+
+    /// Select the first match of the search query following the cursor during this update,
+    /// wrapping around to the start of the text if necessary.
+    ///
+    /// This is useful for the "next" button of a search bar.
+    pub fn find_next(mut self) -> Self {
+        self.maybe_search_action = Some(SearchAction::FindNext);
+        self
+    }
+
+    /// Select the last match of the search query preceding the cursor during this update,
+    /// wrapping around to the end of the text if necessary.
+    pub fn find_previous(mut self) -> Self {
+        self.maybe_search_action = Some(SearchAction::FindPrevious);
+        self
+    }
+
+    /// If the current selection is a match of the search query, replace it with the given
+    /// `replacement` during this update. The following match is then selected.
+    pub fn replace(mut self, replacement: &'a str) -> Self {
+        self.maybe_search_action = Some(SearchAction::Replace(replacement));
+        self
+    }
+
+    /// Replace every match of the search query with the given `replacement` during this update.
+    pub fn replace_all(mut self, replacement: &'a str) -> Self {
+        self.maybe_search_action = Some(SearchAction::ReplaceAll(replacement));
+        self
+    }
+
     /// The `TextEdit` will wrap text via the whitespace that precedes the first width-exceeding
     /// character.
     ///
@@ -165,6 +301,18 @@ impl<'a> TextEdit<'a> {
         self
     }
 
+    /// Switch the `TextEdit` into "code mode".
+    ///
+    /// In code mode:
+    ///
+    /// - A gutter displaying line numbers is drawn along the left edge (see `line_numbers`).
+    /// - `Tab` and `Shift+Tab` indent and outdent the selected lines by `tab_width` spaces.
+    /// - `Return` indents the new line to match the line above (see `auto_indent`).
+    /// - Monospaced fonts are laid out via the cheaper `text::line::infos_monospace` path.
+    pub fn code_mode(self) -> Self {
+        self.code(true)
+    }
+
     builder_methods! {
         pub font_size { style.font_size = Some(FontSize) }
         pub justify { style.justify = Some(text::Justify) }
@@ -172,10 +320,19 @@ impl<'a> TextEdit<'a> {
         pub line_wrap { style.line_wrap = Some(Wrap) }
         pub line_spacing { style.line_spacing = Some(Scalar) }
         pub restrict_to_height { style.restrict_to_height = Some(bool) }
+        pub code { style.code_mode = Some(bool) }
+        pub line_numbers { style.line_numbers = Some(bool) }
+        pub tab_width { style.tab_width = Some(usize) }
+        pub auto_indent { style.auto_indent = Some(bool) }
+        pub match_case { style.match_case = Some(bool) }
+        pub read_only { style.read_only = Some(bool) }
     }
 }
 
-impl<'a> Widget for TextEdit<'a> {
+impl<'a, H> Widget for TextEdit<'a, H>
+where
+    H: Highlighter,
+{
     type State = State;
     type Style = Style;
     // TODO: We should create a more specific `Event` type that:
@@ -189,6 +346,7 @@ impl<'a> Widget for TextEdit<'a> {
             cursor: Cursor::Idx(text::cursor::Index { line: 0, char: 0 }),
             drag: None,
             line_infos: Vec::new(),
+            search_query: None,
             ids: Ids::new(id_gen),
         }
     }
@@ -218,14 +376,15 @@ impl<'a> Widget for TextEdit<'a> {
 
         let text = &self.text;
         let font_size = self.style.font_size(&ui.theme);
+        let gutter_w = gutter_width(&self.style, &ui.theme, text, font, font_size);
         let num_lines = match self.get_w(ui) {
             None => text.lines().count(),
             Some(max_w) => match self.style.line_wrap(&ui.theme) {
                 Wrap::Character => text::line::infos(text, font, font_size)
-                    .wrap_by_character(max_w)
+                    .wrap_by_character(max_w - gutter_w)
                     .count(),
                 Wrap::Whitespace => text::line::infos(text, font, font_size)
-                    .wrap_by_whitespace(max_w)
+                    .wrap_by_whitespace(max_w - gutter_w)
                     .count(),
             },
         };
@@ -244,7 +403,13 @@ impl<'a> Widget for TextEdit<'a> {
             ui,
             ..
         } = args;
-        let TextEdit { text, .. } = self;
+        let TextEdit {
+            text,
+            maybe_highlighter,
+            maybe_search,
+            maybe_search_action,
+            ..
+        } = self;
         let mut text = std::borrow::Cow::Borrowed(text);
 
         // Retrieve the `font_id`, as long as a valid `Font` for it still exists.
@@ -265,6 +430,25 @@ impl<'a> Widget for TextEdit<'a> {
         let y_align = style.y_align(ui.theme());
         let line_spacing = style.line_spacing(ui.theme());
         let restrict_to_height = style.restrict_to_height(ui.theme());
+        let code_mode = style.code_mode(ui.theme());
+        let tab_width = std::cmp::max(style.tab_width(ui.theme()), 1);
+        let auto_indent = style.auto_indent(ui.theme());
+        let match_case = style.match_case(ui.theme());
+        let read_only = style.read_only(ui.theme());
+
+        // In code mode, text is laid out to the right of the line number gutter and monospaced
+        // fonts may take the cheaper monospace layout path.
+        let (rect, gutter_rect, monospace) = {
+            let font = ui.fonts.get(font_id).unwrap();
+            let gutter_w = gutter_width(style, ui.theme(), &text, font, font_size);
+            let gutter_rect = Rect {
+                x: Range::new(rect.left(), rect.left() + gutter_w),
+                y: rect.y,
+            };
+            let monospace =
+                code_mode && text::line::monospace_advance_width(font, font_size).is_some();
+            (rect.pad_left(gutter_w), gutter_rect, monospace)
+        };
 
         /// Returns an iterator yielding the `text::line::Info` for each line in the given text
         /// with the given styling.
@@ -275,8 +459,13 @@ impl<'a> Widget for TextEdit<'a> {
             font_size: FontSize,
             line_wrap: Wrap,
             max_width: Scalar,
+            monospace: bool,
         ) -> LineInfos<'a> {
-            let infos = text::line::infos(text, font, font_size);
+            let infos = if monospace {
+                text::line::infos_monospace(text, font, font_size)
+            } else {
+                text::line::infos(text, font, font_size)
+            };
             match line_wrap {
                 Wrap::Whitespace => infos.wrap_by_whitespace(max_width),
                 Wrap::Character => infos.wrap_by_character(max_width),
@@ -288,7 +477,8 @@ impl<'a> Widget for TextEdit<'a> {
             let maybe_new_line_infos = {
                 let line_info_slice = &state.line_infos[..];
                 let font = ui.fonts.get(font_id).unwrap();
-                let new_line_infos = line_infos(&text, font, font_size, line_wrap, rect.w());
+                let new_line_infos =
+                    line_infos(&text, font, font_size, line_wrap, rect.w(), monospace);
                 match utils::write_if_different(line_info_slice, new_line_infos) {
                     std::borrow::Cow::Owned(new) => Some(new),
                     _ => None,
@@ -433,7 +623,7 @@ impl<'a> Widget for TextEdit<'a> {
 
             // Calculate the new `line_infos` for the `new_text`.
             let new_line_infos: Vec<_> =
-                { line_infos(&new_text, font, font_size, line_wrap, rect.w()).collect() };
+                line_infos(&new_text, font, font_size, line_wrap, rect.w(), monospace).collect();
 
             // Check that the new text would not exceed the `inner_rect` bounds.
             let num_lines = new_line_infos.len();
@@ -456,6 +646,21 @@ impl<'a> Widget for TextEdit<'a> {
             }
         };
 
+        // Produces the `line::Info`s for the given `new_text`.
+        //
+        // Returns `None` if the new text would exceed the height restriction.
+        let line_infos_within_height =
+            |new_text: &str, font: &text::Font| -> Option<std::vec::Vec<text::line::Info>> {
+                let new_line_infos: Vec<_> =
+                    line_infos(new_text, font, font_size, line_wrap, rect.w(), monospace).collect();
+                let height = text::height(new_line_infos.len(), font_size, line_spacing);
+                if height < rect.h() || !restrict_to_height {
+                    Some(new_line_infos)
+                } else {
+                    None
+                }
+            };
+
         // Check for the following events:
         // - `Text` events for receiving new text.
         // - Left mouse 'DoubleClick' to select a word.
@@ -528,6 +733,10 @@ impl<'a> Widget for TextEdit<'a> {
                         // If `Cursor::Idx`, remove the `char` behind the cursor.
                         // If `Cursor::Selection`, remove the selected text.
                         input::Key::Backspace | input::Key::Delete => {
+                            if read_only {
+                                continue 'events;
+                            }
+
                             let delete_word =
                                 press.modifiers.contains(input::keyboard::ModifierKey::CTRL);
 
@@ -586,7 +795,8 @@ impl<'a> Widget for TextEdit<'a> {
                                     let font = ui.fonts.get(font_id).unwrap();
                                     let w = rect.w();
                                     state.line_infos =
-                                        line_infos(&text, font, font_size, line_wrap, w).collect();
+                                        line_infos(&text, font, font_size, line_wrap, w, monospace)
+                                            .collect();
                                 });
                             }
                         }
@@ -767,7 +977,9 @@ impl<'a> Widget for TextEdit<'a> {
 
                         input::Key::V => {
                             // Paste selected text at the current cursor position on ctrl+v.
-                            if press.modifiers.contains(input::keyboard::ModifierKey::CTRL) {
+                            if !read_only
+                                && press.modifiers.contains(input::keyboard::ModifierKey::CTRL)
+                            {
                                 let mut clipboard: ClipboardContext =
                                     ClipboardContext::new().unwrap();
                                 let font = ui.fonts.get(font_id).unwrap();
@@ -827,8 +1039,23 @@ impl<'a> Widget for TextEdit<'a> {
                         }
 
                         input::Key::Return => {
+                            if read_only {
+                                continue 'events;
+                            }
+
                             let font = ui.fonts.get(font_id).unwrap();
-                            match insert_text("\n", cursor, &text, &state.line_infos, font) {
+                            let mut newline = String::from("\n");
+                            if code_mode && auto_indent {
+                                let start = match cursor {
+                                    Cursor::Idx(idx) => idx,
+                                    Cursor::Selection { start, end } => std::cmp::min(start, end),
+                                };
+                                let line_infos = state.line_infos.iter().cloned();
+                                let char_idx =
+                                    text::glyph::index_after_cursor(line_infos, start).unwrap_or(0);
+                                newline.push_str(&line_indentation(&text, char_idx));
+                            }
+                            match insert_text(&newline, cursor, &text, &state.line_infos, font) {
                                 Some((new_text, new_cursor, new_line_infos)) => {
                                     *text.to_mut() = new_text;
                                     cursor = new_cursor;
@@ -838,6 +1065,121 @@ impl<'a> Widget for TextEdit<'a> {
                             }
                         }
 
+                        input::Key::Tab => {
+                            // Indent or outdent the selected lines in code mode.
+                            if !code_mode || read_only {
+                                continue 'events;
+                            }
+
+                            let font = ui.fonts.get(font_id).unwrap();
+                            let outdent = press
+                                .modifiers
+                                .contains(input::keyboard::ModifierKey::SHIFT);
+                            let (start, end) = match cursor {
+                                Cursor::Idx(idx) => (idx, idx),
+                                Cursor::Selection { start, end } => (start, end),
+                            };
+                            let (start_idx, end_idx) = {
+                                let line_infos = state.line_infos.iter().cloned();
+                                (
+                                    text::glyph::index_after_cursor(line_infos.clone(), start),
+                                    text::glyph::index_after_cursor(line_infos, end),
+                                )
+                            };
+                            let (start_idx, end_idx) = match (start_idx, end_idx) {
+                                (Some(start_idx), Some(end_idx)) => (start_idx, end_idx),
+                                _ => continue 'events,
+                            };
+
+                            // Without a selection, `Tab` inserts spaces up to the next tab stop.
+                            if let (Cursor::Idx(_), false) = (cursor, outdent) {
+                                let column = column_of_char(&text, start_idx);
+                                let num_spaces = tab_width - column % tab_width;
+                                let spaces: String =
+                                    std::iter::repeat(' ').take(num_spaces).collect();
+                                if let Some((new_text, new_cursor, new_line_infos)) =
+                                    insert_text(&spaces, cursor, &text, &state.line_infos, font)
+                                {
+                                    *text.to_mut() = new_text;
+                                    cursor = new_cursor;
+                                    state.update(|state| state.line_infos = new_line_infos);
+                                }
+                                continue 'events;
+                            }
+
+                            let (new_text, new_start_idx, new_end_idx) =
+                                indent_lines(&text, start_idx, end_idx, tab_width, outdent);
+                            if new_text == *text {
+                                continue 'events;
+                            }
+                            if let Some(new_line_infos) = line_infos_within_height(&new_text, font)
+                            {
+                                let index_before_char = |char_idx| {
+                                    let line_infos = new_line_infos.iter().cloned();
+                                    text::cursor::index_before_char(line_infos, char_idx)
+                                        .expect("char index was out of range")
+                                };
+                                cursor = match cursor {
+                                    Cursor::Idx(_) => Cursor::Idx(index_before_char(new_start_idx)),
+                                    Cursor::Selection { .. } => Cursor::Selection {
+                                        start: index_before_char(new_start_idx),
+                                        end: index_before_char(new_end_idx),
+                                    },
+                                };
+                                *text.to_mut() = new_text;
+                                state.update(|state| state.line_infos = new_line_infos);
+                            }
+                        }
+
+                        input::Key::F => {
+                            // Search for the selected text on Ctrl+f.
+                            if press.modifiers.contains(input::keyboard::ModifierKey::CTRL) {
+                                let infos = &state.line_infos;
+                                let range = cursor_byte_range(cursor, &text, infos);
+                                if let Some((start_byte, end_byte)) = range {
+                                    if start_byte < end_byte {
+                                        let query = text[start_byte..end_byte].to_string();
+                                        if state.search_query.as_ref() != Some(&query) {
+                                            state.update(|state| state.search_query = Some(query));
+                                        }
+                                    }
+                                }
+                            }
+                        }
+
+                        input::Key::F3 | input::Key::G => {
+                            // Select the next match on F3 or Ctrl+g, or the previous match if
+                            // Shift is held.
+                            if key == input::Key::G
+                                && !press.modifiers.contains(input::keyboard::ModifierKey::CTRL)
+                            {
+                                continue 'events;
+                            }
+                            let query = match maybe_search
+                                .map(String::from)
+                                .or_else(|| state.search_query.clone())
+                            {
+                                Some(query) => query,
+                                None => continue 'events,
+                            };
+                            let forward = !press
+                                .modifiers
+                                .contains(input::keyboard::ModifierKey::SHIFT);
+                            let infos = &state.line_infos;
+                            if let Some(new_cursor) =
+                                adjacent_match(&text, infos, cursor, &query, match_case, forward)
+                            {
+                                cursor = new_cursor;
+                            }
+                        }
+
+                        input::Key::Escape => {
+                            // Clear the search query entered via Ctrl+f.
+                            if state.search_query.is_some() {
+                                state.update(|state| state.search_query = None);
+                            }
+                        }
+
                         _ => (),
                     },
 
@@ -852,7 +1194,8 @@ impl<'a> Widget for TextEdit<'a> {
                 }
 
                 event::Widget::Text(event::Text { string, modifiers }) => {
-                    if modifiers.contains(input::keyboard::ModifierKey::CTRL)
+                    if read_only
+                        || modifiers.contains(input::keyboard::ModifierKey::CTRL)
                         || string.chars().count() == 0
                         || string.chars().next().is_none()
                     {
@@ -918,6 +1261,72 @@ impl<'a> Widget for TextEdit<'a> {
             }
         }
 
+        // The search query, if any, and its matches within the text.
+        let search_query = maybe_search
+            .map(String::from)
+            .or_else(|| state.search_query.clone());
+
+        // Apply any search action requested via the builder methods.
+        if let (Some(action), Some(query)) = (maybe_search_action, search_query.as_ref()) {
+            let font = ui.fonts.get(font_id).unwrap();
+            match action {
+                SearchAction::FindNext | SearchAction::FindPrevious => {
+                    let forward = action == SearchAction::FindNext;
+                    let infos = &state.line_infos;
+                    if let Some(new_cursor) =
+                        adjacent_match(&text, infos, cursor, query, match_case, forward)
+                    {
+                        cursor = new_cursor;
+                    }
+                }
+
+                SearchAction::Replace(_) | SearchAction::ReplaceAll(_) if read_only => (),
+
+                SearchAction::Replace(replacement) => {
+                    let matches = find_matches(&text, query, match_case);
+                    let is_match = cursor_byte_range(cursor, &text, &state.line_infos)
+                        .map(|(start, end)| matches.iter().any(|m| *m == (start..end)))
+                        .unwrap_or(false);
+                    if is_match {
+                        if let Some((new_text, new_cursor, new_line_infos)) =
+                            insert_text(replacement, cursor, &text, &state.line_infos, font)
+                        {
+                            *text.to_mut() = new_text;
+                            cursor = new_cursor;
+                            state.update(|state| state.line_infos = new_line_infos);
+                        }
+                    }
+                    let infos = &state.line_infos;
+                    if let Some(new_cursor) =
+                        adjacent_match(&text, infos, cursor, query, match_case, true)
+                    {
+                        cursor = new_cursor;
+                    }
+                }
+
+                SearchAction::ReplaceAll(replacement) => {
+                    let matches = find_matches(&text, query, match_case);
+                    if !matches.is_empty() {
+                        let new_text = replace_matches(&text, &matches, replacement);
+                        if let Some(new_line_infos) = line_infos_within_height(&new_text, font) {
+                            // Keep the cursor at the same char index where possible.
+                            let char_idx = cursor_byte_range(cursor, &text, &state.line_infos)
+                                .map(|(start, _)| text[..start].chars().count())
+                                .unwrap_or(0);
+                            let char_idx = std::cmp::min(char_idx, new_text.chars().count());
+                            let line_infos = new_line_infos.iter().cloned();
+                            let new_cursor_idx =
+                                text::cursor::index_before_char(line_infos, char_idx)
+                                    .expect("char index was out of range");
+                            cursor = Cursor::Idx(new_cursor_idx);
+                            *text.to_mut() = new_text;
+                            state.update(|state| state.line_infos = new_line_infos);
+                        }
+                    }
+                }
+            }
+        }
+
         if let Some(_) = ui.widget_input(id).mouse() {
             ui.set_mouse_cursor(cursor::MouseCursor::Text);
         }
@@ -949,10 +1358,21 @@ impl<'a> Widget for TextEdit<'a> {
             y: text_y_range,
         };
 
+        // `Text` requires its highlights to be sorted by the start of their byte range.
+        let highlights = match maybe_highlighter {
+            Some(mut highlighter) => {
+                let mut highlights = highlighter.highlight(&text);
+                highlights.sort_by_key(|highlight| highlight.byte_range.start);
+                highlights
+            }
+            None => Vec::new(),
+        };
+
         match line_wrap {
             Wrap::Whitespace => widget::Text::new(&text).wrap_by_word(),
             Wrap::Character => widget::Text::new(&text).wrap_by_character(),
         }
+        .highlights(&highlights)
         .font_id(font_id)
         .wh(text_rect.dim())
         .xy(text_rect.xy())
@@ -964,6 +1384,89 @@ impl<'a> Widget for TextEdit<'a> {
         .font_size(font_size)
         .set(state.ids.text, ui);
 
+        // Draw the line number for the first row of each line within the gutter.
+        if gutter_rect.w() > 0.0 {
+            let mut line_numbers = String::new();
+            let mut line_number = 0;
+            let mut starts_line = true;
+            for (i, info) in state.line_infos.iter().enumerate() {
+                if i > 0 {
+                    line_numbers.push('\n');
+                }
+                if starts_line {
+                    line_number += 1;
+                    line_numbers.push_str(&line_number.to_string());
+                }
+                starts_line = match info.end_break {
+                    text::line::Break::Newline { .. } => true,
+                    _ => false,
+                };
+            }
+            let gutter_pad = font_size as Scalar / 2.0;
+            widget::Text::new(&line_numbers)
+                .no_line_wrap()
+                .font_id(font_id)
+                .w_h(gutter_rect.w() - gutter_pad, text_rect.h())
+                .x_y(gutter_rect.x() - gutter_pad / 2.0, text_rect.y())
+                .right_justify()
+                .parent(id)
+                .graphics_for(id)
+                .color(color.alpha(0.5))
+                .line_spacing(line_spacing)
+                .font_size(font_size)
+                .set(state.ids.line_numbers, ui);
+        }
+
+        // Highlight every match of the search query, emphasising the currently selected match.
+        let matches = match search_query {
+            Some(ref query) => find_matches(&text, query, match_case),
+            None => Vec::new(),
+        };
+        if !matches.is_empty() {
+            let selected_range = cursor_byte_range(cursor, &text, &state.line_infos);
+            let match_triangles = {
+                let font = ui.fonts.get(font_id).unwrap();
+                let line_infos = state.line_infos.iter().cloned();
+                let lines = line_infos.clone().map(|info| &text[info.byte_range()]);
+                let line_rects =
+                    text::line::rects(line_infos, font_size, rect, justify, y_align, line_spacing);
+                let lines_with_rects = lines.zip(line_rects);
+                let selected_color = color.highlighted().alpha(0.4).to_rgb();
+                let match_color = color.highlighted().alpha(0.15).to_rgb();
+                let mut triangles = Vec::new();
+                let rects = match_rects(
+                    &text,
+                    &state.line_infos,
+                    lines_with_rects,
+                    font,
+                    font_size,
+                    &matches,
+                );
+                for (match_idx, match_rect) in rects {
+                    let m = &matches[match_idx];
+                    let is_selected = selected_range == Some((m.start, m.end));
+                    let match_color = if is_selected {
+                        selected_color
+                    } else {
+                        match_color
+                    };
+                    let (l, r, b, t) = match_rect.l_r_b_t();
+                    let (first, second) =
+                        widget::triangles::from_quad([[l, t], [r, t], [r, b], [l, b]]);
+                    triangles.push(first.color_all(match_color));
+                    triangles.push(second.color_all(match_color));
+                }
+                triangles
+            };
+
+            // Draw all matches via a single `Triangles` widget.
+            widget::Triangles::multi_color(match_triangles)
+                .with_bounding_rect(rect)
+                .graphics_for(id)
+                .parent(id)
+                .set(state.ids.matches, ui);
+        }
+
         // Draw the line for the cursor.
         let cursor_idx = match cursor {
             Cursor::Idx(idx) => idx,
@@ -975,44 +1478,48 @@ impl<'a> Widget for TextEdit<'a> {
             return take_if_owned(text);
         }
 
-        let (cursor_x, cursor_y_range) = {
-            let font = ui.fonts.get(font_id).unwrap();
-            cursor_xy_at(cursor_idx, &text, &state.line_infos, font).unwrap_or_else(|| {
-                let x = rect.left();
-                let y = Range::new(0.0, font_size as Scalar).align_to(y_align, rect.y);
-                (x, y)
-            })
-        };
+        // Read-only text has no cursor.
+        if !read_only {
+            let (cursor_x, cursor_y_range) = {
+                let font = ui.fonts.get(font_id).unwrap();
+                cursor_xy_at(cursor_idx, &text, &state.line_infos, font).unwrap_or_else(|| {
+                    let x = rect.left();
+                    let y = Range::new(0.0, font_size as Scalar).align_to(y_align, rect.y);
+                    (x, y)
+                })
+            };
 
-        let start = [0.0, cursor_y_range.start];
-        let end = [0.0, cursor_y_range.end];
-        let prev_cursor_rect = ui.rect_of(state.ids.cursor);
-        widget::Line::centred(start, end)
-            .x_y(cursor_x, cursor_y_range.middle())
-            .graphics_for(id)
-            .parent(id)
-            .color(color)
-            .set(state.ids.cursor, ui);
-
-        // If the cursor position has changed due to input AND one of our parent widgets are
-        // scrollable AND the change in cursor position would cause the cursor to fall outside the
-        // scrollable parent's `Rect`, attempt to scroll the scrollable parent so that the cursor
-        // would be visible.
-        if cursor_has_changed {
-            let cursor_rect = ui.rect_of(state.ids.cursor).unwrap();
-            if prev_cursor_rect != Some(cursor_rect) {
-                use graph::Walker;
-                let mut scrollable_parents = ui.widget_graph().scrollable_y_parent_recursion(id);
-                if let Some(parent_id) = scrollable_parents.next_node(ui.widget_graph()) {
-                    if let Some(parent_rect) = ui.rect_of(parent_id) {
-                        // If cursor is below, scroll down.
-                        if cursor_rect.bottom() < parent_rect.bottom() {
-                            let distance = parent_rect.bottom() - cursor_rect.bottom();
-                            ui.scroll_widget(parent_id, [0.0, distance]);
-                        // If cursor is above, scroll up.
-                        } else if cursor_rect.top() > parent_rect.top() {
-                            let distance = cursor_rect.top() - parent_rect.top();
-                            ui.scroll_widget(parent_id, [0.0, -distance]);
+            let start = [0.0, cursor_y_range.start];
+            let end = [0.0, cursor_y_range.end];
+            let prev_cursor_rect = ui.rect_of(state.ids.cursor);
+            widget::Line::centred(start, end)
+                .x_y(cursor_x, cursor_y_range.middle())
+                .graphics_for(id)
+                .parent(id)
+                .color(color)
+                .set(state.ids.cursor, ui);
+
+            // If the cursor position has changed due to input AND one of our parent widgets are
+            // scrollable AND the change in cursor position would cause the cursor to fall outside
+            // the scrollable parent's `Rect`, attempt to scroll the scrollable parent so that the
+            // cursor would be visible.
+            if cursor_has_changed {
+                let cursor_rect = ui.rect_of(state.ids.cursor).unwrap();
+                if prev_cursor_rect != Some(cursor_rect) {
+                    use graph::Walker;
+                    let mut scrollable_parents =
+                        ui.widget_graph().scrollable_y_parent_recursion(id);
+                    if let Some(parent_id) = scrollable_parents.next_node(ui.widget_graph()) {
+                        if let Some(parent_rect) = ui.rect_of(parent_id) {
+                            // If cursor is below, scroll down.
+                            if cursor_rect.bottom() < parent_rect.bottom() {
+                                let distance = parent_rect.bottom() - cursor_rect.bottom();
+                                ui.scroll_widget(parent_id, [0.0, distance]);
+                            // If cursor is above, scroll up.
+                            } else if cursor_rect.top() > parent_rect.top() {
+                                let distance = cursor_rect.top() - parent_rect.top();
+                                ui.scroll_widget(parent_id, [0.0, -distance]);
+                            }
                         }
                     }
                 }
@@ -1062,6 +1569,397 @@ impl<'a> Widget for TextEdit<'a> {
     }
 }
 
-impl<'a> Colorable for TextEdit<'a> {
+impl<F> Highlighter for F
+where
+    F: FnMut(&str) -> Vec<Highlight>,
+{
+    fn highlight(&mut self, text: &str) -> Vec<Highlight> {
+        (*self)(text)
+    }
+}
+
+impl<'a, H> Colorable for TextEdit<'a, H> {
     builder_method!(color { style.color = Some(Color) });
 }
+
+/// The width of the line number gutter for the given text, or `0.0` if no gutter is displayed.
+fn gutter_width(
+    style: &Style,
+    theme: &Theme,
+    text: &str,
+    font: &text::Font,
+    font_size: FontSize,
+) -> Scalar {
+    if !style.code_mode(theme) || !style.line_numbers(theme) {
+        return 0.0;
+    }
+    // Reserve room for at least two digits so that the gutter does not resize for short texts.
+    let num_digits = std::cmp::max(text.split('\n').count().to_string().len(), 2);
+    let widest_number: String = std::iter::repeat('0').take(num_digits).collect();
+    text::line::width(&widest_number, font, font_size) + font_size as Scalar
+}
+
+/// The byte index within `text` of the given cursor `Index`.
+fn byte_at_cursor(
+    text: &str,
+    line_infos: &[text::line::Info],
+    idx: text::cursor::Index,
+) -> Option<usize> {
+    let info = line_infos.get(idx.line)?;
+    let line = &text[info.byte_range()];
+    line.char_indices()
+        .map(|(i, _)| i)
+        .chain(Some(line.len()))
+        .nth(idx.char)
+        .map(|i| info.start_byte + i)
+}
+
+/// The cursor `Index` at the given byte index within `text`.
+///
+/// Like `text::cursor::index_before_char`, the first line containing the byte is used.
+fn cursor_at_byte(
+    text: &str,
+    line_infos: &[text::line::Info],
+    byte: usize,
+) -> Option<text::cursor::Index> {
+    line_infos
+        .iter()
+        .enumerate()
+        .find(|&(_, info)| info.start_byte <= byte && byte <= info.end_byte())
+        .map(|(line, info)| text::cursor::Index {
+            line: line,
+            char: text[info.start_byte..byte].chars().count(),
+        })
+}
+
+/// The range of bytes covered by the given `cursor`, ordered from lowest to highest.
+fn cursor_byte_range(
+    cursor: Cursor,
+    text: &str,
+    line_infos: &[text::line::Info],
+) -> Option<(usize, usize)> {
+    let (start, end) = match cursor {
+        Cursor::Idx(idx) => (idx, idx),
+        Cursor::Selection { start, end } => (start, end),
+    };
+    let start = byte_at_cursor(text, line_infos, start)?;
+    let end = byte_at_cursor(text, line_infos, end)?;
+    Some((std::cmp::min(start, end), std::cmp::max(start, end)))
+}
+
+/// The length in bytes of the occurrence of `query` at the start of `text`, if there is one.
+fn match_len(text: &str, query: &str, match_case: bool) -> Option<usize> {
+    if match_case {
+        return if text.starts_with(query) {
+            Some(query.len())
+        } else {
+            None
+        };
+    }
+    let mut chars = text.char_indices();
+    for q in query.chars() {
+        let (_, ch) = chars.next()?;
+        if ch != q && !ch.to_lowercase().eq(q.to_lowercase()) {
+            return None;
+        }
+    }
+    Some(chars.next().map(|(i, _)| i).unwrap_or(text.len()))
+}
+
+/// The byte index ranges of all non-overlapping occurrences of `query` within `text`.
+fn find_matches(text: &str, query: &str, match_case: bool) -> Vec<std::ops::Range<usize>> {
+    let mut matches = Vec::new();
+    if query.is_empty() {
+        return matches;
+    }
+    let mut start = 0;
+    while let Some(ch) = text[start..].chars().next() {
+        match match_len(&text[start..], query, match_case) {
+            Some(len) => {
+                matches.push(start..start + len);
+                start += len;
+            }
+            None => start += ch.len_utf8(),
+        }
+    }
+    matches
+}
+
+/// Replace each of the given byte index ranges (as produced by `find_matches`) with
+/// `replacement`.
+fn replace_matches(text: &str, matches: &[std::ops::Range<usize>], replacement: &str) -> String {
+    let mut new_text = String::with_capacity(text.len());
+    let mut end = 0;
+    for m in matches {
+        new_text.push_str(&text[end..m.start]);
+        new_text.push_str(replacement);
+        end = m.end;
+    }
+    new_text.push_str(&text[end..]);
+    new_text
+}
+
+/// Produce a selection of the match of `query` that follows (or precedes if `forward` is `false`)
+/// the given `cursor`, wrapping around the text if necessary.
+fn adjacent_match(
+    text: &str,
+    line_infos: &[text::line::Info],
+    cursor: Cursor,
+    query: &str,
+    match_case: bool,
+    forward: bool,
+) -> Option<Cursor> {
+    let matches = find_matches(text, query, match_case);
+    let (start, end) = cursor_byte_range(cursor, text, line_infos)?;
+    let m = if forward {
+        matches.iter().find(|m| m.start >= end).or(matches.first())
+    } else {
+        matches
+            .iter()
+            .rev()
+            .find(|m| m.end <= start)
+            .or(matches.last())
+    }?;
+    Some(Cursor::Selection {
+        start: cursor_at_byte(text, line_infos, m.start)?,
+        end: cursor_at_byte(text, line_infos, m.end)?,
+    })
+}
+
+/// A `Rect` for each run of characters covered by the given `matches` (as produced by
+/// `find_matches`) on each line, along with the index of the match covering it.
+///
+/// The lines spanned by the matches are laid out only once via
+/// `text::glyph::selected_rects_per_line`.
+fn match_rects<'a, I>(
+    text: &str,
+    line_infos: &[text::line::Info],
+    lines_with_rects: I,
+    font: &'a text::Font,
+    font_size: FontSize,
+    matches: &[std::ops::Range<usize>],
+) -> Vec<(usize, Rect)>
+where
+    I: Iterator<Item = (&'a str, Rect)>,
+{
+    let mut rects: Vec<(usize, Rect)> = Vec::new();
+    let (first, last) = match (matches.first(), matches.last()) {
+        (Some(first), Some(last)) => (first, last),
+        _ => return rects,
+    };
+    let start = match cursor_at_byte(text, line_infos, first.start) {
+        Some(idx) => idx,
+        None => return rects,
+    };
+    let end = match cursor_at_byte(text, line_infos, last.end) {
+        Some(idx) => idx,
+        None => return rects,
+    };
+    let selected_rects_per_line =
+        text::glyph::selected_rects_per_line(lines_with_rects, font, font_size, start, end);
+    let mut match_idx = 0;
+    let lines = selected_rects_per_line.zip(line_infos).enumerate();
+    for (i, (char_rects, info)) in lines.take(end.line + 1).skip(start.line) {
+        let line_start = rects.len();
+        let skip = if i == start.line { start.char } else { 0 };
+        let char_bytes = text[info.byte_range()].char_indices().skip(skip);
+        for ((byte, _), char_rect) in char_bytes.zip(char_rects) {
+            let byte = info.start_byte + byte;
+            while matches
+                .get(match_idx)
+                .map(|m| m.end <= byte)
+                .unwrap_or(false)
+            {
+                match_idx += 1;
+            }
+            match matches.get(match_idx) {
+                Some(m) if m.start <= byte => (),
+                Some(_) => continue,
+                None => return rects,
+            }
+            // Extend the rect of the previous character if it belongs to the same match.
+            let extends_previous = rects.len() > line_start
+                && rects
+                    .last()
+                    .map(|&(idx, _)| idx == match_idx)
+                    .unwrap_or(false);
+            if extends_previous {
+                let last = rects.len() - 1;
+                rects[last].1.x.end = char_rect.x.end;
+            } else {
+                rects.push((match_idx, char_rect));
+            }
+        }
+    }
+    rects
+}
+
+/// The column of the character at the given `char_idx` within its line.
+fn column_of_char(text: &str, char_idx: usize) -> usize {
+    text.chars()
+        .take(char_idx)
+        .fold(0, |column, ch| if ch == '\n' { 0 } else { column + 1 })
+}
+
+/// The leading whitespace of the line containing the given `char_idx`, up to `char_idx`.
+fn line_indentation(text: &str, char_idx: usize) -> String {
+    let column = column_of_char(text, char_idx);
+    text.chars()
+        .skip(char_idx - column)
+        .take(column)
+        .take_while(|&ch| ch == ' ' || ch == '\t')
+        .collect()
+}
+
+/// Indent (or outdent) every line touched by the characters between `start` and `end` by
+/// `tab_width` spaces.
+///
+/// Outdenting removes either a single leading tab or up to `tab_width` leading spaces.
+///
+/// Returns the new text along with the `start` and `end` char indices adjusted to the new text.
+fn indent_lines(
+    text: &str,
+    start: usize,
+    end: usize,
+    tab_width: usize,
+    outdent: bool,
+) -> (String, usize, usize) {
+    let (min, max) = (std::cmp::min(start, end), std::cmp::max(start, end));
+    let mut new_text = String::with_capacity(text.len());
+    let (mut new_start, mut new_end) = (start, end);
+    let mut line_start = 0;
+    let mut new_line_start = 0;
+    for (i, line) in text.split('\n').enumerate() {
+        if i > 0 {
+            new_text.push('\n');
+            new_line_start += 1;
+        }
+        let line_len = line.chars().count();
+        let line_end = line_start + line_len;
+
+        // A selection ending at the very start of a line does not touch that line.
+        let is_touched = line_start <= max && min <= line_end && !(max > min && max == line_start);
+        let (added, removed) = match (is_touched, outdent) {
+            (false, _) => (0, 0),
+            (true, false) => (tab_width, 0),
+            (true, true) => match line.chars().next() {
+                Some('\t') => (0, 1),
+                _ => (
+                    0,
+                    line.chars()
+                        .take(tab_width)
+                        .take_while(|&ch| ch == ' ')
+                        .count(),
+                ),
+            },
+        };
+
+        // Shift the indices that lie within this line.
+        let shift =
+            |idx: usize| new_line_start + added + (idx - line_start).saturating_sub(removed);
+        if line_start <= start && start <= line_end {
+            new_start = shift(start);
+        }
+        if line_start <= end && end <= line_end {
+            new_end = shift(end);
+        }
+
+        new_text.extend(std::iter::repeat(' ').take(added));
+        new_text.extend(line.chars().skip(removed));
+        new_line_start += added + line_len - removed;
+        line_start = line_end + 1;
+    }
+    (new_text, new_start, new_end)
+}
+
+#[test]
+fn test_indent_lines() {
+    let text = "foo\n  bar\nbaz";
+
+    // Indenting a cursor shifts only its own line.
+    assert_eq!(
+        indent_lines(text, 5, 5, 4, false),
+        ("foo\n      bar\nbaz".into(), 9, 9)
+    );
+
+    // A selection across lines indents each touched line.
+    let (new_text, start, end) = indent_lines(text, 1, 8, 2, false);
+    assert_eq!(new_text, "  foo\n    bar\nbaz");
+    assert_eq!((start, end), (3, 12));
+
+    // A selection ending at the start of a line does not touch that line.
+    assert_eq!(indent_lines(text, 0, 4, 2, false).0, "  foo\n  bar\nbaz");
+
+    // Outdenting removes at most `tab_width` leading spaces.
+    assert_eq!(
+        indent_lines(text, 6, 6, 4, true),
+        ("foo\nbar\nbaz".into(), 4, 4)
+    );
+    assert_eq!(indent_lines("\t\tx", 3, 3, 4, true), ("\tx".into(), 2, 2));
+}
+
+#[test]
+fn test_find_and_replace_matches() {
+    let text = "Foo foo fOO bar foofoo";
+    assert_eq!(find_matches(text, "foo", true), vec![4..7, 16..19, 19..22]);
+    assert_eq!(find_matches(text, "foo", false).len(), 5);
+    assert_eq!(find_matches(text, "", false), vec![]);
+    assert_eq!(find_matches("aaaa", "aa", true), vec![0..2, 2..4]);
+
+    let matches = find_matches(text, "foo", false);
+    assert_eq!(
+        replace_matches(text, &matches, "baz"),
+        "baz baz baz bar bazbaz"
+    );
+    let matches = find_matches("ßa ßa", "ßa", true);
+    assert_eq!(matches, vec![0..3, 4..7]);
+    assert_eq!(replace_matches("ßa ßa", &matches, ""), " ");
+    assert_eq!(find_matches("ÀB àb", "àb", false), vec![0..3, 4..7]);
+}
+
+#[test]
+fn test_match_rects() {
+    let path = concat!(
+        env!("CARGO_MANIFEST_DIR"),
+        "/../assets/fonts/NotoSans/NotoSans-Regular.ttf"
+    );
+    let font = text::font::from_file(path).unwrap();
+    let font_size = 14;
+    let text = "foo bar\nbär foo foo";
+    let line_infos: Vec<_> = text::line::infos(text, &font, font_size).collect();
+    let lines = line_infos.iter().map(|info| &text[info.byte_range()]);
+    let rect = Rect::from_xy_dim([0.0, 0.0], [400.0, 100.0]);
+    let line_rects = text::line::rects(
+        line_infos.iter().cloned(),
+        font_size,
+        rect,
+        text::Justify::Left,
+        Align::End,
+        0.0,
+    );
+    let matches = find_matches(text, "foo", true);
+    let rects = match_rects(
+        text,
+        &line_infos,
+        lines.zip(line_rects),
+        &font,
+        font_size,
+        &matches,
+    );
+
+    // A single rect per match, as wide as the matched text.
+    let match_indices: Vec<_> = rects.iter().map(|&(idx, _)| idx).collect();
+    assert_eq!(match_indices, vec![0, 1, 2]);
+    let foo_w = text::line::width("foo", &font, font_size);
+    for &(_, r) in &rects {
+        assert!((r.w() - foo_w).abs() < 2.0);
+    }
+    assert!(rects[0].1.y != rects[1].1.y);
+    assert_eq!(rects[1].1.y, rects[2].1.y);
+    assert!(rects[1].1.right() < rects[2].1.left());
+
+    // Byte indices convert to and from cursor indices across lines.
+    let second_line = text::cursor::Index { line: 1, char: 4 };
+    assert_eq!(byte_at_cursor(text, &line_infos, second_line), Some(13));
+    assert_eq!(cursor_at_byte(text, &line_infos, 13), Some(second_line));
+}